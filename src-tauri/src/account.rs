@@ -3,6 +3,24 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use std::path::PathBuf;
 
+use crate::account_health::AccountHealth;
+use crate::providers::is_idc_provider;
+use crate::usage::UsageSummary;
+
+/// 无法获取 email 时使用的占位值（不参与按 email 去重）
+pub const UNKNOWN_EMAIL: &str = "unknown@kiro.dev";
+
+/// 应用数据目录（accounts.json、用量历史等都存放在这里）
+pub fn get_app_data_dir() -> PathBuf {
+    let data_dir = dirs::data_dir().unwrap_or_else(|| {
+        let home = std::env::var("USERPROFILE")
+            .or_else(|_| std::env::var("HOME"))
+            .unwrap_or_else(|_| ".".to_string());
+        PathBuf::from(home)
+    });
+    data_dir.join(".kiro-account-manager")
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Account {
@@ -25,12 +43,28 @@ pub struct Account {
     pub client_secret: Option<String>,
     pub region: Option<String>,
     pub client_id_hash: Option<String>,
+    /// 企业 IdC 的组织 start URL（BuilderId 为空）
+    pub start_url: Option<String>,
     pub sso_session_id: Option<String>,
     pub id_token: Option<String>,
     // Social 专用
     pub profile_arn: Option<String>,
-    // 原始 usage API 响应
+    // 归一化后的用量
+    pub usage: Option<UsageSummary>,
+    // 原始 usage API 响应（仅用于调试）
     pub usage_data: Option<serde_json::Value>,
+    // 分组与标记
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub group: Option<String>,
+    pub notes: Option<String>,
+    #[serde(default)]
+    pub pinned: bool,
+    // 健康状态（status 为其显示文案）
+    #[serde(default)]
+    pub health: AccountHealth,
+    pub last_checked_at: Option<String>,
+    pub last_error: Option<String>,
 }
 
 
@@ -54,11 +88,122 @@ impl Account {
             client_secret: None,
             region: None,
             client_id_hash: None,
+            start_url: None,
             sso_session_id: None,
             id_token: None,
             profile_arn: None,
+            usage: None,
             usage_data: None,
+            tags: Vec::new(),
+            group: None,
+            notes: None,
+            pinned: false,
+            health: AccountHealth::Active,
+            last_checked_at: None,
+            last_error: None,
+        }
+    }
+
+    /// 用另一条记录中非空的凭据字段覆盖当前账号（保留 id、label、added_at 等元数据）
+    pub fn merge_credentials_from(&mut self, other: &Account) {
+        fn take(target: &mut Option<String>, source: &Option<String>) {
+            if source.as_ref().map(|s| !s.is_empty()).unwrap_or(false) {
+                *target = source.clone();
+            }
         }
+        take(&mut self.access_token, &other.access_token);
+        take(&mut self.refresh_token, &other.refresh_token);
+        take(&mut self.csrf_token, &other.csrf_token);
+        take(&mut self.session_token, &other.session_token);
+        take(&mut self.expires_at, &other.expires_at);
+        take(&mut self.provider, &other.provider);
+        take(&mut self.user_id, &other.user_id);
+        take(&mut self.client_id, &other.client_id);
+        take(&mut self.client_secret, &other.client_secret);
+        take(&mut self.region, &other.region);
+        take(&mut self.client_id_hash, &other.client_id_hash);
+        take(&mut self.start_url, &other.start_url);
+        take(&mut self.sso_session_id, &other.sso_session_id);
+        take(&mut self.id_token, &other.id_token);
+        take(&mut self.profile_arn, &other.profile_arn);
+    }
+
+    /// 只补齐当前账号中为空的字段（字段级合并）
+    pub fn fill_missing_from(&mut self, other: &Account) {
+        fn fill(target: &mut Option<String>, source: &Option<String>) {
+            if target.as_ref().map(|s| s.is_empty()).unwrap_or(true) && source.is_some() {
+                *target = source.clone();
+            }
+        }
+        fill(&mut self.access_token, &other.access_token);
+        fill(&mut self.refresh_token, &other.refresh_token);
+        fill(&mut self.csrf_token, &other.csrf_token);
+        fill(&mut self.session_token, &other.session_token);
+        fill(&mut self.expires_at, &other.expires_at);
+        fill(&mut self.provider, &other.provider);
+        fill(&mut self.user_id, &other.user_id);
+        fill(&mut self.client_id, &other.client_id);
+        fill(&mut self.client_secret, &other.client_secret);
+        fill(&mut self.region, &other.region);
+        fill(&mut self.client_id_hash, &other.client_id_hash);
+        fill(&mut self.start_url, &other.start_url);
+        fill(&mut self.sso_session_id, &other.sso_session_id);
+        fill(&mut self.id_token, &other.id_token);
+        fill(&mut self.profile_arn, &other.profile_arn);
+        if self.email == UNKNOWN_EMAIL && other.email != UNKNOWN_EMAIL {
+            self.email = other.email.clone();
+        }
+        if self.usage.is_none() {
+            self.usage = other.usage.clone();
+            self.usage_data = other.usage_data.clone();
+        }
+        fill(&mut self.group, &other.group);
+        fill(&mut self.notes, &other.notes);
+        for tag in &other.tags {
+            if !self.tags.contains(tag) {
+                self.tags.push(tag.clone());
+            }
+        }
+    }
+
+    /// 写入用量：typed 摘要 + 原始响应
+    pub fn set_usage(&mut self, usage: Option<UsageSummary>, raw: serde_json::Value) {
+        self.usage = usage;
+        self.usage_data = Some(raw);
+    }
+
+    /// 是否是 IdC 账号（BuilderId / Enterprise），刷新走 AWS OIDC
+    pub fn is_idc(&self) -> bool {
+        self.provider.as_deref().map(is_idc_provider).unwrap_or(false)
+    }
+
+    /// 记录一次检查结果：健康状态、同步的 status 文案、检查时间和错误
+    pub fn set_health(&mut self, health: AccountHealth, error: Option<String>) {
+        self.status = health.status_label().to_string();
+        self.health = health;
+        self.last_checked_at = Some(Local::now().format("%Y/%m/%d %H:%M:%S").to_string());
+        self.last_error = error;
+    }
+
+    /// 根据错误信息记录失败
+    pub fn record_error(&mut self, error: &str) {
+        self.set_health(AccountHealth::from_error(error), Some(error.to_string()));
+    }
+
+    /// 主资源剩余额度（主额度 + 试用 + 奖励），无用量数据时返回 None
+    pub fn quota_remaining(&self) -> Option<f64> {
+        self.usage.as_ref()?.remaining()
+    }
+
+    /// 解析 expires_at（兼容 %Y/%m/%d %H:%M:%S 与 RFC3339）
+    pub fn expires_at_time(&self) -> Option<DateTime<Local>> {
+        let raw = self.expires_at.as_deref()?.trim();
+        if let Ok(dt) = DateTime::parse_from_rfc3339(raw) {
+            return Some(dt.with_timezone(&Local));
+        }
+        chrono::NaiveDateTime::parse_from_str(raw, "%Y/%m/%d %H:%M:%S")
+            .ok()
+            .and_then(|dt| dt.and_local_timezone(Local).single())
     }
 }
 
@@ -75,29 +220,38 @@ impl AccountStore {
     }
 
     fn get_storage_path() -> PathBuf {
-        let data_dir = dirs::data_dir().unwrap_or_else(|| {
-            let home = std::env::var("USERPROFILE")
-                .or_else(|_| std::env::var("HOME"))
-                .unwrap_or_else(|_| ".".to_string());
-            PathBuf::from(home)
-        });
-        data_dir.join(".kiro-account-manager").join("accounts.json")
+        get_app_data_dir().join("accounts.json")
     }
 
     fn load_from_file(path: &PathBuf) -> Vec<Account> {
-        if let Ok(content) = std::fs::read_to_string(path) {
+        let mut accounts: Vec<Account> = if let Ok(content) = std::fs::read_to_string(path) {
             serde_json::from_str(&content).unwrap_or_default()
         } else {
             Vec::new()
+        };
+        // 旧版本只保存了原始 usage_data，加载时补齐 typed 用量
+        for account in accounts.iter_mut().filter(|a| a.usage.is_none()) {
+            account.usage = account.usage_data.as_ref().and_then(UsageSummary::from_raw);
         }
+        // 旧版本只有 status 文案
+        for account in accounts.iter_mut().filter(|a| a.health == AccountHealth::Active) {
+            if account.status == "已封禁" || account.status == "封禁" {
+                account.health = AccountHealth::Suspended { reason: None };
+            }
+        }
+        accounts
     }
 
     pub fn save_to_file(&self) {
         if let Some(parent) = self.file_path.parent() {
             let _ = std::fs::create_dir_all(parent);
         }
+        // 原子写入：先写临时文件，再覆盖（后台同步与前台命令可能同时保存）
         if let Ok(json) = serde_json::to_string_pretty(&self.accounts) {
-            let _ = std::fs::write(&self.file_path, json);
+            let temp_path = self.file_path.with_extension("json.tmp");
+            if std::fs::write(&temp_path, json).is_ok() {
+                let _ = std::fs::rename(&temp_path, &self.file_path);
+            }
         }
     }
 
@@ -125,20 +279,48 @@ impl AccountStore {
         deleted
     }
 
-    pub fn import_from_json(&mut self, json: &str) -> Result<usize, String> {
-        match serde_json::from_str::<Vec<Account>>(json) {
-            Ok(imported) => {
-                let count = imported.len();
-                for account in imported {
-                    if !self.accounts.iter().any(|a| a.id == account.id) {
-                        self.accounts.push(account);
-                    }
+    /// 批量添加标签，返回有变化的账号数
+    pub fn tag_many(&mut self, ids: &[String], tags: &[String]) -> usize {
+        let mut changed = 0;
+        for account in self.accounts.iter_mut().filter(|a| ids.contains(&a.id)) {
+            let before = account.tags.len();
+            for tag in tags.iter().map(|t| t.trim()).filter(|t| !t.is_empty()) {
+                if !account.tags.iter().any(|t| t == tag) {
+                    account.tags.push(tag.to_string());
                 }
-                self.save_to_file();
-                Ok(count)
             }
-            Err(e) => Err(e.to_string()),
+            if account.tags.len() != before {
+                changed += 1;
+            }
         }
+        if changed > 0 {
+            self.save_to_file();
+        }
+        changed
+    }
+
+    /// 批量移除标签，返回有变化的账号数
+    pub fn untag_many(&mut self, ids: &[String], tags: &[String]) -> usize {
+        let tags: Vec<&str> = tags.iter().map(|t| t.trim()).filter(|t| !t.is_empty()).collect();
+        let mut changed = 0;
+        for account in self.accounts.iter_mut().filter(|a| ids.contains(&a.id)) {
+            let before = account.tags.len();
+            account.tags.retain(|t| !tags.contains(&t.as_str()));
+            if account.tags.len() != before {
+                changed += 1;
+            }
+        }
+        if changed > 0 {
+            self.save_to_file();
+        }
+        changed
+    }
+
+    /// 导入账号 JSON（按身份合并，默认保留 expires_at 更新的凭据），返回新增数量
+    pub fn import_from_json(&mut self, json: &str) -> Result<usize, String> {
+        let imported = serde_json::from_str::<Vec<Account>>(json).map_err(|e| e.to_string())?;
+        let report = crate::importers::apply_import(self, "native", imported, crate::importers::MergeStrategy::Newer);
+        Ok(report.added)
     }
 
     pub fn export_to_json(&self) -> String {
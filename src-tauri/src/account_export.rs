@@ -0,0 +1,190 @@
+// 账号导出 - 导出档位（仅元数据 / 完整凭据 / 加密包）
+// 加密包：PBKDF2-SHA256 派生密钥 + AES-256-GCM，头部信息作为 AAD 参与校验
+
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::{Aes256Gcm, Nonce};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+use crate::account::Account;
+use crate::account_health::AccountHealth;
+use crate::usage::UsageSummary;
+
+pub const BUNDLE_FORMAT: &str = "kiro-account-manager-bundle";
+pub const BUNDLE_VERSION: u32 = 2;
+const PBKDF2_ITERATIONS: u32 = 210_000;
+/// 导入时接受的最大迭代次数，避免恶意导出包让密钥派生长时间卡住
+const MAX_PBKDF2_ITERATIONS: u32 = PBKDF2_ITERATIONS * 10;
+
+/// 导出档位
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ExportProfile {
+    /// 仅元数据（email、备注、provider、状态、用量），用于报表
+    Metadata,
+    /// 完整凭据，用于迁移
+    #[default]
+    Full,
+    /// 使用密码加密的单文件导出包
+    Encrypted,
+}
+
+/// 不含任何凭据的账号信息
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountMetadata {
+    pub id: String,
+    pub email: String,
+    pub label: String,
+    pub provider: Option<String>,
+    pub status: String,
+    pub health: AccountHealth,
+    pub last_checked_at: Option<String>,
+    pub added_at: String,
+    pub usage: Option<UsageSummary>,
+    pub tags: Vec<String>,
+    pub group: Option<String>,
+    pub notes: Option<String>,
+    pub pinned: bool,
+}
+
+impl From<&Account> for AccountMetadata {
+    fn from(account: &Account) -> Self {
+        Self {
+            id: account.id.clone(),
+            email: account.email.clone(),
+            label: account.label.clone(),
+            provider: account.provider.clone(),
+            status: account.status.clone(),
+            health: account.health.clone(),
+            last_checked_at: account.last_checked_at.clone(),
+            added_at: account.added_at.clone(),
+            usage: account.usage.clone(),
+            tags: account.tags.clone(),
+            group: account.group.clone(),
+            notes: account.notes.clone(),
+            pinned: account.pinned,
+        }
+    }
+}
+
+/// 加密导出包（文件内容即该结构的 JSON）
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EncryptedBundle {
+    format: String,
+    version: u32,
+    kdf: String,
+    iterations: u32,
+    salt: String,
+    nonce: String,
+    /// 版本 1 的导出包带有明文 SHA-256（已作为 AAD），仅为兼容旧包保留，新导出包不再写入
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    checksum: Option<String>,
+    ciphertext: String,
+}
+
+impl EncryptedBundle {
+    /// 头部字段作为 AAD，篡改任何一个都会导致解密失败
+    fn aad(&self) -> String {
+        match &self.checksum {
+            Some(checksum) => format!("{}:{}:{}:{}:{}", self.format, self.version, self.kdf, self.iterations, checksum),
+            None => format!("{}:{}:{}:{}", self.format, self.version, self.kdf, self.iterations),
+        }
+    }
+}
+
+fn derive_key(passphrase: &str, salt: &[u8], iterations: u32) -> [u8; 32] {
+    let mut key = [0u8; 32];
+    pbkdf2::pbkdf2_hmac::<Sha256>(passphrase.as_bytes(), salt, iterations, &mut key);
+    key
+}
+
+/// 按档位导出账号
+pub fn export_accounts(accounts: &[&Account], profile: ExportProfile, passphrase: Option<&str>) -> Result<String, String> {
+    match profile {
+        ExportProfile::Metadata => {
+            let list: Vec<AccountMetadata> = accounts.iter().map(|a| AccountMetadata::from(*a)).collect();
+            serde_json::to_string_pretty(&list).map_err(|e| e.to_string())
+        }
+        ExportProfile::Full => serde_json::to_string_pretty(accounts).map_err(|e| e.to_string()),
+        ExportProfile::Encrypted => {
+            let passphrase = passphrase.filter(|p| !p.is_empty()).ok_or("加密导出需要设置密码")?;
+            let plain = serde_json::to_string(accounts).map_err(|e| e.to_string())?;
+            encrypt_bundle(&plain, passphrase)
+        }
+    }
+}
+
+/// 加密为导出包
+pub fn encrypt_bundle(plain: &str, passphrase: &str) -> Result<String, String> {
+    let mut salt = [0u8; 16];
+    let mut nonce = [0u8; 12];
+    rand::thread_rng().fill_bytes(&mut salt);
+    rand::thread_rng().fill_bytes(&mut nonce);
+
+    let mut bundle = EncryptedBundle {
+        format: BUNDLE_FORMAT.to_string(),
+        version: BUNDLE_VERSION,
+        kdf: "pbkdf2-sha256".to_string(),
+        iterations: PBKDF2_ITERATIONS,
+        salt: BASE64.encode(salt),
+        nonce: BASE64.encode(nonce),
+        checksum: None,
+        ciphertext: String::new(),
+    };
+
+    let key = derive_key(passphrase, &salt, bundle.iterations);
+    let cipher = Aes256Gcm::new_from_slice(&key).map_err(|e| e.to_string())?;
+    let aad = bundle.aad();
+    let ciphertext = cipher
+        .encrypt(Nonce::from_slice(&nonce), Payload { msg: plain.as_bytes(), aad: aad.as_bytes() })
+        .map_err(|_| "加密失败".to_string())?;
+    bundle.ciphertext = BASE64.encode(ciphertext);
+
+    serde_json::to_string_pretty(&bundle).map_err(|e| e.to_string())
+}
+
+/// 判断内容是否为加密导出包
+pub fn is_bundle(content: &str) -> bool {
+    serde_json::from_str::<serde_json::Value>(content)
+        .ok()
+        .and_then(|v| v.get("format").and_then(|f| f.as_str()).map(|f| f == BUNDLE_FORMAT))
+        .unwrap_or(false)
+}
+
+/// 解密导出包，返回原始账号 JSON
+pub fn decrypt_bundle(content: &str, passphrase: &str) -> Result<String, String> {
+    let bundle: EncryptedBundle = serde_json::from_str(content)
+        .map_err(|e| format!("导出包格式错误: {}", e))?;
+    if bundle.format != BUNDLE_FORMAT {
+        return Err("不是加密导出包".to_string());
+    }
+    if bundle.version > BUNDLE_VERSION {
+        return Err(format!("不支持的导出包版本: {}，请升级应用", bundle.version));
+    }
+    if bundle.kdf != "pbkdf2-sha256" {
+        return Err(format!("不支持的密钥派生算法: {}", bundle.kdf));
+    }
+    if !(PBKDF2_ITERATIONS..=MAX_PBKDF2_ITERATIONS).contains(&bundle.iterations) {
+        return Err(format!("导出包已损坏: 迭代次数 {} 超出允许范围", bundle.iterations));
+    }
+
+    let salt = BASE64.decode(&bundle.salt).map_err(|e| format!("导出包已损坏: {}", e))?;
+    let nonce = BASE64.decode(&bundle.nonce).map_err(|e| format!("导出包已损坏: {}", e))?;
+    let ciphertext = BASE64.decode(&bundle.ciphertext).map_err(|e| format!("导出包已损坏: {}", e))?;
+    if nonce.len() != 12 {
+        return Err("导出包已损坏: nonce 长度错误".to_string());
+    }
+
+    let key = derive_key(passphrase, &salt, bundle.iterations);
+    let cipher = Aes256Gcm::new_from_slice(&key).map_err(|e| e.to_string())?;
+    let aad = bundle.aad();
+    let plain = cipher
+        .decrypt(Nonce::from_slice(&nonce), Payload { msg: &ciphertext, aad: aad.as_bytes() })
+        .map_err(|_| "解密失败：密码错误或导出包已被篡改".to_string())?;
+
+    String::from_utf8(plain).map_err(|e| format!("导出包内容无效: {}", e))
+}
@@ -0,0 +1,140 @@
+// 账号健康状态 - 由刷新 / 用量接口的错误映射而来
+// 各 provider 的错误仍是字符串，通过约定前缀（BANNED: / CLIENT_EXPIRED: / RETRY_AFTER:）和已知文案识别
+
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+
+/// 账号被封禁时 usage 接口返回 "BANNED:REASON"
+pub const BANNED_PREFIX: &str = "BANNED:";
+/// IdC 客户端注册过期 / 被吊销
+pub const CLIENT_EXPIRED_PREFIX: &str = "CLIENT_EXPIRED:";
+/// 被限流或服务端暂时错误，格式 "RETRY_AFTER:<秒>:<信息>"
+pub const RETRY_AFTER_PREFIX: &str = "RETRY_AFTER:";
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "state", rename_all = "camelCase")]
+pub enum AccountHealth {
+    #[default]
+    Active,
+    /// AccessToken 过期，刷新即可恢复
+    AccessTokenExpired,
+    /// RefreshToken 失效，需要重新登录
+    RefreshTokenInvalid,
+    /// IdC 客户端注册过期，需要重新登录
+    ClientRegistrationExpired,
+    Suspended {
+        reason: Option<String>,
+    },
+    /// 网络错误 / 限流 / 服务端错误，稍后重试
+    TransientError {
+        /// 建议的重试时间（%Y/%m/%d %H:%M:%S）
+        #[serde(rename = "retryAfter")]
+        retry_after: Option<String>,
+    },
+    /// 无法识别的错误，重试不一定能恢复，需要查看 lastError
+    RequestFailed,
+}
+
+/// 是否是网络层面的错误（请求没有拿到响应）
+pub fn is_network_error(error: &str) -> bool {
+    let lower = error.to_lowercase();
+    error.contains("网络错误")
+        || lower.contains("request failed")
+        || lower.contains("error sending request")
+        || lower.contains("timed out")
+        || lower.contains("connect")
+}
+
+/// 从错误信息中取出 HTTP 状态码，例如 "failed (503 Service Unavailable)" / "failed: 429 Too Many Requests"
+fn http_status(error: &str) -> Option<u16> {
+    let bytes = error.as_bytes();
+    (1..bytes.len().saturating_sub(2)).find_map(|i| {
+        let prev = bytes[i - 1];
+        let digits = &bytes[i..i + 3];
+        let next = bytes.get(i + 3).copied();
+        ((prev == b'(' || prev == b' ')
+            && digits.iter().all(u8::is_ascii_digit)
+            && !next.is_some_and(|c| c.is_ascii_digit()))
+            .then(|| error[i..i + 3].parse::<u16>().ok())
+            .flatten()
+            .filter(|code| (100..600).contains(code))
+    })
+}
+
+/// 账号缺少刷新所需的凭据
+fn is_missing_credentials(error: &str) -> bool {
+    let lower = error.to_lowercase();
+    lower.contains("no refresh token")
+        || lower.contains("is required for")
+        || error.contains("缺少")
+}
+
+/// 带重试时间的错误
+pub fn retry_after_error(retry_after_secs: u64, message: &str) -> String {
+    format!("{}{}:{}", RETRY_AFTER_PREFIX, retry_after_secs, message)
+}
+
+/// 从响应头读取 Retry-After（只支持秒数），缺省 60 秒
+pub fn parse_retry_after(headers: &reqwest::header::HeaderMap) -> u64 {
+    headers.get(reqwest::header::RETRY_AFTER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse().ok())
+        .unwrap_or(60)
+}
+
+impl AccountHealth {
+    /// 把刷新 / 用量接口的错误映射为健康状态
+    pub fn from_error(error: &str) -> Self {
+        let lower = error.to_lowercase();
+        if let Some(reason) = error.strip_prefix(BANNED_PREFIX) {
+            let reason = reason.trim();
+            return AccountHealth::Suspended {
+                reason: (!reason.is_empty()).then(|| reason.to_string()),
+            };
+        }
+        if error.starts_with(CLIENT_EXPIRED_PREFIX)
+            || lower.contains("invalid_client")
+            || lower.contains("unauthorized_client")
+            || lower.contains("invalidclientexception")
+        {
+            return AccountHealth::ClientRegistrationExpired;
+        }
+        if let Some(rest) = error.strip_prefix(RETRY_AFTER_PREFIX) {
+            let secs = rest.split(':').next().and_then(|s| s.parse::<i64>().ok()).unwrap_or(60);
+            let retry_at = Local::now() + chrono::Duration::seconds(secs);
+            return AccountHealth::TransientError {
+                retry_after: Some(retry_at.format("%Y/%m/%d %H:%M:%S").to_string()),
+            };
+        }
+        if error.contains("已过期或无效") || lower.contains("invalid_grant") || is_missing_credentials(error) {
+            return AccountHealth::RefreshTokenInvalid;
+        }
+        // usage 接口 401/403 但没有封禁原因：AccessToken 已失效
+        if lower.contains("getusagelimits failed (401") || lower.contains("getusagelimits failed (403") {
+            return AccountHealth::AccessTokenExpired;
+        }
+        // 只有网络错误、限流和服务端错误才是暂时的
+        if is_network_error(error) || http_status(error).is_some_and(|code| code == 429 || code >= 500) {
+            return AccountHealth::TransientError { retry_after: None };
+        }
+        AccountHealth::RequestFailed
+    }
+
+    /// 登录 / 导入时只知道是否封禁
+    pub fn from_banned(is_banned: bool) -> Self {
+        if is_banned { AccountHealth::Suspended { reason: None } } else { AccountHealth::Active }
+    }
+
+    /// 兼容旧版 status 字段的显示文案
+    pub fn status_label(&self) -> &'static str {
+        match self {
+            AccountHealth::Active => "正常",
+            AccountHealth::AccessTokenExpired => "Token已过期",
+            AccountHealth::RefreshTokenInvalid => "Token已失效",
+            AccountHealth::ClientRegistrationExpired => "客户端已过期",
+            AccountHealth::Suspended { .. } => "已封禁",
+            AccountHealth::TransientError { .. } => "刷新失败",
+            AccountHealth::RequestFailed => "请求失败",
+        }
+    }
+}
@@ -0,0 +1,359 @@
+// 账号 IDE 配置 - 每个账号绑定模型、代理、启用的 MCP 服务器和 steering 文件
+// 切换账号时一次性应用到 settings.json、mcp.json 和 ~/.kiro/steering，任何一步失败都恢复原状
+// 配置和应用记录保存在 <应用数据目录>/account-profiles.json，停用的 steering 文件移到 <应用数据目录>/steering-inactive
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::account::get_app_data_dir;
+use crate::jsonc::JsoncDocument;
+use crate::kiro_paths::kiro_settings_path;
+use crate::mcp::McpConfig;
+use crate::model_catalog;
+use crate::settings_profile::apply_settings;
+use crate::steering::SteeringManager;
+
+/// 最多保留的应用记录数量
+const MAX_APPLIED: usize = 20;
+
+/// 账号绑定的 IDE 配置，None 表示不修改该项
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountIdeProfile {
+    /// kiroAgent.modelSelection
+    pub model: Option<String>,
+    /// http.proxy，空字符串表示不使用代理
+    pub proxy: Option<String>,
+    /// 启用的 MCP 服务器，mcp.json 中其余服务器设为 disabled
+    pub mcp_servers: Option<Vec<String>>,
+    /// 启用的 steering 文件，其余移到停用目录
+    pub steering: Option<Vec<String>>,
+}
+
+/// 一次应用记录，保存被修改项原来的值，用于恢复
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppliedAccountProfile {
+    pub account_id: String,
+    pub applied_at: String,
+    /// settings.json 中被修改的键原来的值（原来不存在为 null）
+    pub settings: Map<String, Value>,
+    /// MCP 服务器原来的 disabled 状态
+    pub mcp_disabled: Map<String, Value>,
+    /// 原来启用的 steering 文件（未修改时为空）
+    pub steering: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ProfileStore {
+    #[serde(default)]
+    profiles: HashMap<String, AccountIdeProfile>,
+    /// 应用记录，最新的在最后
+    #[serde(default)]
+    applied: Vec<AppliedAccountProfile>,
+}
+
+fn store_path() -> PathBuf {
+    get_app_data_dir().join("account-profiles.json")
+}
+
+fn inactive_steering_dir() -> PathBuf {
+    get_app_data_dir().join("steering-inactive")
+}
+
+fn load_store() -> Result<ProfileStore, String> {
+    let path = store_path();
+    if !path.exists() {
+        return Ok(ProfileStore::default());
+    }
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| format!("读取账号 IDE 配置失败: {}", e))?;
+    serde_json::from_str(&content)
+        .map_err(|e| format!("解析账号 IDE 配置失败: {}", e))
+}
+
+fn save_store(store: &ProfileStore) -> Result<(), String> {
+    let path = store_path();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).ok();
+    }
+    let content = serde_json::to_string_pretty(store)
+        .map_err(|e| format!("序列化账号 IDE 配置失败: {}", e))?;
+    let temp = path.with_extension("json.tmp");
+    std::fs::write(&temp, content).map_err(|e| format!("写入账号 IDE 配置失败: {}", e))?;
+    std::fs::rename(&temp, &path).map_err(|e| format!("写入账号 IDE 配置失败: {}", e))
+}
+
+pub fn get_profile(account_id: &str) -> Result<Option<AccountIdeProfile>, String> {
+    Ok(load_store()?.profiles.remove(account_id))
+}
+
+/// 保存账号的 IDE 配置，None 表示删除
+pub fn save_profile(account_id: &str, profile: Option<AccountIdeProfile>) -> Result<(), String> {
+    let mut store = load_store()?;
+    match profile {
+        Some(profile) => {
+            let names = profile.steering.iter().flatten();
+            if let Some(bad) = names.into_iter().find(|n| !is_plain_file_name(n)) {
+                return Err(format!("无效的 steering 文件名: {}", bad));
+            }
+            if let Some(model) = &profile.model {
+                model_catalog::validate_model(model, Some(account_id))?;
+            }
+            store.profiles.insert(account_id.to_string(), profile);
+        }
+        None => { store.profiles.remove(account_id); }
+    }
+    save_store(&store)
+}
+
+/// 删除账号时清理对应的配置
+pub fn remove_profiles(account_ids: &[String]) -> Result<(), String> {
+    let mut store = load_store()?;
+    let before = store.profiles.len();
+    store.profiles.retain(|id, _| !account_ids.contains(id));
+    if store.profiles.len() != before {
+        save_store(&store)?;
+    }
+    Ok(())
+}
+
+fn is_plain_file_name(name: &str) -> bool {
+    !name.is_empty() && Path::new(name).file_name().is_some_and(|n| n == name)
+}
+
+/// 配置对应的 settings.json 键值（与 set_kiro_proxy 写入的键一致）
+fn profile_settings(profile: &AccountIdeProfile) -> Map<String, Value> {
+    let mut settings = Map::new();
+    if let Some(model) = &profile.model {
+        settings.insert(model_catalog::MODEL_SETTING_KEY.to_string(), Value::String(model.clone()));
+    }
+    if let Some(proxy) = &profile.proxy {
+        if proxy.is_empty() {
+            settings.insert("http.proxy".to_string(), Value::Null);
+        } else {
+            settings.insert("http.proxy".to_string(), Value::String(proxy.clone()));
+            settings.insert("http.proxyStrictSSL".to_string(), Value::Bool(false));
+            settings.insert("http.proxySupport".to_string(), Value::String("on".to_string()));
+        }
+    }
+    settings
+}
+
+/// 修改 MCP 服务器的 disabled 状态，返回被修改的服务器原来的状态
+fn apply_mcp_disabled(doc: &mut JsoncDocument, target: &Map<String, Value>) -> Result<Map<String, Value>, String> {
+    let mut previous = Map::new();
+    let Some(Value::Object(servers)) = doc.get("mcpServers") else {
+        return Ok(previous);
+    };
+    for (name, disabled) in target {
+        let Some(Value::Object(server)) = servers.get(name) else { continue };
+        let current = server.get("disabled").and_then(|v| v.as_bool()).unwrap_or(false);
+        let desired = disabled.as_bool().unwrap_or(false);
+        if current != desired {
+            // 只改 disabled 成员，保留 mcp.json 中的注释和其它字段
+            doc.set_path(&["mcpServers", name, "disabled"], &Value::Bool(desired))?;
+            previous.insert(name.clone(), Value::Bool(current));
+        }
+    }
+    Ok(previous)
+}
+
+fn list_md(dir: &Path) -> Vec<String> {
+    let mut names: Vec<String> = std::fs::read_dir(dir)
+        .map(|entries| {
+            entries.flatten()
+                .map(|e| e.file_name().to_string_lossy().to_string())
+                .filter(|n| n.ends_with(".md"))
+                .collect()
+        })
+        .unwrap_or_default();
+    names.sort();
+    names
+}
+
+/// 已完成的文件移动（from, to），用于回滚
+type SteeringMoves = Vec<(PathBuf, PathBuf)>;
+
+/// 让 steering 目录中只保留 active 中的文件，返回原来启用的文件和已完成的移动
+fn apply_steering(active: &[String]) -> Result<(Vec<String>, SteeringMoves), String> {
+    let steering_dir = SteeringManager::steering_dir().ok_or("无法获取用户目录")?;
+    let inactive_dir = inactive_steering_dir();
+    let previous = list_md(&steering_dir);
+
+    let mut moves = Vec::new();
+    for name in &previous {
+        if !active.contains(name) {
+            moves.push((steering_dir.join(name), inactive_dir.join(name)));
+        }
+    }
+    for name in active {
+        let stashed = inactive_dir.join(name);
+        if !previous.contains(name) && stashed.exists() {
+            moves.push((stashed, steering_dir.join(name)));
+        }
+    }
+
+    let mut done = Vec::new();
+    for (from, to) in moves {
+        let result = to.parent()
+            .map(std::fs::create_dir_all)
+            .unwrap_or(Ok(()))
+            // 停用目录中的同名文件是更早停用的旧版本
+            .and_then(|_| if to.exists() { std::fs::remove_file(&to) } else { Ok(()) })
+            .and_then(|_| std::fs::rename(&from, &to));
+        if let Err(e) = result {
+            undo_moves(&done);
+            return Err(format!("移动 steering 文件 {:?} 失败: {}", from.file_name().unwrap_or_default(), e));
+        }
+        done.push((from, to));
+    }
+    Ok((previous, done))
+}
+
+fn undo_moves(done: &[(PathBuf, PathBuf)]) {
+    for (from, to) in done.iter().rev() {
+        let _ = std::fs::rename(to, from);
+    }
+}
+
+/// 读取文件原始内容（不存在为 None），用于失败时恢复
+fn read_original(path: &Path) -> Option<String> {
+    std::fs::read_to_string(path).ok()
+}
+
+/// 恢复文件原始内容（原子写入），失败时返回带文件名的错误
+fn restore_original(path: &Path, original: &Option<String>) -> Result<(), String> {
+    let result = match original {
+        Some(content) => {
+            let temp = path.with_extension("json.restore.tmp");
+            std::fs::write(&temp, content).and_then(|_| std::fs::rename(&temp, path))
+        }
+        None if path.exists() => std::fs::remove_file(path),
+        None => Ok(()),
+    };
+    result.map_err(|e| format!("恢复 {:?} 失败: {}", path, e))
+}
+
+/// 依次回滚已写入的文件，把回滚失败附加到原错误后面
+fn rollback(error: String, files: &[(&Path, &Option<String>)]) -> String {
+    let failures: Vec<String> = files.iter()
+        .filter_map(|(path, original)| restore_original(path, original).err())
+        .collect();
+    if failures.is_empty() {
+        error
+    } else {
+        println!("[AccountProfile] 回滚失败: {}", failures.join("; "));
+        format!("{}（回滚失败，IDE 配置可能不完整: {}）", error, failures.join("; "))
+    }
+}
+
+/// 在一个事务中修改 settings.json、mcp.json 和 steering 目录，任何一步失败都恢复原状
+fn apply_changes(
+    account_id: &str,
+    settings: &Map<String, Value>,
+    mcp_target: impl FnOnce(&JsoncDocument) -> Map<String, Value>,
+    steering: Option<&[String]>,
+) -> Result<AppliedAccountProfile, String> {
+    let settings_path = kiro_settings_path().ok_or("无法获取 Kiro 设置路径")?;
+    let mcp_path = McpConfig::config_path().ok_or("无法获取用户目录")?;
+
+    // 1. 先在内存中完成修改，文件无法解析时不做任何写入
+    let mut settings_doc = JsoncDocument::load(&settings_path)
+        .map_err(|e| format!("settings.json 无法解析，已取消: {}", e))?;
+    let mut mcp_doc = JsoncDocument::load(&mcp_path)
+        .map_err(|e| format!("mcp.json 无法解析，已取消: {}", e))?;
+    let settings_previous = apply_settings(&mut settings_doc, settings)?;
+    let mcp_target = mcp_target(&mcp_doc);
+    let mcp_previous = apply_mcp_disabled(&mut mcp_doc, &mcp_target)?;
+
+    // 2. 依次写入，失败时恢复已写入的部分
+    let settings_original = read_original(&settings_path);
+    let mcp_original = read_original(&mcp_path);
+    if !settings_previous.is_empty() {
+        settings_doc.save(&settings_path)?;
+    }
+    if !mcp_previous.is_empty() {
+        if let Err(e) = mcp_doc.save(&mcp_path) {
+            return Err(rollback(e, &[(&settings_path, &settings_original)]));
+        }
+    }
+    let steering_previous = match steering {
+        Some(active) => match apply_steering(active) {
+            Ok((previous, moved)) => (!moved.is_empty()).then_some(previous),
+            Err(e) => {
+                return Err(rollback(e, &[(&settings_path, &settings_original), (&mcp_path, &mcp_original)]));
+            }
+        },
+        None => None,
+    };
+
+    Ok(AppliedAccountProfile {
+        account_id: account_id.to_string(),
+        applied_at: chrono::Local::now().format("%Y/%m/%d %H:%M:%S").to_string(),
+        settings: settings_previous,
+        mcp_disabled: mcp_previous,
+        steering: steering_previous,
+    })
+}
+
+fn has_changes(applied: &AppliedAccountProfile) -> bool {
+    !applied.settings.is_empty() || !applied.mcp_disabled.is_empty() || applied.steering.is_some()
+}
+
+/// 应用账号绑定的 IDE 配置，没有配置时返回 Ok(false)
+pub fn apply_for_account(account_id: &str) -> Result<bool, String> {
+    let mut store = load_store()?;
+    let Some(profile) = store.profiles.get(account_id).cloned() else {
+        return Ok(false);
+    };
+
+    let mcp_enabled = profile.mcp_servers.clone();
+    let applied = apply_changes(
+        account_id,
+        &profile_settings(&profile),
+        |doc| {
+            let Some(enabled) = mcp_enabled else { return Map::new() };
+            let servers = doc.get("mcpServers");
+            servers.as_ref()
+                .and_then(|s| s.as_object())
+                .map(|s| s.keys().map(|name| (name.clone(), Value::Bool(!enabled.contains(name)))).collect())
+                .unwrap_or_default()
+        },
+        profile.steering.as_deref(),
+    )?;
+
+    if has_changes(&applied) {
+        store.applied.push(applied);
+        let excess = store.applied.len().saturating_sub(MAX_APPLIED);
+        store.applied.drain(..excess);
+        save_store(&store)?;
+    }
+    println!("[AccountProfile] 已应用账号 {} 的 IDE 配置", account_id);
+    Ok(true)
+}
+
+/// 恢复最近一次应用前的 IDE 配置
+pub fn restore_last() -> Result<AppliedAccountProfile, String> {
+    let mut store = load_store()?;
+    let applied = store.applied.last().cloned().ok_or("没有可恢复的 IDE 配置")?;
+
+    let mcp_disabled = applied.mcp_disabled.clone();
+    apply_changes(&applied.account_id, &applied.settings, |_| mcp_disabled, applied.steering.as_deref())?;
+
+    store.applied.pop();
+    save_store(&store)?;
+    println!("[AccountProfile] 已恢复账号 {} 应用前的 IDE 配置", applied.account_id);
+    Ok(applied)
+}
+
+/// 应用记录，最新的在前
+pub fn list_applied() -> Result<Vec<AppliedAccountProfile>, String> {
+    let mut applied = load_store()?.applied;
+    applied.reverse();
+    Ok(applied)
+}
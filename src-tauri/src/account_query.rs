@@ -0,0 +1,136 @@
+// 账号查询 - 过滤、排序、分页
+
+use std::cmp::Ordering;
+
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+
+use crate::account::Account;
+
+/// 查询条件（字段均可省略）
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AccountQuery {
+    /// 关键字（匹配 email / label / notes）
+    pub search: Option<String>,
+    pub providers: Vec<String>,
+    pub statuses: Vec<String>,
+    /// 命中任意一个标签即可
+    pub tags: Vec<String>,
+    pub group: Option<String>,
+    pub pinned: Option<bool>,
+    pub min_remaining: Option<f64>,
+    pub max_remaining: Option<f64>,
+    /// Token 在多少小时内过期（已过期的也算）
+    pub expires_within_hours: Option<i64>,
+    /// email / label / provider / status / group / addedAt / expiresAt / remaining
+    pub sort_by: Option<String>,
+    pub sort_desc: bool,
+    /// 从 1 开始
+    pub page: Option<usize>,
+    pub page_size: Option<usize>,
+}
+
+/// 查询结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountQueryResult {
+    pub items: Vec<Account>,
+    pub total: usize,
+    pub page: usize,
+    pub page_size: usize,
+}
+
+impl AccountQuery {
+    fn matches(&self, account: &Account) -> bool {
+        if let Some(keyword) = self.search.as_deref().map(|s| s.trim().to_lowercase()).filter(|s| !s.is_empty()) {
+            let hit = account.email.to_lowercase().contains(&keyword)
+                || account.label.to_lowercase().contains(&keyword)
+                || account.notes.as_deref().map(|n| n.to_lowercase().contains(&keyword)).unwrap_or(false);
+            if !hit {
+                return false;
+            }
+        }
+        if !self.providers.is_empty() {
+            let provider = account.provider.as_deref().unwrap_or_default();
+            if !self.providers.iter().any(|p| p.eq_ignore_ascii_case(provider)) {
+                return false;
+            }
+        }
+        if !self.statuses.is_empty() && !self.statuses.contains(&account.status) {
+            return false;
+        }
+        if !self.tags.is_empty() && !self.tags.iter().any(|t| account.tags.contains(t)) {
+            return false;
+        }
+        if let Some(group) = self.group.as_deref() {
+            if account.group.as_deref() != Some(group) {
+                return false;
+            }
+        }
+        if let Some(pinned) = self.pinned {
+            if account.pinned != pinned {
+                return false;
+            }
+        }
+        if self.min_remaining.is_some() || self.max_remaining.is_some() {
+            let Some(remaining) = account.quota_remaining() else {
+                return false;
+            };
+            if self.min_remaining.map(|min| remaining < min).unwrap_or(false)
+                || self.max_remaining.map(|max| remaining > max).unwrap_or(false)
+            {
+                return false;
+            }
+        }
+        if let Some(hours) = self.expires_within_hours {
+            let deadline = Local::now() + chrono::Duration::hours(hours);
+            match account.expires_at_time() {
+                Some(expires) if expires <= deadline => {}
+                _ => return false,
+            }
+        }
+        true
+    }
+
+    fn compare(&self, a: &Account, b: &Account) -> Ordering {
+        fn opt<T: PartialOrd>(a: Option<T>, b: Option<T>) -> Ordering {
+            // 空值始终排在后面
+            match (a, b) {
+                (Some(a), Some(b)) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            }
+        }
+        let ordering = match self.sort_by.as_deref().unwrap_or("addedAt") {
+            "email" => a.email.to_lowercase().cmp(&b.email.to_lowercase()),
+            "label" => a.label.cmp(&b.label),
+            "provider" => opt(a.provider.as_ref(), b.provider.as_ref()),
+            "status" => a.status.cmp(&b.status),
+            "group" => opt(a.group.as_ref(), b.group.as_ref()),
+            "expiresAt" => opt(a.expires_at_time(), b.expires_at_time()),
+            "remaining" => opt(a.quota_remaining(), b.quota_remaining()),
+            _ => a.added_at.cmp(&b.added_at),
+        };
+        if self.sort_desc { ordering.reverse() } else { ordering }
+    }
+
+    /// 执行查询：置顶账号始终排在最前
+    pub fn run(&self, accounts: &[Account]) -> AccountQueryResult {
+        let mut matched: Vec<&Account> = accounts.iter().filter(|a| self.matches(a)).collect();
+        matched.sort_by(|a, b| b.pinned.cmp(&a.pinned).then_with(|| self.compare(a, b)));
+
+        let total = matched.len();
+        let page_size = self.page_size.filter(|s| *s > 0).unwrap_or(total.max(1));
+        let page = self.page.filter(|p| *p > 0).unwrap_or(1);
+        let items = matched.into_iter()
+            // 页码来自前端，超大值时跳过全部而不是溢出
+            .skip(page.saturating_sub(1).saturating_mul(page_size))
+            .take(page_size)
+            .cloned()
+            .collect();
+
+        AccountQueryResult { items, total, page, page_size }
+    }
+}
@@ -0,0 +1,206 @@
+// 账号同步 - 刷新 Token + 获取用量
+// 网络请求（fetch_sync）与写回存储（apply_sync）分开，批量同步时可以并发请求、最后统一保存
+
+use serde::Serialize;
+
+use crate::account::Account;
+use crate::account_health::AccountHealth;
+use crate::auth::get_usage_limits_desktop;
+use crate::codewhisperer_client::CodeWhispererClient;
+use crate::kiro::get_machine_id;
+use crate::providers::{is_idc_provider, AuthProvider, AuthResult, IdcProvider, RefreshMetadata, SocialProvider};
+use crate::usage::UsageSummary;
+
+/// 一次同步得到的新凭据和用量
+pub struct SyncResult {
+    pub access_token: String,
+    pub refresh_token: Option<String>,
+    pub expires_in: i64,
+    pub profile_arn: Option<String>,
+    pub id_token: Option<String>,
+    pub sso_session_id: Option<String>,
+    /// 只刷新 Token 时为 None
+    pub usage: Option<SyncUsage>,
+}
+
+pub struct SyncUsage {
+    pub summary: Option<UsageSummary>,
+    pub raw: serde_json::Value,
+    /// 由用量接口结果得到的健康状态（封禁 / 正常 / 暂时错误）
+    pub health: AccountHealth,
+    pub error: Option<String>,
+}
+
+/// 同步失败的分类
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SyncErrorKind {
+    /// RefreshToken 过期或无效，需要重新登录
+    Expired,
+    /// 账号被封禁 / 暂停
+    Suspended,
+    /// 网络错误，可重试
+    Network,
+    Other,
+}
+
+/// 根据错误信息判断失败类型
+pub fn classify_error(error: &str) -> SyncErrorKind {
+    match AccountHealth::from_error(error) {
+        AccountHealth::Suspended { .. } => SyncErrorKind::Suspended,
+        AccountHealth::RefreshTokenInvalid
+        | AccountHealth::ClientRegistrationExpired
+        | AccountHealth::AccessTokenExpired => SyncErrorKind::Expired,
+        AccountHealth::TransientError { .. } => SyncErrorKind::Network,
+        AccountHealth::Active | AccountHealth::RequestFailed => SyncErrorKind::Other,
+    }
+}
+
+/// 刷新接口所在的主机（批量同步时按主机限速）
+pub fn refresh_host(account: &Account) -> String {
+    if account.is_idc() {
+        format!("oidc.{}.amazonaws.com", account.region.as_deref().unwrap_or("us-east-1"))
+    } else {
+        "prod.us-east-1.auth.desktop.kiro.dev".to_string()
+    }
+}
+
+/// 刷新 Token，with_usage 为 true 时同时获取用量
+pub async fn fetch_sync(account: &Account, with_usage: bool) -> Result<SyncResult, String> {
+    let provider_str = account.provider.as_deref().unwrap_or("Google");
+    let refresh_token_str = account.refresh_token.as_ref().ok_or("No refresh token")?;
+
+    println!("[sync_account] Refreshing {} account", provider_str);
+
+    // 根据 provider 选择刷新接口
+    // 注意：Web OAuth 的 refresh_token 也是 aor 开头的 RefreshToken Cookie，可以用 Desktop API
+    let mut result = if account.is_idc() {
+        // BuilderId / Enterprise -> 账号所在区域的 AWS OIDC
+        let metadata = RefreshMetadata {
+            client_id: account.client_id.clone(),
+            client_secret: account.client_secret.clone(),
+            region: account.region.clone(),
+            client_id_hash: account.client_id_hash.clone(),
+            ..Default::default()
+        };
+        let idc_provider = IdcProvider::new(provider_str, metadata.region.as_deref().unwrap_or("us-east-1"), account.start_url.clone());
+        let auth_result = idc_provider.refresh_token(refresh_token_str, metadata).await?;
+        SyncResult {
+            access_token: auth_result.access_token,
+            refresh_token: Some(auth_result.refresh_token),
+            expires_in: auth_result.expires_in,
+            profile_arn: None,
+            id_token: auth_result.id_token,
+            sso_session_id: auth_result.sso_session_id,
+            usage: None,
+        }
+    } else {
+        // Google/Github (Desktop OAuth 或 Web OAuth) -> Desktop API
+        let metadata = RefreshMetadata {
+            profile_arn: account.profile_arn.clone(),
+            ..Default::default()
+        };
+        let social_provider = SocialProvider::new(provider_str);
+        let auth_result = social_provider.refresh_token(refresh_token_str, metadata).await?;
+        SyncResult {
+            access_token: auth_result.access_token,
+            refresh_token: Some(auth_result.refresh_token),
+            expires_in: auth_result.expires_in,
+            profile_arn: auth_result.profile_arn,
+            id_token: None,
+            sso_session_id: None,
+            usage: None,
+        }
+    };
+
+    if with_usage {
+        result.usage = Some(fetch_usage(provider_str, &result.access_token).await);
+    }
+    Ok(result)
+}
+
+/// 获取 usage 数据（失败不影响 Token 刷新结果）
+pub async fn fetch_usage(provider: &str, access_token: &str) -> SyncUsage {
+    let (summary, raw, error) = if is_idc_provider(provider) {
+        let machine_id = get_machine_id();
+        let cw_client = CodeWhispererClient::new(&machine_id);
+        match cw_client.get_usage_limits(access_token).await {
+            Ok(u) => (Some(UsageSummary::from(&u)), serde_json::to_value(Some(&u)), None),
+            Err(e) => (None, Ok(serde_json::Value::Null), Some(e)),
+        }
+    } else {
+        match get_usage_limits_desktop(access_token).await {
+            Ok(u) => (Some(UsageSummary::from(&u)), serde_json::to_value(Some(&u)), None),
+            Err(e) => (None, Ok(serde_json::Value::Null), Some(e)),
+        }
+    };
+    SyncUsage {
+        summary,
+        raw: raw.unwrap_or(serde_json::Value::Null),
+        health: error.as_deref().map(AccountHealth::from_error).unwrap_or_default(),
+        error,
+    }
+}
+
+/// 把同步结果写回账号（不保存文件）
+pub fn apply_sync(account: &mut Account, result: SyncResult) {
+    let expires_at = chrono::Local::now() + chrono::Duration::seconds(result.expires_in);
+
+    account.access_token = Some(result.access_token);
+    if let Some(rt) = result.refresh_token {
+        account.refresh_token = Some(rt);
+    }
+    if let Some(arn) = result.profile_arn {
+        account.profile_arn = Some(arn);
+    }
+    if let Some(id_token) = result.id_token {
+        account.id_token = Some(id_token);
+    }
+    if let Some(session_id) = result.sso_session_id {
+        account.sso_session_id = Some(session_id);
+    }
+    account.expires_at = Some(expires_at.format("%Y/%m/%d %H:%M:%S").to_string());
+
+    match result.usage {
+        Some(usage) => {
+            account.set_usage(usage.summary, usage.raw);
+            account.set_health(usage.health, usage.error);
+        }
+        // 只刷新 Token 时无法判断封禁状态，保留原有的封禁记录
+        None if matches!(account.health, AccountHealth::Suspended { .. }) => {}
+        None => account.set_health(AccountHealth::Active, None),
+    }
+}
+
+/// 重新登录后只替换凭据字段（保留 id、label、标签等元数据）
+pub fn apply_auth_result(account: &mut Account, auth: AuthResult) {
+    account.access_token = Some(auth.access_token);
+    account.refresh_token = Some(auth.refresh_token);
+    account.expires_at = Some(auth.expires_at);
+    if auth.csrf_token.is_some() {
+        account.csrf_token = auth.csrf_token;
+    }
+    if auth.session_token.is_some() {
+        account.session_token = auth.session_token;
+    }
+    if auth.profile_arn.is_some() {
+        account.profile_arn = auth.profile_arn;
+    }
+    // IdC 重新登录会注册新的客户端
+    if auth.client_id.is_some() {
+        account.client_id = auth.client_id;
+        account.client_secret = auth.client_secret;
+    }
+    if auth.client_id_hash.is_some() {
+        account.client_id_hash = auth.client_id_hash;
+    }
+    if auth.region.is_some() {
+        account.region = auth.region;
+    }
+    if auth.id_token.is_some() {
+        account.id_token = auth.id_token;
+    }
+    if auth.sso_session_id.is_some() {
+        account.sso_session_id = auth.sso_session_id;
+    }
+}
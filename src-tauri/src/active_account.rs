@@ -0,0 +1,115 @@
+// 当前 IDE 登录的账号
+// 把 kiro-auth-token.json 对应到账号列表中的账号：RefreshToken > profileArn + userId > clientIdHash
+
+use serde::Serialize;
+
+use crate::account::Account;
+use crate::account_sync;
+use crate::kiro::{get_kiro_local_token, KiroLocalToken};
+use crate::state::AppState;
+
+/// 匹配方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ActiveMatchKind {
+    RefreshToken,
+    ProfileArnUserId,
+    ClientIdHash,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActiveAccount {
+    /// IDE 是否已登录（存在 Token 文件）
+    pub logged_in: bool,
+    pub account_id: Option<String>,
+    pub email: Option<String>,
+    pub matched_by: Option<ActiveMatchKind>,
+    pub provider: Option<String>,
+    pub auth_method: Option<String>,
+    /// IDE 已登录但账号不在列表中，可通过 add_local_kiro_account 导入
+    pub importable: bool,
+    /// 未匹配时通过用量接口识别到的邮箱
+    pub detected_email: Option<String>,
+}
+
+impl ActiveAccount {
+    fn matched(account: &Account, kind: ActiveMatchKind, token: &KiroLocalToken) -> Self {
+        ActiveAccount {
+            logged_in: true,
+            account_id: Some(account.id.clone()),
+            email: Some(account.email.clone()),
+            matched_by: Some(kind),
+            provider: token.provider.clone(),
+            auth_method: token.auth_method.clone(),
+            importable: false,
+            detected_email: None,
+        }
+    }
+}
+
+/// 解析当前 IDE 登录的账号
+pub async fn resolve(state: &AppState) -> ActiveAccount {
+    let Some(token) = get_kiro_local_token() else {
+        return ActiveAccount::default();
+    };
+    let is_idc = token.auth_method.as_deref() == Some("IdC");
+
+    // 1. RefreshToken 完全一致
+    if let Some(refresh_token) = token.refresh_token.as_deref() {
+        let store = state.store.lock().unwrap();
+        if let Some(account) = store.accounts.iter().find(|a| a.refresh_token.as_deref() == Some(refresh_token)) {
+            return ActiveAccount::matched(account, ActiveMatchKind::RefreshToken, &token);
+        }
+    }
+
+    // 2. 用 AccessToken 查询 userId（Token 已过期时查不到，只能按 clientIdHash 匹配）
+    let user = match token.access_token.as_deref() {
+        Some(access_token) => {
+            let provider = token.provider.as_deref().unwrap_or("Google");
+            account_sync::fetch_usage(provider, access_token).await
+                .summary.and_then(|s| s.user)
+        }
+        None => None,
+    };
+    let user_id = user.as_ref().and_then(|u| u.user_id.clone());
+
+    let store = state.store.lock().unwrap();
+    if !is_idc {
+        if let (Some(arn), Some(uid)) = (token.profile_arn.as_deref(), user_id.as_deref()) {
+            let found = store.accounts.iter().find(|a| {
+                a.profile_arn.as_deref() == Some(arn) && a.user_id.as_deref() == Some(uid)
+            });
+            if let Some(account) = found {
+                return ActiveAccount::matched(account, ActiveMatchKind::ProfileArnUserId, &token);
+            }
+        }
+    } else if let Some(hash) = token.client_id_hash.as_deref() {
+        // 同一 start URL 的账号共用 clientIdHash，有多个时再按 userId 区分
+        let candidates: Vec<&Account> = store.accounts.iter()
+            .filter(|a| a.is_idc() && a.client_id_hash.as_deref() == Some(hash))
+            .collect();
+        // 只有一个候选时，双方的 userId 都已知就必须一致
+        let found = match candidates.as_slice() {
+            [only] => match (user_id.as_deref(), only.user_id.as_deref()) {
+                (Some(uid), Some(account_uid)) if uid != account_uid => None,
+                _ => Some(*only),
+            },
+            _ => user_id.as_deref().and_then(|uid| {
+                candidates.iter().copied().find(|a| a.user_id.as_deref() == Some(uid))
+            }),
+        };
+        if let Some(account) = found {
+            return ActiveAccount::matched(account, ActiveMatchKind::ClientIdHash, &token);
+        }
+    }
+
+    ActiveAccount {
+        logged_in: true,
+        provider: token.provider.clone(),
+        auth_method: token.auth_method.clone(),
+        importable: token.refresh_token.is_some(),
+        detected_email: user.and_then(|u| u.email),
+        ..Default::default()
+    }
+}
@@ -0,0 +1,290 @@
+// 用量告警 - 同步后按规则检查额度、试用/奖励到期和 Token 刷新失败
+// 通过 Tauri 事件 + 系统通知提醒，已触发过的告警记录在 alerts-state.json 中避免重复
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use chrono::Local;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_notification::NotificationExt;
+
+use crate::account::{get_app_data_dir, Account};
+use crate::account_health::AccountHealth;
+
+/// 保留最近触发的告警条数
+const MAX_RECENT_ALERTS: usize = 100;
+/// 去重记录保留天数
+const DEDUP_RETENTION_DAYS: i64 = 60;
+
+/// 串行化 alerts-state.json 的读改写（批量同步时会并发调用）
+static STATE_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+/// 告警规则（存放在 AppSettings.alert_rules）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AlertRules {
+    pub enabled: bool,
+    /// 用量百分比阈值，例如 [80, 95]
+    pub usage_percent_thresholds: Vec<f64>,
+    /// 免费试用剩余天数阈值，0 表示不检查
+    pub trial_expiry_days: i64,
+    /// 奖励额度剩余天数阈值，0 表示不检查
+    pub bonus_expiry_days: i64,
+    pub notify_refresh_failure: bool,
+    /// 是否弹出系统通知（关闭后只发送事件）
+    pub desktop_notification: bool,
+}
+
+impl Default for AlertRules {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            usage_percent_thresholds: vec![80.0, 95.0],
+            trial_expiry_days: 3,
+            bonus_expiry_days: 3,
+            notify_refresh_failure: true,
+            desktop_notification: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AlertKind {
+    UsageThreshold,
+    TrialExpiring,
+    BonusExpiring,
+    RefreshFailed,
+    Suspended,
+}
+
+/// 一条告警
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountAlert {
+    /// 去重键
+    pub key: String,
+    pub kind: AlertKind,
+    pub account_id: String,
+    pub email: String,
+    pub title: String,
+    pub message: String,
+    pub fired_at: String,
+}
+
+/// 去重状态
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct AlertState {
+    /// 去重键 -> 首次触发时间（毫秒）
+    fired: HashMap<String, i64>,
+    recent: Vec<AccountAlert>,
+}
+
+fn get_state_path() -> PathBuf {
+    get_app_data_dir().join("alerts-state.json")
+}
+
+fn load_state() -> AlertState {
+    std::fs::read_to_string(get_state_path())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_state(state: &AlertState) {
+    let path = get_state_path();
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    if let Ok(json) = serde_json::to_string_pretty(state) {
+        let _ = std::fs::write(path, json);
+    }
+}
+
+fn alert(account: &Account, kind: AlertKind, key: String, title: String, message: String) -> AccountAlert {
+    AccountAlert {
+        key,
+        kind,
+        account_id: account.id.clone(),
+        email: account.email.clone(),
+        title,
+        message,
+        fired_at: Local::now().format("%Y/%m/%d %H:%M:%S").to_string(),
+    }
+}
+
+/// 距离秒级时间戳还有多少天
+fn days_until(timestamp_secs: f64) -> f64 {
+    (timestamp_secs - Local::now().timestamp() as f64) / 86_400.0
+}
+
+/// 按规则检查账号，返回当前满足条件的告警（未去重）
+/// failure 为同步失败的告警类型（RefreshFailed / Suspended）和错误信息
+pub fn evaluate(account: &Account, rules: &AlertRules, failure: Option<(AlertKind, &str)>) -> Vec<AccountAlert> {
+    let mut alerts = Vec::new();
+
+    if let Some((kind, error)) = failure {
+        let message = format!("{}: {}", account.email, error);
+        match kind {
+            AlertKind::Suspended => alerts.push(alert(
+                account,
+                kind,
+                format!("suspended:{}", account.id),
+                "账号已被封禁".to_string(),
+                message,
+            )),
+            _ if rules.notify_refresh_failure => alerts.push(alert(
+                account,
+                kind,
+                format!("refresh:{}", account.id),
+                "Token 刷新失败".to_string(),
+                message,
+            )),
+            _ => {}
+        }
+        return alerts;
+    }
+
+    let Some(resource) = account.usage.as_ref().and_then(|u| u.primary()) else {
+        return alerts;
+    };
+    // 重置周期作为去重键的一部分，额度重置后可以再次提醒
+    let cycle = resource.next_date_reset
+        .or(account.usage.as_ref().and_then(|u| u.next_date_reset))
+        .map(|r| (r as i64).to_string())
+        .unwrap_or_default();
+
+    let limit = resource.total_limit();
+    if limit > 0.0 {
+        let percent = resource.total_used() / limit * 100.0;
+        // 只提醒已达到的最高阈值
+        let reached = rules.usage_percent_thresholds.iter()
+            .copied()
+            .filter(|t| percent >= *t)
+            .fold(None, |max: Option<f64>, t| Some(max.map_or(t, |m| m.max(t))));
+        if let Some(threshold) = reached {
+            alerts.push(alert(
+                account,
+                AlertKind::UsageThreshold,
+                format!("usage:{}:{}:{}", account.id, threshold, cycle),
+                format!("额度已使用 {:.0}%", percent),
+                format!("{} 已使用 {:.1} / {:.1}", account.email, resource.total_used(), limit),
+            ));
+        }
+    }
+
+    if rules.trial_expiry_days > 0 {
+        if let Some(trial) = resource.free_trial.as_ref().filter(|t| t.status.as_deref() == Some("ACTIVE")) {
+            if let Some(expiry) = trial.expiry {
+                let days = days_until(expiry);
+                if days >= 0.0 && days <= rules.trial_expiry_days as f64 {
+                    alerts.push(alert(
+                        account,
+                        AlertKind::TrialExpiring,
+                        format!("trial:{}:{}", account.id, expiry as i64),
+                        "免费试用即将到期".to_string(),
+                        format!("{} 的免费试用将在 {:.1} 天后到期", account.email, days),
+                    ));
+                }
+            }
+        }
+    }
+
+    if rules.bonus_expiry_days > 0 {
+        for bonus in &resource.bonuses {
+            if bonus.status.as_deref().map(|s| s != "ACTIVE").unwrap_or(false) {
+                continue;
+            }
+            let Some(expires_at) = bonus.expires_at else {
+                continue;
+            };
+            let days = days_until(expires_at);
+            if days >= 0.0 && days <= rules.bonus_expiry_days as f64 {
+                let name = bonus.display_name.clone()
+                    .or_else(|| bonus.code.clone())
+                    .unwrap_or_else(|| "奖励额度".to_string());
+                alerts.push(alert(
+                    account,
+                    AlertKind::BonusExpiring,
+                    format!("bonus:{}:{}:{}", account.id, bonus.code.as_deref().unwrap_or(&name), expires_at as i64),
+                    "奖励额度即将到期".to_string(),
+                    format!("{} 的 {} 将在 {:.1} 天后到期（剩余 {:.1}）", account.email, name, days, bonus.usage_limit - bonus.current_usage),
+                ));
+            }
+        }
+    }
+
+    alerts
+}
+
+/// 同步结束后检查告警：去重、持久化、发送事件和系统通知，返回新触发的告警
+pub fn process_sync_result(app: &AppHandle, account: &Account, failure: Option<(AlertKind, &str)>) -> Vec<AccountAlert> {
+    let rules = crate::commands::app_settings_cmd::get_alert_rules();
+    if !rules.enabled {
+        return Vec::new();
+    }
+
+    let _guard = STATE_LOCK.lock().unwrap();
+    let mut state = load_state();
+    let now = Local::now().timestamp_millis();
+    let mut changed = false;
+
+    // 同步成功后重新允许刷新失败 / 封禁告警
+    if failure.is_none() {
+        changed |= state.fired.remove(&format!("refresh:{}", account.id)).is_some();
+        changed |= state.fired.remove(&format!("suspended:{}", account.id)).is_some();
+    }
+    let before = state.fired.len();
+    state.fired.retain(|_, fired_at| now - *fired_at < DEDUP_RETENTION_DAYS * 86_400_000);
+    changed |= state.fired.len() != before;
+
+    let fresh: Vec<AccountAlert> = evaluate(account, &rules, failure)
+        .into_iter()
+        .filter(|a| !state.fired.contains_key(&a.key))
+        .collect();
+
+    for item in &fresh {
+        println!("[Alerts] {}: {}", item.title, item.message);
+        state.fired.insert(item.key.clone(), now);
+        state.recent.insert(0, item.clone());
+
+        let _ = app.emit("account-alert", item);
+        if rules.desktop_notification {
+            if let Err(e) = app.notification().builder().title(&item.title).body(&item.message).show() {
+                println!("[Alerts] 发送系统通知失败: {}", e);
+            }
+        }
+    }
+    state.recent.truncate(MAX_RECENT_ALERTS);
+
+    if changed || !fresh.is_empty() {
+        save_state(&state);
+    }
+    fresh
+}
+
+/// 同步失败后检查告警：封禁单独告警，只有需要重新登录的错误才算 Token 刷新失败，
+/// 网络错误 / 限流 / AccessToken 过期 / 无法识别的错误不告警
+pub fn process_sync_error(app: &AppHandle, account: &Account, error: &str) -> Vec<AccountAlert> {
+    let kind = match AccountHealth::from_error(error) {
+        AccountHealth::Suspended { .. } => AlertKind::Suspended,
+        AccountHealth::RefreshTokenInvalid | AccountHealth::ClientRegistrationExpired => AlertKind::RefreshFailed,
+        _ => return Vec::new(),
+    };
+    process_sync_result(app, account, Some((kind, error)))
+}
+
+/// 最近触发的告警
+pub fn get_recent_alerts() -> Vec<AccountAlert> {
+    load_state().recent
+}
+
+/// 清空告警记录（之后满足条件的告警会重新提醒）
+pub fn clear_alerts() {
+    let _guard = STATE_LOCK.lock().unwrap();
+    save_state(&AlertState::default());
+}
@@ -1,6 +1,7 @@
 // Auth 模块 - 当前使用的认证相关代码
 
 use serde::{Deserialize, Serialize};
+use crate::account_health::{parse_retry_after, retry_after_error};
 use std::sync::Mutex;
 
 // ============================================================
@@ -40,7 +41,7 @@ impl AuthState {
 
 pub const DESKTOP_AUTH_API: &str = "https://prod.us-east-1.auth.desktop.kiro.dev";
 const DESKTOP_USAGE_API: &str = "https://codewhisperer.us-east-1.amazonaws.com";
-const PROFILE_ARN: &str = "arn:aws:codewhisperer:us-east-1:699475941385:profile/EHGA3GRVQMUK";
+pub const PROFILE_ARN: &str = "arn:aws:codewhisperer:us-east-1:699475941385:profile/EHGA3GRVQMUK";
 
 // ============================================================
 // 桌面端 API 响应结构
@@ -219,6 +220,7 @@ pub async fn get_usage_limits_desktop(access_token: &str) -> Result<DesktopUsage
         {
             Ok(response) => {
                 let status = response.status();
+                let retry_after = parse_retry_after(response.headers());
                 let text = response.text().await.unwrap_or_default();
                 
                 println!("\n[Social] GET USAGE LIMITS RESPONSE");
@@ -236,6 +238,9 @@ pub async fn get_usage_limits_desktop(access_token: &str) -> Result<DesktopUsage
                 println!();
                 
                 if !status.is_success() {
+                    if status.as_u16() == 429 || status.is_server_error() {
+                        return Err(retry_after_error(retry_after, &format!("GetUsageLimits failed ({})", status)));
+                    }
                     // 解析错误响应，提取 reason 字段
                     if let Ok(error_json) = serde_json::from_str::<serde_json::Value>(&text) {
                         if let Some(reason) = error_json.get("reason").and_then(|r| r.as_str()) {
@@ -2,6 +2,7 @@
 /// 实现 AWS SSO OIDC API 调用，用于 BuilderId 认证
 
 use reqwest::Client;
+use crate::account_health::{parse_retry_after, retry_after_error, CLIENT_EXPIRED_PREFIX};
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
@@ -92,12 +93,27 @@ impl AWSSSOClient {
             .map_err(|e| format!("Token refresh request failed: {}", e))?;
 
         let status = resp.status();
+        let retry_after = parse_retry_after(resp.headers());
         let text = resp.text().await.unwrap_or_default();
 
         if !status.is_success() {
+            // OIDC 错误响应: {"error": "invalid_grant", "error_description": ...}
+            let error_code = serde_json::from_str::<serde_json::Value>(&text).ok()
+                .and_then(|v| v.get("error").and_then(|e| e.as_str()).map(|e| e.to_string()))
+                .unwrap_or_default();
+            match error_code.as_str() {
+                "invalid_client" | "unauthorized_client" => {
+                    return Err(format!("{}客户端注册已过期，需要重新登录", CLIENT_EXPIRED_PREFIX));
+                }
+                "invalid_grant" => return Err("RefreshToken 已过期或无效".to_string()),
+                _ => {}
+            }
             if status.as_u16() == 401 {
                 return Err("RefreshToken 已过期或无效".to_string());
             }
+            if status.as_u16() == 429 || status.is_server_error() {
+                return Err(retry_after_error(retry_after, &format!("Token refresh failed ({}): {}", status, text)));
+            }
             return Err(format!("Token refresh failed ({}): {}", status, text));
         }
 
@@ -1,12 +1,15 @@
 // CodeWhisperer API Client
-// 用于 IdC (BuilderId) 账号获取限额信息
+// 用于 IdC (BuilderId) 账号获取限额信息，以及获取账号可用的模型列表
 
 use reqwest::Client;
+use crate::account_health::{parse_retry_after, retry_after_error};
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 use uuid::Uuid;
 
 const CODEWHISPERER_API: &str = "https://codewhisperer.us-east-1.amazonaws.com";
+/// ListAvailableModels 最多翻页数
+const MAX_MODEL_PAGES: usize = 20;
 
 /// CodeWhisperer 限额响应
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -90,6 +93,29 @@ pub struct BonusInfo {
     pub status: Option<String>,
 }
 
+/// 账号可用的模型
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AvailableModel {
+    pub model_id: String,
+    pub model_name: Option<String>,
+    pub description: Option<String>,
+    pub rate_multiplier: Option<f64>,
+    pub rate_unit: Option<String>,
+    pub supported_input_types: Option<Vec<String>>,
+    pub token_limits: Option<serde_json::Value>,
+}
+
+/// ListAvailableModels 响应
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListAvailableModelsResponse {
+    #[serde(default)]
+    pub models: Vec<AvailableModel>,
+    pub default_model: Option<AvailableModel>,
+    pub next_token: Option<String>,
+}
+
 pub struct CodeWhispererClient {
     client: Client,
     machine_id: String,
@@ -143,12 +169,16 @@ impl CodeWhispererClient {
             .map_err(|e| format!("Request failed: {}", e))?;
 
         let status = response.status();
+        let retry_after = parse_retry_after(response.headers());
         let text = response.text().await.unwrap_or_default();
 
         println!("Status: {}", status);
 
         if !status.is_success() {
             println!("Error: {}", text);
+            if status.as_u16() == 429 || status.is_server_error() {
+                return Err(retry_after_error(retry_after, &format!("GetUsageLimits failed ({})", status)));
+            }
             // 解析错误响应，提取 reason 字段
             if let Ok(error_json) = serde_json::from_str::<serde_json::Value>(&text) {
                 if let Some(reason) = error_json.get("reason").and_then(|r| r.as_str()) {
@@ -169,4 +199,78 @@ impl CodeWhispererClient {
         serde_json::from_str(&text)
             .map_err(|e| format!("Parse failed: {}", e))
     }
+
+    /// 获取账号可用的模型列表（自动翻页），返回模型列表和默认模型 ID
+    /// Social 账号需要传入 profileArn，IdC 账号传 None
+    pub async fn list_available_models(
+        &self,
+        access_token: &str,
+        profile_arn: Option<&str>,
+    ) -> Result<(Vec<AvailableModel>, Option<String>), String> {
+        let kiro_version = "0.6.18";
+        let x_amz_user_agent = format!("aws-sdk-js/1.0.0 KiroIDE-{}-{}", kiro_version, self.machine_id);
+
+        let mut models = Vec::new();
+        let mut default_model = None;
+        let mut next_token: Option<String> = None;
+        let mut seen_tokens = std::collections::HashSet::new();
+        for page_index in 0.. {
+            if page_index >= MAX_MODEL_PAGES {
+                return Err(format!("ListAvailableModels 翻页超过 {} 页，已停止", MAX_MODEL_PAGES));
+            }
+            let mut url = format!("{}/ListAvailableModels?origin=AI_EDITOR", CODEWHISPERER_API);
+            if let Some(arn) = profile_arn {
+                url.push_str(&format!("&profileArn={}", urlencoding::encode(arn)));
+            }
+            if let Some(token) = &next_token {
+                url.push_str(&format!("&nextToken={}", urlencoding::encode(token)));
+            }
+
+            println!("\n[CodeWhisperer] LIST AVAILABLE MODELS");
+            println!("URL: {}", url);
+
+            let response = self.client
+                .get(&url)
+                .header("Authorization", format!("Bearer {}", access_token))
+                .header("x-amz-user-agent", &x_amz_user_agent)
+                .header("amz-sdk-invocation-id", Self::generate_invocation_id())
+                .header("amz-sdk-request", "attempt=1; max=1")
+                .header("Accept", "application/json")
+                .send()
+                .await
+                .map_err(|e| format!("Request failed: {}", e))?;
+
+            let status = response.status();
+            let retry_after = parse_retry_after(response.headers());
+            let text = response.text().await.unwrap_or_default();
+
+            println!("Status: {}", status);
+
+            if !status.is_success() {
+                println!("Error: {}", text);
+                if status.as_u16() == 429 || status.is_server_error() {
+                    return Err(retry_after_error(retry_after, &format!("ListAvailableModels failed ({})", status)));
+                }
+                return Err(format!("ListAvailableModels failed ({}): {}", status, text));
+            }
+
+            let page: ListAvailableModelsResponse = serde_json::from_str(&text)
+                .map_err(|e| format!("Parse failed: {}", e))?;
+            models.extend(page.models);
+            if default_model.is_none() {
+                default_model = page.default_model.map(|m| m.model_id);
+            }
+            match page.next_token.filter(|t| !t.is_empty()) {
+                // 服务端重复返回同一个 nextToken 时会无限翻页
+                Some(token) if !seen_tokens.insert(token.clone()) => {
+                    return Err("ListAvailableModels 返回了重复的 nextToken，已停止".to_string());
+                }
+                Some(token) => next_token = Some(token),
+                None => break,
+            }
+        }
+
+        println!("[CodeWhisperer] {} models available", models.len());
+        Ok((models, default_model))
+    }
 }
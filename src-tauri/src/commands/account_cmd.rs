@@ -3,10 +3,21 @@
 use tauri::State;
 use crate::state::AppState;
 use crate::account::Account;
+use crate::account_health::AccountHealth;
+use crate::usage::UsageSummary;
+use crate::account_export::{self, ExportProfile};
+use crate::account_query::{AccountQuery, AccountQueryResult};
 use crate::auth::{User, refresh_token_desktop, get_usage_limits_desktop};
 use crate::codewhisperer_client::CodeWhispererClient;
-use crate::providers::{AuthProvider, SocialProvider, IdcProvider, RefreshMetadata};
+use crate::providers::{is_idc_provider, normalize_start_url, AuthProvider, IdcProvider, RefreshMetadata, BUILDER_ID_START_URL};
 use crate::kiro::get_machine_id;
+use crate::usage_history;
+use crate::account_profile;
+use crate::model_catalog;
+use crate::switch_history;
+use crate::alerts;
+use crate::account_sync;
+use crate::active_account::{self, ActiveAccount};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -28,98 +39,169 @@ pub fn get_accounts(state: State<AppState>) -> Vec<Account> {
     state.store.lock().unwrap().get_all()
 }
 
+/// 删除前取出账号的 RefreshToken，用于清理切换快照中的凭证
+fn collect_refresh_tokens(state: &AppState, ids: &[String]) -> Vec<String> {
+    let store = state.store.lock().unwrap();
+    store.accounts.iter()
+        .filter(|a| ids.contains(&a.id))
+        .filter_map(|a| a.refresh_token.clone())
+        .collect()
+}
+
 #[tauri::command]
 pub fn delete_account(state: State<AppState>, id: String) -> bool {
-    state.store.lock().unwrap().delete(&id)
+    let ids = [id];
+    let refresh_tokens = collect_refresh_tokens(&state, &ids);
+    let deleted = state.store.lock().unwrap().delete(&ids[0]);
+    if deleted {
+        switch_history::purge_for_refresh_tokens(&refresh_tokens);
+        let _ = usage_history::delete_history(&ids);
+        let _ = account_profile::remove_profiles(&ids);
+        let _ = model_catalog::remove_catalogs(&ids);
+    }
+    deleted
 }
 
 #[tauri::command]
 pub fn delete_accounts(state: State<AppState>, ids: Vec<String>) -> usize {
-    state.store.lock().unwrap().delete_many(&ids)
+    let refresh_tokens = collect_refresh_tokens(&state, &ids);
+    let deleted = state.store.lock().unwrap().delete_many(&ids);
+    if deleted > 0 {
+        switch_history::purge_for_refresh_tokens(&refresh_tokens);
+        let _ = usage_history::delete_history(&ids);
+        let _ = account_profile::remove_profiles(&ids);
+        let _ = model_catalog::remove_catalogs(&ids);
+    }
+    deleted
+}
+
+/// 按条件查询账号（过滤 / 排序 / 分页）
+#[tauri::command]
+pub fn query_accounts(state: State<AppState>, query: Option<AccountQuery>) -> AccountQueryResult {
+    let store = state.store.lock().unwrap();
+    query.unwrap_or_default().run(&store.accounts)
+}
+
+#[tauri::command]
+pub fn tag_accounts(state: State<AppState>, ids: Vec<String>, tags: Vec<String>) -> usize {
+    state.store.lock().unwrap().tag_many(&ids, &tags)
+}
+
+#[tauri::command]
+pub fn untag_accounts(state: State<AppState>, ids: Vec<String>, tags: Vec<String>) -> usize {
+    state.store.lock().unwrap().untag_many(&ids, &tags)
+}
+
+/// 账号分组与标记的修改内容（未传的字段保持不变，group / notes 传空字符串表示清除）
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountMetaPatch {
+    pub tags: Option<Vec<String>>,
+    pub group: Option<String>,
+    pub notes: Option<String>,
+    pub pinned: Option<bool>,
 }
 
+/// 更新账号的标签、分组、备注和置顶状态
 #[tauri::command]
-pub async fn sync_account(state: State<'_, AppState>, id: String) -> Result<Account, String> {
+pub fn update_account_meta(state: State<AppState>, id: String, patch: AccountMetaPatch) -> Result<Account, String> {
+    let mut store = state.store.lock().unwrap();
+    let account = store.accounts.iter_mut()
+        .find(|a| a.id == id)
+        .ok_or("账号不存在")?;
+
+    if let Some(tags) = patch.tags {
+        account.tags.clear();
+        for tag in tags.iter().map(|t| t.trim()).filter(|t| !t.is_empty()) {
+            if !account.tags.iter().any(|t| t == tag) {
+                account.tags.push(tag.to_string());
+            }
+        }
+    }
+    if let Some(group) = patch.group {
+        account.group = Some(group.trim().to_string()).filter(|g| !g.is_empty());
+    }
+    if let Some(notes) = patch.notes {
+        account.notes = Some(notes).filter(|n| !n.trim().is_empty());
+    }
+    if let Some(pinned) = patch.pinned {
+        account.pinned = pinned;
+    }
+
+    let result = account.clone();
+    store.save_to_file();
+    Ok(result)
+}
+
+/// 获取所有已使用的标签和分组（供筛选下拉框使用）
+#[tauri::command]
+pub fn get_account_tags_and_groups(state: State<AppState>) -> (Vec<String>, Vec<String>) {
+    let store = state.store.lock().unwrap();
+    let mut tags: Vec<String> = store.accounts.iter().flat_map(|a| a.tags.iter().cloned()).collect();
+    let mut groups: Vec<String> = store.accounts.iter().filter_map(|a| a.group.clone()).collect();
+    tags.sort();
+    tags.dedup();
+    groups.sort();
+    groups.dedup();
+    (tags, groups)
+}
+
+#[tauri::command]
+pub async fn sync_account(
+    app_handle: tauri::AppHandle,
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<Account, String> {
+    let result = sync_account_inner(&state, &id).await;
+    // 同步后检查告警规则
+    match &result {
+        // Token 已刷新但用量接口报错（例如封禁）时按错误告警
+        Ok(account) => match account.last_error.as_deref() {
+            Some(e) => { alerts::process_sync_error(&app_handle, account, e); }
+            None => { alerts::process_sync_result(&app_handle, account, None); }
+        },
+        Err(e) => {
+            if let Some(account) = record_sync_error(&state, &id, e) {
+                alerts::process_sync_error(&app_handle, &account, e);
+            }
+        }
+    }
+    result
+}
+
+/// 同步失败时记录健康状态和错误
+fn record_sync_error(state: &AppState, id: &str, error: &str) -> Option<Account> {
+    let mut store = state.store.lock().unwrap();
+    let account = store.accounts.iter_mut().find(|a| a.id == id)?;
+    account.record_error(error);
+    let account = account.clone();
+    store.save_to_file();
+    Some(account)
+}
+
+async fn sync_account_inner(state: &AppState, id: &str) -> Result<Account, String> {
     let account = {
         let store = state.store.lock().unwrap();
         store.accounts.iter().find(|a| a.id == id).cloned()
     }.ok_or("Account not found")?;
 
-    let provider_str = account.provider.as_deref().unwrap_or("Google");
-    let refresh_token_str = account.refresh_token.as_ref().ok_or("No refresh token")?;
-    
-    println!("[sync_account] Refreshing {} account", provider_str);
-    
-    // 根据 provider 选择刷新接口
-    // 注意：Web OAuth 的 refresh_token 也是 aor 开头的 RefreshToken Cookie，可以用 Desktop API
-    let (new_access_token, new_refresh_token, expires_in, new_profile_arn, new_id_token, new_sso_session_id) = 
-        if provider_str == "BuilderId" {
-            // BuilderId -> AWS OIDC
-            let metadata = RefreshMetadata {
-                client_id: account.client_id.clone(),
-                client_secret: account.client_secret.clone(),
-                region: account.region.clone(),
-                ..Default::default()
-            };
-            let idc_provider = IdcProvider::new("BuilderId", metadata.region.as_deref().unwrap_or("us-east-1"), None);
-            let auth_result = idc_provider.refresh_token(refresh_token_str, metadata).await?;
-            (auth_result.access_token, Some(auth_result.refresh_token), auth_result.expires_in, None, auth_result.id_token, auth_result.sso_session_id)
-        } else {
-            // Google/Github (Desktop OAuth 或 Web OAuth) -> Desktop API
-            // Web OAuth 的 refresh_token 是 RefreshToken Cookie (aor开头)，跟 Desktop OAuth 相同
-            let metadata = RefreshMetadata {
-                profile_arn: account.profile_arn.clone(),
-                ..Default::default()
-            };
-            let social_provider = SocialProvider::new(provider_str);
-            let auth_result = social_provider.refresh_token(refresh_token_str, metadata).await?;
-            (auth_result.access_token, Some(auth_result.refresh_token), auth_result.expires_in, auth_result.profile_arn, None, None)
-        };
-    
-    // 获取 usage 数据
-    let (usage_data, is_banned): (serde_json::Value, bool) = if provider_str == "BuilderId" {
-        let machine_id = get_machine_id();
-        let cw_client = CodeWhispererClient::new(&machine_id);
-        let usage_call = cw_client.get_usage_limits(&new_access_token).await;
-        let (usage, banned) = match &usage_call {
-            Ok(u) => (Some(u.clone()), false),
-            Err(e) if e.starts_with("BANNED:") => (None, true),
-            Err(_) => (None, false),
-        };
-        (serde_json::to_value(&usage).unwrap_or(serde_json::Value::Null), banned)
-    } else {
-        let usage_call = get_usage_limits_desktop(&new_access_token).await;
-        let (usage, banned) = match &usage_call {
-            Ok(u) => (Some(u.clone()), false),
-            Err(e) if e.starts_with("BANNED:") => (None, true),
-            Err(_) => (None, false),
-        };
-        (serde_json::to_value(&usage).unwrap_or(serde_json::Value::Null), banned)
-    };
+    let result = account_sync::fetch_sync(&account, true).await?;
 
-    let expires_at = chrono::Local::now() + chrono::Duration::seconds(expires_in);
-    let expires_at_str = expires_at.format("%Y/%m/%d %H:%M:%S").to_string();
+    if let Some(summary) = result.usage.as_ref().and_then(|u| u.summary.clone()) {
+        let account_id = id.to_string();
+        let recorded = tokio::task::spawn_blocking(move || usage_history::record_snapshot(&account_id, &summary))
+            .await
+            .map_err(|e| format!("Task failed: {}", e))
+            .and_then(|r| r);
+        if let Err(e) = recorded {
+            println!("[sync_account] 记录用量历史失败: {}", e);
+        }
+    }
 
     // 更新账号
     let mut store = state.store.lock().unwrap();
     if let Some(a) = store.accounts.iter_mut().find(|a| a.id == id) {
-        a.access_token = Some(new_access_token);
-        if let Some(rt) = new_refresh_token {
-            a.refresh_token = Some(rt);
-        }
-        if let Some(arn) = new_profile_arn {
-            a.profile_arn = Some(arn);
-        }
-        if let Some(id_token) = new_id_token {
-            a.id_token = Some(id_token);
-        }
-        if let Some(session_id) = new_sso_session_id {
-            a.sso_session_id = Some(session_id);
-        }
-        a.expires_at = Some(expires_at_str);
-        a.usage_data = Some(usage_data);
-        a.status = if is_banned { "已封禁".to_string() } else { "正常".to_string() };
-        
+        account_sync::apply_sync(a, result);
         let result = a.clone();
         store.save_to_file();
         return Ok(result);
@@ -136,46 +218,21 @@ pub async fn refresh_account_token(state: State<'_, AppState>, id: String) -> Re
         store.accounts.iter().find(|a| a.id == id).cloned()
     }.ok_or("Account not found")?;
 
-    let provider_str = account.provider.as_deref().unwrap_or("Google");
-    let refresh_token_str = account.refresh_token.as_ref().ok_or("No refresh token")?;
-    
-    println!("[refresh_token] Refreshing {} token only", provider_str);
-    
-    let (new_access_token, new_refresh_token, expires_in) = 
-        if provider_str == "BuilderId" {
-            let metadata = RefreshMetadata {
-                client_id: account.client_id.clone(),
-                client_secret: account.client_secret.clone(),
-                region: account.region.clone(),
-                ..Default::default()
-            };
-            let idc_provider = IdcProvider::new("BuilderId", metadata.region.as_deref().unwrap_or("us-east-1"), None);
-            let auth_result = idc_provider.refresh_token(refresh_token_str, metadata).await?;
-            (auth_result.access_token, Some(auth_result.refresh_token), auth_result.expires_in)
-        } else {
-            let metadata = RefreshMetadata {
-                profile_arn: account.profile_arn.clone(),
-                ..Default::default()
-            };
-            let social_provider = SocialProvider::new(provider_str);
-            let auth_result = social_provider.refresh_token(refresh_token_str, metadata).await?;
-            (auth_result.access_token, Some(auth_result.refresh_token), auth_result.expires_in)
-        };
-
-    let expires_at = chrono::Local::now() + chrono::Duration::seconds(expires_in);
-    let expires_at_str = expires_at.format("%Y/%m/%d %H:%M:%S").to_string();
+    println!("[refresh_token] Refreshing token only");
+    let result = match account_sync::fetch_sync(&account, false).await {
+        Ok(result) => result,
+        Err(e) => {
+            record_sync_error(&state, &id, &e);
+            return Err(e);
+        }
+    };
 
     let mut store = state.store.lock().unwrap();
     if let Some(a) = store.accounts.iter_mut().find(|a| a.id == id) {
-        a.access_token = Some(new_access_token);
-        if let Some(rt) = new_refresh_token {
-            a.refresh_token = Some(rt);
-        }
-        a.expires_at = Some(expires_at_str);
-        
+        account_sync::apply_sync(a, result);
         let result = a.clone();
         store.save_to_file();
-        println!("[refresh_token] {} token refreshed", provider_str);
+        println!("[refresh_token] {} token refreshed", result.email);
         return Ok(result);
     }
 
@@ -195,7 +252,7 @@ pub async fn verify_account(
     region: Option<String>,
 ) -> Result<VerifyAccountResponse, String> {
     // 判断是否是 IdC 账号
-    let is_idc = provider == "BuilderId" || provider == "Enterprise";
+    let is_idc = is_idc_provider(&provider);
     
     let (new_access_token, new_refresh_token, quota, used, subscription_type) = if is_idc {
         // IdC 账号使用 AWS OIDC 刷新
@@ -292,12 +349,13 @@ pub async fn add_account_by_social(
         Err(_) => (None, None),
     };
     let usage_data = serde_json::to_value(&usage_result).unwrap_or(serde_json::Value::Null);
+    let usage_summary = usage_result.as_ref().map(UsageSummary::from);
     let is_banned = ban_reason.is_some();
     
     let email = usage_result.as_ref()
         .and_then(|u| u.user_info.as_ref())
         .and_then(|u| u.email.clone())
-        .unwrap_or_else(|| "unknown@kiro.dev".to_string());
+        .unwrap_or_else(|| crate::account::UNKNOWN_EMAIL.to_string());
     let user_id = usage_result.as_ref()
         .and_then(|u| u.user_info.as_ref())
         .and_then(|u| u.user_id.clone());
@@ -315,8 +373,8 @@ pub async fn add_account_by_social(
         existing.access_token = Some(access_token.clone());
         existing.refresh_token = Some(new_refresh_token);
         existing.user_id = user_id;
-        existing.usage_data = Some(usage_data);
-        existing.status = if is_banned { "已封禁".to_string() } else { "正常".to_string() };
+        existing.set_usage(usage_summary, usage_data);
+        existing.set_health(AccountHealth::from_banned(is_banned), None);
         existing.clone()
     } else {
         let mut account = Account::new(email.clone(), format!("Kiro {} 账号", idp));
@@ -324,8 +382,8 @@ pub async fn add_account_by_social(
         account.refresh_token = Some(new_refresh_token);
         account.provider = Some(idp.clone());
         account.user_id = user_id;
-        account.usage_data = Some(usage_data);
-        account.status = if is_banned { "已封禁".to_string() } else { "正常".to_string() };
+        account.set_usage(usage_summary, usage_data);
+        account.set_health(AccountHealth::from_banned(is_banned), None);
         store.accounts.insert(0, account.clone());
         account
     };
@@ -347,7 +405,12 @@ pub async fn add_account_by_social(
 }
 
 #[tauri::command]
-pub fn import_accounts(state: State<AppState>, json: String) -> Result<usize, String> {
+pub fn import_accounts(state: State<AppState>, json: String, passphrase: Option<String>) -> Result<usize, String> {
+    let json = if account_export::is_bundle(&json) {
+        account_export::decrypt_bundle(&json, passphrase.as_deref().unwrap_or_default())?
+    } else {
+        json
+    };
     state.store.lock().unwrap().import_from_json(&json)
 }
 
@@ -369,6 +432,34 @@ pub fn export_accounts(state: State<AppState>, ids: Option<Vec<String>>) -> Stri
     }
 }
 
+/// 按档位导出账号（仅元数据 / 完整凭据 / 加密包）
+#[tauri::command]
+pub async fn export_accounts_with_profile(
+    state: State<'_, AppState>,
+    ids: Option<Vec<String>>,
+    profile: Option<ExportProfile>,
+    passphrase: Option<String>,
+) -> Result<String, String> {
+    // 先复制选中的账号再释放锁，加密（PBKDF2）期间不占用账号存储
+    let selected: Vec<Account> = {
+        let store = state.store.lock().unwrap();
+        match ids {
+            Some(id_list) if !id_list.is_empty() => store.accounts.iter()
+                .filter(|a| id_list.contains(&a.id))
+                .cloned()
+                .collect(),
+            _ => store.accounts.clone(),
+        }
+    };
+
+    tokio::task::spawn_blocking(move || {
+        let refs: Vec<&Account> = selected.iter().collect();
+        account_export::export_accounts(&refs, profile.unwrap_or_default(), passphrase.as_deref())
+    })
+    .await
+    .map_err(|e| format!("Task failed: {}", e))?
+}
+
 /// 添加本地 Kiro IDE 账号
 #[tauri::command]
 pub async fn add_local_kiro_account(state: State<'_, AppState>) -> Result<Account, String> {
@@ -398,6 +489,7 @@ pub async fn add_local_kiro_account(state: State<'_, AppState>) -> Result<Accoun
             client_reg.client_id,
             client_reg.client_secret,
             Some(region),
+            local_token.start_url.clone(),
         ).await
     } else {
         add_account_by_social(
@@ -408,6 +500,12 @@ pub async fn add_local_kiro_account(state: State<'_, AppState>) -> Result<Accoun
     }
 }
 
+/// 当前 IDE 登录的是账号列表中的哪个账号
+#[tauri::command]
+pub async fn get_active_account(state: State<'_, AppState>) -> Result<ActiveAccount, String> {
+    Ok(active_account::resolve(&state).await)
+}
+
 /// 手动添加 BuilderId 账号
 #[tauri::command]
 pub async fn add_account_by_idc(
@@ -416,8 +514,16 @@ pub async fn add_account_by_idc(
     client_id: String,
     client_secret: String,
     region: Option<String>,
+    start_url: Option<String>,
 ) -> Result<Account, String> {
     let region = region.unwrap_or_else(|| "us-east-1".to_string());
+    // 填写了组织 start URL 的是企业 IdC 账号
+    let start_url = start_url
+        .filter(|url| !url.trim().is_empty())
+        .map(|url| normalize_start_url(&url))
+        .transpose()?
+        .filter(|url| url != BUILDER_ID_START_URL);
+    let provider = if start_url.is_some() { "Enterprise" } else { "BuilderId" };
     let metadata = RefreshMetadata {
         client_id: Some(client_id.clone()),
         client_secret: Some(client_secret.clone()),
@@ -425,7 +531,7 @@ pub async fn add_account_by_idc(
         ..Default::default()
     };
     
-    let idc_provider = IdcProvider::new("BuilderId", &region, None);
+    let idc_provider = IdcProvider::new(provider, &region, start_url.clone());
     let auth_result = idc_provider.refresh_token(&refresh_token, metadata).await?;
     
     let machine_id = get_machine_id();
@@ -437,6 +543,7 @@ pub async fn add_account_by_idc(
         Err(_) => (None, false),
     };
     let usage_data = serde_json::to_value(&usage).unwrap_or(serde_json::Value::Null);
+    let usage_summary = usage.as_ref().map(UsageSummary::from);
     
     let email = usage.as_ref()
         .and_then(|u| u.user_info.as_ref())
@@ -446,18 +553,14 @@ pub async fn add_account_by_idc(
         .and_then(|u| u.user_info.as_ref())
         .and_then(|u| u.user_id.clone());
     
-    use sha2::{Digest, Sha256};
-    let start_url = "https://view.awsapps.com/start";
-    let mut hasher = Sha256::new();
-    hasher.update(start_url.as_bytes());
-    let client_id_hash = hex::encode(hasher.finalize());
+    let client_id_hash = IdcProvider::compute_client_id_hash(start_url.as_deref().unwrap_or(BUILDER_ID_START_URL));
     
     let expires_at = chrono::Local::now() + chrono::Duration::seconds(auth_result.expires_in);
     
     let mut store = state.store.lock().unwrap();
     
     // 按 email + provider 去重
-    let account = if let Some(existing) = store.accounts.iter_mut().find(|a| a.email == email && a.provider.as_deref() == Some("BuilderId")) {
+    let account = if let Some(existing) = store.accounts.iter_mut().find(|a| a.email == email && a.provider.as_deref() == Some(provider)) {
         existing.access_token = Some(auth_result.access_token);
         existing.refresh_token = Some(auth_result.refresh_token);
         existing.user_id = user_id;
@@ -466,26 +569,28 @@ pub async fn add_account_by_idc(
         existing.client_secret = Some(client_secret);
         existing.region = Some(region);
         existing.client_id_hash = Some(client_id_hash);
+        existing.start_url = start_url;
         existing.id_token = auth_result.id_token;
         existing.sso_session_id = auth_result.sso_session_id;
-        existing.usage_data = Some(usage_data);
-        existing.status = if is_banned { "已封禁".to_string() } else { "正常".to_string() };
+        existing.set_usage(usage_summary, usage_data);
+        existing.set_health(AccountHealth::from_banned(is_banned), None);
         existing.clone()
     } else {
-        let mut account = Account::new(email.clone(), "Kiro BuilderId 账号".to_string());
+        let mut account = Account::new(email.clone(), format!("Kiro {} 账号", provider));
         account.access_token = Some(auth_result.access_token);
         account.refresh_token = Some(auth_result.refresh_token);
-        account.provider = Some("BuilderId".to_string());
+        account.provider = Some(provider.to_string());
         account.user_id = user_id;
         account.expires_at = Some(expires_at.format("%Y/%m/%d %H:%M:%S").to_string());
         account.client_id = Some(client_id);
         account.client_secret = Some(client_secret);
         account.region = Some(region);
         account.client_id_hash = Some(client_id_hash);
+        account.start_url = start_url;
         account.id_token = auth_result.id_token;
         account.sso_session_id = auth_result.sso_session_id;
-        account.usage_data = Some(usage_data);
-        account.status = if is_banned { "已封禁".to_string() } else { "正常".to_string() };
+        account.set_usage(usage_summary, usage_data);
+        account.set_health(AccountHealth::from_banned(is_banned), None);
         store.accounts.insert(0, account.clone());
         account
     };
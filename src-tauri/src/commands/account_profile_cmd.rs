@@ -0,0 +1,54 @@
+// 账号 IDE 配置命令
+
+use tauri::State;
+
+use crate::account_profile::{self, AccountIdeProfile, AppliedAccountProfile};
+use crate::model_catalog;
+use crate::state::AppState;
+
+#[tauri::command]
+pub async fn get_account_ide_profile(account_id: String) -> Result<Option<AccountIdeProfile>, String> {
+    tokio::task::spawn_blocking(move || account_profile::get_profile(&account_id))
+        .await
+        .map_err(|e| format!("Task failed: {}", e))?
+}
+
+/// 保存账号的 IDE 配置，profile 为空时删除
+#[tauri::command]
+pub async fn save_account_ide_profile(
+    state: State<'_, AppState>,
+    account_id: String,
+    profile: Option<AccountIdeProfile>,
+) -> Result<(), String> {
+    // 先获取该账号的模型目录（没有缓存时），保存时按目录校验
+    if let Some(model) = profile.as_ref().and_then(|p| p.model.as_deref()) {
+        model_catalog::ensure_model_available(&state, model, Some(&account_id)).await?;
+    }
+    tokio::task::spawn_blocking(move || account_profile::save_profile(&account_id, profile))
+        .await
+        .map_err(|e| format!("Task failed: {}", e))?
+}
+
+/// 立即应用账号的 IDE 配置（切换账号时会自动应用）
+#[tauri::command]
+pub async fn apply_account_ide_profile(account_id: String) -> Result<bool, String> {
+    tokio::task::spawn_blocking(move || account_profile::apply_for_account(&account_id))
+        .await
+        .map_err(|e| format!("Task failed: {}", e))?
+}
+
+/// 恢复最近一次应用前的 IDE 配置
+#[tauri::command]
+pub async fn restore_account_ide_profile() -> Result<AppliedAccountProfile, String> {
+    tokio::task::spawn_blocking(account_profile::restore_last)
+        .await
+        .map_err(|e| format!("Task failed: {}", e))?
+}
+
+/// IDE 配置应用记录（最新的在前）
+#[tauri::command]
+pub async fn get_applied_account_ide_profiles() -> Result<Vec<AppliedAccountProfile>, String> {
+    tokio::task::spawn_blocking(account_profile::list_applied)
+        .await
+        .map_err(|e| format!("Task failed: {}", e))?
+}
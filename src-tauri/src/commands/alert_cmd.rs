@@ -0,0 +1,19 @@
+// 用量告警命令（规则保存在 AppSettings.alert_rules）
+
+use crate::alerts::{self, AccountAlert};
+
+/// 获取最近触发的告警
+#[tauri::command]
+pub async fn get_recent_alerts() -> Result<Vec<AccountAlert>, String> {
+    tokio::task::spawn_blocking(alerts::get_recent_alerts)
+        .await
+        .map_err(|e| format!("Task failed: {}", e))
+}
+
+/// 清空告警记录
+#[tauri::command]
+pub async fn clear_alerts() -> Result<(), String> {
+    tokio::task::spawn_blocking(alerts::clear_alerts)
+        .await
+        .map_err(|e| format!("Task failed: {}", e))
+}
@@ -2,6 +2,7 @@
 
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
+use crate::alerts::AlertRules;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
@@ -27,6 +28,19 @@ pub struct AppSettings {
     pub kiro2api_region: Option<String>,
     pub kiro2api_kiro_version: Option<String>,
     pub kiro2api_anthropic_compat_mode: Option<String>,
+    // 用量告警规则
+    pub alert_rules: Option<AlertRules>,
+    // 社交登录回调方式："deepLink"（kiro:// 协议，默认）或 "loopback"（本地 127.0.0.1 端口）
+    pub oauth_callback_mode: Option<String>,
+    // Kiro IDE Token 回写：IDE 刷新 kiro-auth-token.json 后同步到账号列表
+    pub ide_token_sync: Option<bool>,
+    pub ide_token_conflict_policy: Option<String>,  // "newest"（默认）/ "ide" / "manager"
+    // Kiro IDE 用户数据目录（非标准安装时手动指定，空字符串表示自动检测）
+    pub kiro_data_dir: Option<String>,
+    // Kiro IDE 安装位置（可执行文件、macOS 的 .app 或 Linux 的 .desktop 文件，空字符串表示自动检测）
+    pub kiro_ide_path: Option<String>,
+    // 关闭 Kiro IDE 时等待正常退出的秒数，超时后强制结束
+    pub kiro_shutdown_timeout: Option<u64>,
 }
 
 fn get_app_settings_path() -> PathBuf {
@@ -65,6 +79,10 @@ fn save_app_settings_inner(updates: AppSettings) -> Result<(), String> {
     // 只更新传入的非 None 字段
     if updates.theme.is_some() { current.theme = updates.theme; }
     if updates.lock_model.is_some() { current.lock_model = updates.lock_model; }
+    if let Some(model) = &updates.locked_model {
+        // 锁定的模型必须在可用模型列表中
+        crate::model_catalog::validate_model(model, None)?;
+    }
     if updates.locked_model.is_some() { current.locked_model = updates.locked_model; }
     if updates.auto_refresh.is_some() { current.auto_refresh = updates.auto_refresh; }
     if updates.auto_refresh_interval.is_some() { current.auto_refresh_interval = updates.auto_refresh_interval; }
@@ -82,6 +100,13 @@ fn save_app_settings_inner(updates: AppSettings) -> Result<(), String> {
     if updates.kiro2api_region.is_some() { current.kiro2api_region = updates.kiro2api_region; }
     if updates.kiro2api_kiro_version.is_some() { current.kiro2api_kiro_version = updates.kiro2api_kiro_version; }
     if updates.kiro2api_anthropic_compat_mode.is_some() { current.kiro2api_anthropic_compat_mode = updates.kiro2api_anthropic_compat_mode; }
+    if updates.alert_rules.is_some() { current.alert_rules = updates.alert_rules; }
+    if updates.oauth_callback_mode.is_some() { current.oauth_callback_mode = updates.oauth_callback_mode; }
+    if updates.ide_token_sync.is_some() { current.ide_token_sync = updates.ide_token_sync; }
+    if updates.ide_token_conflict_policy.is_some() { current.ide_token_conflict_policy = updates.ide_token_conflict_policy; }
+    if updates.kiro_data_dir.is_some() { current.kiro_data_dir = updates.kiro_data_dir; }
+    if updates.kiro_ide_path.is_some() { current.kiro_ide_path = updates.kiro_ide_path; }
+    if updates.kiro_shutdown_timeout.is_some() { current.kiro_shutdown_timeout = updates.kiro_shutdown_timeout; }
     
     let content = serde_json::to_string_pretty(&current)
         .map_err(|e| format!("序列化失败: {}", e))?;
@@ -118,6 +143,50 @@ pub fn get_browser_path() -> Option<String> {
     browser_path
 }
 
+/// 社交登录是否使用本地回环地址接收回调（未配置时使用 kiro:// deep link）
+pub fn use_loopback_callback() -> bool {
+    get_app_settings_inner().ok()
+        .and_then(|s| s.oauth_callback_mode)
+        .is_some_and(|m| m == "loopback")
+}
+
+/// IDE Token 回写设置：(是否启用, 冲突策略)，默认启用、以较新的 Token 为准
+pub fn get_ide_token_sync_settings() -> (bool, String) {
+    let settings = get_app_settings_inner().unwrap_or_default();
+    (
+        settings.ide_token_sync.unwrap_or(true),
+        settings.ide_token_conflict_policy.unwrap_or_else(|| "newest".to_string()),
+    )
+}
+
+/// 用户指定的 Kiro IDE 用户数据目录
+pub fn get_kiro_data_dir_override() -> Option<String> {
+    get_app_settings_inner().ok()
+        .and_then(|s| s.kiro_data_dir)
+        .filter(|p| !p.trim().is_empty())
+}
+
+/// 用户指定的 Kiro IDE 安装位置
+pub fn get_kiro_ide_path_override() -> Option<String> {
+    get_app_settings_inner().ok()
+        .and_then(|s| s.kiro_ide_path)
+        .filter(|p| !p.trim().is_empty())
+}
+
+/// 关闭 Kiro IDE 时等待正常退出的时间（默认 10 秒，限制在 1~120 秒）
+pub fn get_kiro_shutdown_timeout() -> std::time::Duration {
+    let secs = get_app_settings_inner().ok()
+        .and_then(|s| s.kiro_shutdown_timeout)
+        .unwrap_or(10)
+        .clamp(1, 120);
+    std::time::Duration::from_secs(secs)
+}
+
+/// 获取告警规则（未配置时使用默认规则）
+pub fn get_alert_rules() -> AlertRules {
+    get_app_settings_inner().ok().and_then(|s| s.alert_rules).unwrap_or_default()
+}
+
 // ============================================================
 // 账号绑定机器码功能
 // ============================================================
@@ -2,11 +2,18 @@
 
 use tauri::{Emitter, State};
 use crate::state::AppState;
-use crate::account::Account;
+use crate::account::{Account, UNKNOWN_EMAIL};
+use crate::account_sync;
+use crate::account_health::AccountHealth;
+use crate::usage::{UsageSummary, UsageUser};
 use crate::auth::{User, get_usage_limits_desktop};
-use crate::auth_social;
+use crate::deep_link_handler::OAuthCallbackResult;
+use crate::login_session;
 use crate::codewhisperer_client::CodeWhispererClient;
-use crate::providers::{AuthMethod, AuthProvider, get_provider_config, create_social_provider, create_idc_provider};
+use crate::providers::{
+    AuthMethod, AuthProvider, AuthResult, ProviderConfig, BUILDER_ID_START_URL,
+    get_provider_config, get_enterprise_config, create_social_provider, create_idc_provider,
+};
 use crate::kiro::get_machine_id;
 
 #[tauri::command]
@@ -26,9 +33,10 @@ pub async fn kiro_login(
     app_handle: tauri::AppHandle,
     state: State<'_, AppState>,
     provider: String,
+    start_url: Option<String>,
+    region: Option<String>,
 ) -> Result<String, String> {
-    let config = get_provider_config(&provider)
-        .ok_or_else(|| format!("Unsupported provider: {}", provider))?;
+    let config = resolve_provider_config(&provider, start_url.as_deref(), region.as_deref())?;
 
     match config.auth_method {
         AuthMethod::Social => login_social(app_handle, state, &config).await,
@@ -36,10 +44,19 @@ pub async fn kiro_login(
     }
 }
 
+/// Enterprise 需要组织的 start URL 和 SSO 区域，其它 provider 使用固定配置
+pub(crate) fn resolve_provider_config(provider: &str, start_url: Option<&str>, region: Option<&str>) -> Result<ProviderConfig, String> {
+    if provider == "Enterprise" {
+        let start_url = start_url.ok_or("Enterprise 登录需要填写 Start URL")?;
+        return get_enterprise_config(start_url, region);
+    }
+    get_provider_config(provider).ok_or_else(|| format!("Unsupported provider: {}", provider))
+}
+
 async fn login_social(
     app_handle: tauri::AppHandle,
     state: State<'_, AppState>,
-    config: &crate::providers::ProviderConfig,
+    config: &ProviderConfig,
 ) -> Result<String, String> {
     let social_provider = create_social_provider(config);
     let provider_id = social_provider.get_provider_id().to_string();
@@ -50,6 +67,7 @@ async fn login_social(
     // 获取 usage，失败不影响登录（账号可能被暂停但仍可保存）
     let usage = get_usage_limits_desktop(&auth_result.access_token).await.ok();
     let usage_data = serde_json::to_value(&usage).unwrap_or(serde_json::Value::Null);
+    let usage_summary = usage.as_ref().map(UsageSummary::from);
 
     // 优先从 usage 获取 email，否则用默认值
     let email = usage.as_ref()
@@ -72,8 +90,8 @@ async fn login_social(
         existing.profile_arn = auth_result.profile_arn;
         existing.label = format!("Kiro {} 账号", provider_id);
         // 不覆盖 csrfToken，保留 Web OAuth 的
-        existing.usage_data = Some(usage_data);
-        existing.status = "正常".to_string();
+        existing.set_usage(usage_summary, usage_data);
+        existing.set_health(AccountHealth::Active, None);
         existing.clone()
     } else {
         // 新建账号
@@ -85,7 +103,7 @@ async fn login_social(
         account.expires_at = Some(auth_result.expires_at.clone());
         account.profile_arn = auth_result.profile_arn;
         account.csrf_token = auth_result.csrf_token;
-        account.usage_data = Some(usage_data);
+        account.set_usage(usage_summary, usage_data);
         store.accounts.insert(0, account.clone());
         account
     };
@@ -103,13 +121,25 @@ async fn login_social(
 async fn login_idc(
     app_handle: tauri::AppHandle,
     state: State<'_, AppState>,
-    config: &crate::providers::ProviderConfig,
+    config: &ProviderConfig,
 ) -> Result<String, String> {
     let idc_provider = create_idc_provider(config);
-    let provider_id = idc_provider.get_provider_id().to_string();
     let auth_method = idc_provider.get_auth_method();
     
     let auth_result = idc_provider.login().await?;
+    let account = save_idc_login(&state, config, auth_result).await?;
+
+    let _ = app_handle.emit("login-success", account.id.clone());
+    Ok(format!("{} login completed for {}", auth_method, account.email))
+}
+
+/// 保存 IdC 登录结果（按 email + provider 去重），并更新当前登录用户
+pub(crate) async fn save_idc_login(
+    state: &State<'_, AppState>,
+    config: &ProviderConfig,
+    auth_result: AuthResult,
+) -> Result<Account, String> {
+    let provider_id = config.provider_id.clone();
 
     let machine_id = get_machine_id();
     let cw_client = CodeWhispererClient::new(&machine_id);
@@ -120,6 +150,7 @@ async fn login_idc(
         Err(_) => (None, false),
     };
     let usage_data = serde_json::to_value(&usage).unwrap_or(serde_json::Value::Null);
+    let usage_summary = usage.as_ref().map(UsageSummary::from);
 
     let email = usage.as_ref()
         .and_then(|u| u.user_info.as_ref())
@@ -144,8 +175,9 @@ async fn login_idc(
         existing.sso_session_id = auth_result.sso_session_id;
         existing.id_token = auth_result.id_token;
         existing.profile_arn = auth_result.profile_arn;
-        existing.usage_data = Some(usage_data);
-        existing.status = if is_banned { "已封禁".to_string() } else { "正常".to_string() };
+        existing.start_url = enterprise_start_url(config);
+        existing.set_usage(usage_summary, usage_data);
+        existing.set_health(AccountHealth::from_banned(is_banned), None);
         existing.clone()
     } else {
         let mut account = Account::new(email.clone(), format!("Kiro {} 账号", provider_id));
@@ -161,8 +193,9 @@ async fn login_idc(
         account.sso_session_id = auth_result.sso_session_id;
         account.id_token = auth_result.id_token;
         account.profile_arn = auth_result.profile_arn;
-        account.usage_data = Some(usage_data);
-        account.status = if is_banned { "已封禁".to_string() } else { "正常".to_string() };
+        account.start_url = enterprise_start_url(config);
+        account.set_usage(usage_summary, usage_data);
+        account.set_health(AccountHealth::from_banned(is_banned), None);
         store.accounts.insert(0, account.clone());
         account
     };
@@ -170,11 +203,14 @@ async fn login_idc(
     store.save_to_file();
     drop(store);
 
-    update_auth_state(&state, &email, &provider_id, &auth_result.access_token, &auth_result.refresh_token);
-    println!("\n[{}] LOGIN SUCCESS: {}", auth_method, account.email);
+    update_auth_state(state, &email, &provider_id, &auth_result.access_token, &auth_result.refresh_token);
+    println!("\n[IdC] LOGIN SUCCESS: {}", account.email);
+    Ok(account)
+}
 
-    let _ = app_handle.emit("login-success", account.id.clone());
-    Ok(format!("{} login completed for {}", auth_method, email))
+/// 只为企业 IdC 记录 start URL
+fn enterprise_start_url(config: &ProviderConfig) -> Option<String> {
+    config.start_url.clone().filter(|url| url != BUILDER_ID_START_URL)
 }
 
 fn update_auth_state(state: &State<'_, AppState>, email: &str, provider: &str, access_token: &str, refresh_token: &str) {
@@ -188,68 +224,18 @@ fn update_auth_state(state: &State<'_, AppState>, email: &str, provider: &str, a
     *state.auth.user.lock().unwrap() = Some(user);
     *state.auth.access_token.lock().unwrap() = Some(access_token.to_string());
     *state.auth.refresh_token.lock().unwrap() = Some(refresh_token.to_string());
-    *state.pending_login.lock().unwrap() = None;
 }
 
+/// 前端回调页收到的社交登录回调，按 state 转交给进行中的登录会话
 #[tauri::command]
-pub async fn handle_kiro_social_callback(
-    app_handle: tauri::AppHandle,
-    state: State<'_, AppState>,
+pub fn handle_kiro_social_callback(
     code: String,
     callback_state: String,
 ) -> Result<(), String> {
-    let pending = {
-        let lock = state.pending_login.lock().unwrap();
-        lock.clone().ok_or("No pending login found")?
-    };
-    
-    if pending.state != callback_state {
-        return Err("State mismatch".to_string());
+    let result = OAuthCallbackResult { code, state: callback_state.clone() };
+    if !login_session::deliver(&callback_state, Ok(result)) {
+        return Err("No pending login found".to_string());
     }
-    
-    let redirect_uri = "kiro://app/callback";
-    let token_response = auth_social::exchange_social_code_for_token(
-        &code, &pending.code_verifier, redirect_uri, &pending.machineid,
-    ).await?;
-    
-    let usage = get_usage_limits_desktop(&token_response.access_token).await.ok();
-    let usage_data = serde_json::to_value(&usage).unwrap_or(serde_json::Value::Null);
-    
-    let email = usage.as_ref()
-        .and_then(|u| u.user_info.as_ref())
-        .and_then(|ui| ui.email.clone())
-        .unwrap_or_else(|| format!("user@{}.com", pending.provider.to_lowercase()));
-    let user_id = usage.as_ref()
-        .and_then(|u| u.user_info.as_ref())
-        .and_then(|ui| ui.user_id.clone());
-
-    let mut store = state.store.lock().unwrap();
-    
-    let account = if let Some(existing) = store.accounts.iter_mut().find(|a| a.email == email) {
-        existing.access_token = Some(token_response.access_token.clone());
-        existing.refresh_token = Some(token_response.refresh_token.clone());
-        existing.provider = Some(pending.provider.clone());
-        existing.user_id = user_id;
-        existing.usage_data = Some(usage_data);
-        existing.status = "正常".to_string();
-        existing.clone()
-    } else {
-        let mut account = Account::new(email.clone(), format!("Kiro {} 账号", pending.provider));
-        account.access_token = Some(token_response.access_token.clone());
-        account.refresh_token = Some(token_response.refresh_token.clone());
-        account.provider = Some(pending.provider.clone());
-        account.user_id = user_id;
-        account.usage_data = Some(usage_data);
-        store.accounts.insert(0, account.clone());
-        account
-    };
-    
-    store.save_to_file();
-    drop(store);
-    
-    update_auth_state(&state, &email, &pending.provider, &token_response.access_token, &token_response.refresh_token);
-    let _ = app_handle.emit("login-success", account.id);
-    println!("Social callback login completed: {}", email);
     Ok(())
 }
 
@@ -272,6 +258,7 @@ pub async fn add_kiro_account(
         None
     };
     let usage_data = serde_json::to_value(&usage).unwrap_or(serde_json::Value::Null);
+    let usage_summary = usage.as_ref().map(UsageSummary::from);
     
     let final_email = usage.as_ref()
         .and_then(|u| u.user_info.as_ref())
@@ -293,7 +280,6 @@ pub async fn add_kiro_account(
         provider: idp.clone(),
     };
     *state.auth.user.lock().unwrap() = Some(user);
-    *state.pending_login.lock().unwrap() = None;
     
     let mut store = state.store.lock().unwrap();
     
@@ -303,8 +289,8 @@ pub async fn add_kiro_account(
         existing.provider = Some(idp);
         existing.user_id = user_id;
         existing.csrf_token = Some(csrf_token);
-        existing.usage_data = Some(usage_data);
-        existing.status = "正常".to_string();
+        existing.set_usage(usage_summary, usage_data);
+        existing.set_health(AccountHealth::Active, None);
         existing.clone()
     } else {
         let mut account = Account::new(final_email.clone(), format!("Kiro {} 账号", idp));
@@ -313,7 +299,7 @@ pub async fn add_kiro_account(
         account.provider = Some(idp);
         account.user_id = user_id;
         account.csrf_token = Some(csrf_token);
-        account.usage_data = Some(usage_data);
+        account.set_usage(usage_summary, usage_data);
         store.accounts.insert(0, account.clone());
         account
     };
@@ -323,6 +309,106 @@ pub async fn add_kiro_account(
     Ok(account)
 }
 
+/// 重新登录已有账号：按原 provider 走登录流程，身份一致时只替换凭据字段
+#[tauri::command]
+pub async fn reauthenticate_account(
+    app_handle: tauri::AppHandle,
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<Account, String> {
+    let account = {
+        let store = state.store.lock().unwrap();
+        store.accounts.iter().find(|a| a.id == id).cloned()
+    }.ok_or("Account not found")?;
+
+    let config = reauth_provider_config(&account)?;
+    // IdC 走设备码会话（可取消、有进度事件），见 device_auth_cmd::start_device_reauth
+    if config.auth_method == AuthMethod::Idc {
+        return Err(format!("{} 账号请使用设备码重新登录", config.provider_id));
+    }
+
+    println!("\n[Reauth] {} ({})", account.email, config.provider_id);
+    let auth_result = create_social_provider(&config).login().await?;
+    let updated = finish_reauth(&state, &id, &config.provider_id, auth_result).await?;
+
+    let _ = app_handle.emit("login-success", updated.id.clone());
+    Ok(updated)
+}
+
+/// 重新登录使用的 provider 配置；旧数据没有 provider 时按 client_id / start_url 推断 IdC 类型
+pub(crate) fn reauth_provider_config(account: &Account) -> Result<ProviderConfig, String> {
+    let provider = match account.provider.as_deref() {
+        Some(provider) => provider,
+        None => match account.start_url.as_deref() {
+            Some(url) if url.trim_end_matches('/') != BUILDER_ID_START_URL => "Enterprise",
+            _ if account.client_id.is_some() || account.client_id_hash.is_some() || account.start_url.is_some() => "BuilderId",
+            _ => return Err("账号缺少 provider 信息，无法确定重新登录方式".to_string()),
+        },
+    };
+    resolve_provider_config(provider, account.start_url.as_deref(), account.region.as_deref())
+}
+
+/// 重新登录成功后校验身份并更新原账号的凭证和用量
+pub(crate) async fn finish_reauth(
+    state: &AppState,
+    id: &str,
+    provider: &str,
+    auth_result: AuthResult,
+) -> Result<Account, String> {
+    let account = {
+        let store = state.store.lock().unwrap();
+        store.accounts.iter().find(|a| a.id == id).cloned()
+    }.ok_or("Account not found")?;
+
+    let usage = account_sync::fetch_usage(provider, &auth_result.access_token).await;
+    let user = usage.summary.as_ref().and_then(|u| u.user.as_ref());
+    verify_same_identity(&account, user)?;
+
+    let mut store = state.store.lock().unwrap();
+    let existing = store.accounts.iter_mut().find(|a| a.id == id).ok_or("Account not found")?;
+    account_sync::apply_auth_result(existing, auth_result);
+    if existing.user_id.is_none() {
+        existing.user_id = user.and_then(|u| u.user_id.clone());
+    }
+    if existing.email == UNKNOWN_EMAIL {
+        if let Some(email) = user.and_then(|u| u.email.clone()) {
+            existing.email = email;
+        }
+    }
+    existing.set_usage(usage.summary, usage.raw);
+    existing.set_health(usage.health, usage.error);
+    let updated = existing.clone();
+    store.save_to_file();
+    drop(store);
+
+    println!("[Reauth] {} 重新登录成功", updated.email);
+    Ok(updated)
+}
+
+/// 校验重新登录得到的身份与原账号一致（优先 userId，其次 email）
+fn verify_same_identity(account: &Account, user: Option<&UsageUser>) -> Result<(), String> {
+    let new_user_id = user.and_then(|u| u.user_id.as_deref());
+    let new_email = user.and_then(|u| u.email.as_deref());
+
+    if let (Some(old), Some(new)) = (account.user_id.as_deref(), new_user_id) {
+        return if old == new {
+            Ok(())
+        } else {
+            Err(format!("登录的账号（{}）与原账号 {} 不一致", new_email.unwrap_or(new), account.email))
+        };
+    }
+    if let Some(new) = new_email {
+        if account.email != UNKNOWN_EMAIL {
+            return if account.email.eq_ignore_ascii_case(new) {
+                Ok(())
+            } else {
+                Err(format!("登录的账号（{}）与原账号 {} 不一致", new, account.email))
+            };
+        }
+    }
+    Err("无法获取登录账号的身份信息，已取消更新".to_string())
+}
+
 #[tauri::command]
 pub fn get_supported_providers() -> Vec<&'static str> {
     crate::providers::get_supported_providers()
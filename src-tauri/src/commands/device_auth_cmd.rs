@@ -0,0 +1,189 @@
+// 设备码登录命令（BuilderId / Enterprise）
+// start 立即返回 user code 和验证地址，后台轮询并通过事件通知进度，可随时取消
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::browser::open_browser;
+use crate::commands::auth_cmd::{finish_reauth, reauth_provider_config, resolve_provider_config, save_idc_login};
+use crate::providers::{create_idc_provider, AuthMethod, DeviceFlowCancel, DevicePollEvent, ProviderConfig};
+use crate::state::AppState;
+
+/// 进行中的设备码登录：session_id -> 取消信号
+static DEVICE_SESSIONS: Lazy<Mutex<HashMap<String, Arc<DeviceFlowCancel>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeviceLoginSession {
+    pub session_id: String,
+    pub provider: String,
+    pub user_code: String,
+    pub verification_uri: String,
+    pub verification_uri_complete: Option<String>,
+    pub expires_in: i64,
+    pub expires_at: String,
+    pub interval: u64,
+    /// 是否已自动打开浏览器（失败时需要用户手动打开并输入 user code）
+    pub browser_opened: bool,
+}
+
+/// 轮询进度事件 device-login-pending / device-login-slow-down
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DeviceLoginProgress<'a> {
+    session_id: &'a str,
+    interval: u64,
+}
+
+/// 结束事件 device-login-complete
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DeviceLoginComplete<'a> {
+    session_id: &'a str,
+    success: bool,
+    cancelled: bool,
+    account_id: Option<String>,
+    email: Option<String>,
+    error: Option<String>,
+}
+
+/// 授权成功后的处理：新增账号，或更新已有账号（重新登录）
+enum DeviceLoginTarget {
+    NewAccount,
+    Reauth { account_id: String },
+}
+
+/// 发起设备码登录，返回 user code 等信息；授权结果通过 device-login-complete 事件通知
+#[tauri::command]
+pub async fn start_device_login(
+    app_handle: AppHandle,
+    provider: String,
+    start_url: Option<String>,
+    region: Option<String>,
+) -> Result<DeviceLoginSession, String> {
+    let config = resolve_provider_config(&provider, start_url.as_deref(), region.as_deref())?;
+    start_session(app_handle, config, DeviceLoginTarget::NewAccount).await
+}
+
+/// 用设备码重新登录已有账号（BuilderId / Enterprise），事件与 start_device_login 相同
+#[tauri::command]
+pub async fn start_device_reauth(app_handle: AppHandle, id: String) -> Result<DeviceLoginSession, String> {
+    let account = {
+        let state = app_handle.state::<AppState>();
+        let store = state.store.lock().unwrap();
+        store.accounts.iter().find(|a| a.id == id).cloned()
+    }.ok_or("Account not found")?;
+
+    let config = reauth_provider_config(&account)?;
+    println!("\n[Reauth] {} ({}) 使用设备码登录", account.email, config.provider_id);
+    start_session(app_handle, config, DeviceLoginTarget::Reauth { account_id: id }).await
+}
+
+async fn start_session(
+    app_handle: AppHandle,
+    config: ProviderConfig,
+    target: DeviceLoginTarget,
+) -> Result<DeviceLoginSession, String> {
+    if config.auth_method != AuthMethod::Idc {
+        return Err(format!("{} 不支持设备码登录", config.provider_id));
+    }
+
+    let idc_provider = create_idc_provider(&config);
+    let flow = idc_provider.start_device_flow().await?;
+
+    let verification_url = flow.verification_uri_complete.as_ref().unwrap_or(&flow.verification_uri);
+    let browser_opened = match open_browser(verification_url) {
+        Ok(_) => true,
+        Err(e) => {
+            println!("[DeviceLogin] 打开浏览器失败: {}", e);
+            false
+        }
+    };
+
+    let session_id = uuid::Uuid::new_v4().to_string();
+    let cancel = Arc::new(DeviceFlowCancel::default());
+    DEVICE_SESSIONS.lock().unwrap().insert(session_id.clone(), cancel.clone());
+
+    let session = DeviceLoginSession {
+        session_id: session_id.clone(),
+        provider: config.provider_id.clone(),
+        user_code: flow.user_code.clone(),
+        verification_uri: flow.verification_uri.clone(),
+        verification_uri_complete: flow.verification_uri_complete.clone(),
+        expires_in: flow.expires_in,
+        expires_at: (chrono::Local::now() + chrono::Duration::seconds(flow.expires_in))
+            .format("%Y/%m/%d %H:%M:%S")
+            .to_string(),
+        interval: flow.interval,
+        browser_opened,
+    };
+    println!("[DeviceLogin] 会话 {} 已开始 ({})", session_id, config.provider_id);
+
+    let app = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        let on_event = |event: DevicePollEvent| {
+            let (name, interval) = match event {
+                DevicePollEvent::Pending { interval } => ("device-login-pending", interval),
+                DevicePollEvent::SlowDown { interval } => ("device-login-slow-down", interval),
+            };
+            let _ = app.emit(name, DeviceLoginProgress { session_id: &session_id, interval });
+        };
+
+        let result = match idc_provider.poll_device_flow(&flow, &cancel, on_event).await {
+            Ok(auth_result) => {
+                let state = app.state::<AppState>();
+                match &target {
+                    DeviceLoginTarget::NewAccount => save_idc_login(&state, &config, auth_result).await,
+                    DeviceLoginTarget::Reauth { account_id } => {
+                        finish_reauth(&state, account_id, &config.provider_id, auth_result).await
+                    }
+                }
+            }
+            Err(e) => Err(e),
+        };
+        DEVICE_SESSIONS.lock().unwrap().remove(&session_id);
+
+        let complete = match &result {
+            Ok(account) => DeviceLoginComplete {
+                session_id: &session_id,
+                success: true,
+                cancelled: false,
+                account_id: Some(account.id.clone()),
+                email: Some(account.email.clone()),
+                error: None,
+            },
+            Err(e) => DeviceLoginComplete {
+                session_id: &session_id,
+                success: false,
+                cancelled: cancel.is_cancelled(),
+                account_id: None,
+                email: None,
+                error: Some(e.clone()),
+            },
+        };
+        let _ = app.emit("device-login-complete", &complete);
+        if let Ok(account) = result {
+            let _ = app.emit("login-success", account.id);
+        }
+    });
+
+    Ok(session)
+}
+
+/// 取消设备码登录
+#[tauri::command]
+pub fn cancel_device_login(session_id: String) -> bool {
+    match DEVICE_SESSIONS.lock().unwrap().get(&session_id) {
+        Some(cancel) => {
+            cancel.cancel();
+            println!("[DeviceLogin] 会话 {} 已取消", session_id);
+            true
+        }
+        None => false,
+    }
+}
@@ -0,0 +1,74 @@
+// 多格式账号导入命令
+
+use tauri::State;
+use crate::state::AppState;
+use crate::account::Account;
+use crate::account_export;
+use crate::importers::{self, AccountImporter, ImportFormatInfo, ImportPreview, MergeStrategy};
+
+/// 选择导入器：指定格式时按 ID 查找，否则按内容自动识别
+fn resolve_importer(format: Option<&str>, content: &str) -> Result<Box<dyn AccountImporter>, String> {
+    match format.filter(|f| !f.is_empty() && *f != "auto") {
+        Some(id) => importers::get_importer(id).ok_or_else(|| format!("不支持的导入格式: {}", id)),
+        None => importers::detect_importer(content).ok_or_else(|| "无法识别导入内容的格式".to_string()),
+    }
+}
+
+fn parse_candidates(
+    format: Option<String>,
+    content: Option<String>,
+    passphrase: Option<String>,
+) -> Result<(String, Vec<Account>), String> {
+    let mut content = content.unwrap_or_default();
+    // 加密导出包：先解密为原始账号 JSON
+    if account_export::is_bundle(&content) {
+        let passphrase = passphrase.filter(|p| !p.is_empty()).ok_or("该文件为加密导出包，请输入密码")?;
+        content = account_export::decrypt_bundle(&content, &passphrase)?;
+    }
+    let importer = resolve_importer(format.as_deref(), &content)?;
+    if importer.requires_content() && content.trim().is_empty() {
+        return Err("导入内容为空".to_string());
+    }
+    let accounts = importer.parse(content.trim())?;
+    println!("[Import] {} 解析出 {} 条记录", importer.get_format_id(), accounts.len());
+    Ok((importer.get_format_id().to_string(), accounts))
+}
+
+/// 获取支持的导入格式
+#[tauri::command]
+pub fn get_import_formats() -> Vec<ImportFormatInfo> {
+    importers::get_import_format_infos()
+}
+
+/// 预览导入（不写入存储）
+#[tauri::command]
+pub fn preview_import_accounts(
+    state: State<AppState>,
+    format: Option<String>,
+    content: Option<String>,
+    strategy: Option<MergeStrategy>,
+    passphrase: Option<String>,
+) -> Result<ImportPreview, String> {
+    let (format_id, candidates) = parse_candidates(format, content, passphrase)?;
+    let store = state.store.lock().unwrap();
+    Ok(importers::plan_import(&store, &format_id, &candidates, strategy.unwrap_or_default()))
+}
+
+/// 按指定格式导入账号
+#[tauri::command]
+pub fn import_accounts_with_format(
+    state: State<AppState>,
+    format: Option<String>,
+    content: Option<String>,
+    strategy: Option<MergeStrategy>,
+    passphrase: Option<String>,
+) -> Result<ImportPreview, String> {
+    let (format_id, candidates) = parse_candidates(format, content, passphrase)?;
+    let mut store = state.store.lock().unwrap();
+    let result = importers::apply_import(&mut store, &format_id, candidates, strategy.unwrap_or_default());
+    println!(
+        "[Import] 新增 {}, 更新 {}, 跳过 {}, 冲突 {}",
+        result.added, result.updated, result.skipped, result.conflicts
+    );
+    Ok(result)
+}
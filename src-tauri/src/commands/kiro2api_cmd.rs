@@ -40,10 +40,46 @@ pub struct Kiro2ApiStatus {
     pub project_path: Option<String>,
     pub log_path: Option<String>,
     pub shared_accounts_file: Option<String>,
-    pub healthy: bool,
+    pub health: Kiro2ApiHealth,
     pub message: Option<String>,
 }
 
+/// 单项健康探测结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Kiro2ApiProbe {
+    pub name: String,
+    pub ok: bool,
+    pub skipped: bool,
+    pub latency_ms: Option<u64>,
+    pub error: Option<String>,
+}
+
+/// Admin API 暴露的单个凭据上游状态
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Kiro2ApiCredentialHealth {
+    pub id: u64,
+    pub email: Option<String>,
+    pub auth_method: Option<String>,
+    pub priority: Option<u32>,
+    pub disabled: bool,
+    pub is_current: bool,
+    pub failure_count: u32,
+    pub expires_at: Option<String>,
+}
+
+/// 健康报告（替代原来的 healthy: bool）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Kiro2ApiHealth {
+    pub healthy: bool,
+    pub checked_at: String,
+    pub probes: Vec<Kiro2ApiProbe>,
+    pub credentials: Vec<Kiro2ApiCredentialHealth>,
+    pub available_credentials: Option<u32>,
+}
+
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Kiro2ApiRequestLog {
@@ -326,16 +362,10 @@ fn account_to_credential(account: &Account, priority: usize, default_region: &st
     let disabled = status_lc.contains("封禁") || status_lc.contains("banned") || status_lc.contains("suspend");
 
     let subscription_title = account
-        .usage_data
+        .usage
         .as_ref()
-        .and_then(|usage| usage.get("subscriptionInfo"))
-        .and_then(|s| {
-            s.get("subscriptionTitle")
-                .or_else(|| s.get("subscriptionName"))
-                .or_else(|| s.get("subscriptionType"))
-        })
-        .and_then(|v| v.as_str())
-        .map(|s| s.to_string());
+        .and_then(|usage| usage.subscription.as_ref())
+        .and_then(|s| s.title.clone().or_else(|| s.subscription_type.clone()));
 
     let region = account
         .region
@@ -732,12 +762,231 @@ fn resolve_log_path_for_read(state: &AppState) -> Result<Option<PathBuf>, String
     Ok(None)
 }
 
-async fn check_health(port: u16, api_key: &str) -> bool {
-    let url = format!("http://127.0.0.1:{}/v1/models", port);
-    let client = reqwest::Client::new();
-    match client.get(url).header("x-api-key", api_key).send().await {
-        Ok(resp) => resp.status().is_success(),
-        Err(_) => false,
+const HEALTH_PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+const MESSAGES_PROBE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// 健康检查所需的运行时快照（避免持锁跨 await）
+struct HealthTarget {
+    alive: bool,
+    port: u16,
+    api_key: String,
+    admin_key: String,
+}
+
+impl Kiro2ApiProbe {
+    fn passed(name: &str, latency_ms: u64) -> Self {
+        Self {
+            name: name.to_string(),
+            ok: true,
+            skipped: false,
+            latency_ms: Some(latency_ms),
+            error: None,
+        }
+    }
+
+    fn failed(name: &str, latency_ms: Option<u64>, error: String) -> Self {
+        Self {
+            name: name.to_string(),
+            ok: false,
+            skipped: false,
+            latency_ms,
+            error: Some(error),
+        }
+    }
+
+    fn skipped(name: &str, reason: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            ok: false,
+            skipped: true,
+            latency_ms: None,
+            error: Some(reason.to_string()),
+        }
+    }
+}
+
+impl Kiro2ApiHealth {
+    fn stopped() -> Self {
+        Self {
+            healthy: false,
+            checked_at: Local::now().to_rfc3339(),
+            probes: vec![Kiro2ApiProbe::failed("process", None, "service is not running".to_string())],
+            credentials: Vec::new(),
+            available_credentials: None,
+        }
+    }
+}
+
+fn elapsed_ms(started: std::time::Instant) -> u64 {
+    started.elapsed().as_millis() as u64
+}
+
+fn probe_port(port: u16) -> Kiro2ApiProbe {
+    let addr = std::net::SocketAddr::from(([127, 0, 0, 1], port));
+    let started = std::time::Instant::now();
+    match std::net::TcpStream::connect_timeout(&addr, HEALTH_PROBE_TIMEOUT) {
+        Ok(_) => Kiro2ApiProbe::passed("port", elapsed_ms(started)),
+        Err(e) => Kiro2ApiProbe::failed(
+            "port",
+            Some(elapsed_ms(started)),
+            format!("port {} not accepting connections: {}", port, e),
+        ),
+    }
+}
+
+async fn probe_http(
+    name: &str,
+    request: reqwest::RequestBuilder,
+) -> (Kiro2ApiProbe, Option<serde_json::Value>) {
+    let started = std::time::Instant::now();
+    match request.send().await {
+        Ok(resp) => {
+            let status = resp.status();
+            let text = resp.text().await.unwrap_or_default();
+            let latency = elapsed_ms(started);
+            if status.is_success() {
+                (Kiro2ApiProbe::passed(name, latency), serde_json::from_str(&text).ok())
+            } else {
+                let body = clamp_status_text(text);
+                (Kiro2ApiProbe::failed(name, Some(latency), format!("HTTP {}: {}", status, body)), None)
+            }
+        }
+        Err(e) => (Kiro2ApiProbe::failed(name, Some(elapsed_ms(started)), e.to_string()), None),
+    }
+}
+
+fn parse_credential_health(value: &serde_json::Value) -> Vec<Kiro2ApiCredentialHealth> {
+    value
+        .get("credentials")
+        .and_then(|v| v.as_array())
+        .map(|items| {
+            items
+                .iter()
+                .map(|item| Kiro2ApiCredentialHealth {
+                    id: item.get("id").and_then(|v| v.as_u64()).unwrap_or(0),
+                    email: item.get("email").and_then(|v| v.as_str()).map(|s| s.to_string()),
+                    auth_method: item.get("authMethod").and_then(|v| v.as_str()).map(|s| s.to_string()),
+                    priority: item.get("priority").and_then(|v| v.as_u64()).map(|v| v as u32),
+                    disabled: item.get("disabled").and_then(|v| v.as_bool()).unwrap_or(false),
+                    is_current: item.get("isCurrent").and_then(|v| v.as_bool()).unwrap_or(false),
+                    failure_count: item.get("failureCount").and_then(|v| v.as_u64()).unwrap_or(0) as u32,
+                    expires_at: item.get("expiresAt").and_then(|v| v.as_str()).map(|s| s.to_string()),
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// 依次执行进程、端口、/v1/models、/v1/messages（可选）和 Admin API 探测
+async fn check_health(target: &HealthTarget, probe_model: Option<&str>) -> Kiro2ApiHealth {
+    let mut probes = Vec::new();
+    let mut credentials = Vec::new();
+    let mut available_credentials = None;
+
+    if target.alive {
+        probes.push(Kiro2ApiProbe::passed("process", 0));
+    } else {
+        probes.push(Kiro2ApiProbe::failed("process", None, "process has exited".to_string()));
+    }
+
+    let port = target.port;
+    let port_probe = tokio::task::spawn_blocking(move || probe_port(port))
+        .await
+        .unwrap_or_else(|e| Kiro2ApiProbe::failed("port", None, format!("Task failed: {}", e)));
+    let port_ok = port_probe.ok;
+    probes.push(port_probe);
+
+    let base_url = format!("http://127.0.0.1:{}", target.port);
+    let client = reqwest::Client::builder()
+        .timeout(HEALTH_PROBE_TIMEOUT)
+        .build()
+        .unwrap_or_default();
+
+    if port_ok {
+        let (models_probe, _) = probe_http(
+            "models",
+            client
+                .get(format!("{}/v1/models", base_url))
+                .header("x-api-key", &target.api_key),
+        )
+        .await;
+        probes.push(models_probe);
+
+        match probe_model.map(|m| m.trim()).filter(|m| !m.is_empty()) {
+            Some(model) => {
+                let body = serde_json::json!({
+                    "model": model,
+                    "max_tokens": 1,
+                    "messages": [{ "role": "user", "content": "ping" }]
+                });
+                let (messages_probe, _) = probe_http(
+                    "messages",
+                    client
+                        .post(format!("{}/v1/messages", base_url))
+                        .header("x-api-key", &target.api_key)
+                        .header("anthropic-version", "2023-06-01")
+                        .timeout(MESSAGES_PROBE_TIMEOUT)
+                        .json(&body),
+                )
+                .await;
+                probes.push(messages_probe);
+            }
+            None => probes.push(Kiro2ApiProbe::skipped("messages", "no probe model selected")),
+        }
+
+        if target.admin_key.trim().is_empty() {
+            probes.push(Kiro2ApiProbe::skipped("admin", "admin key not configured"));
+        } else {
+            let (admin_probe, admin_body) = probe_http(
+                "admin",
+                client
+                    .get(format!("{}/api/admin/credentials", base_url))
+                    .header("Authorization", format!("Bearer {}", target.admin_key)),
+            )
+            .await;
+            if let Some(body) = admin_body {
+                credentials = parse_credential_health(&body);
+                available_credentials = body.get("available").and_then(|v| v.as_u64()).map(|v| v as u32);
+            }
+            probes.push(admin_probe);
+        }
+    } else {
+        for name in ["models", "messages", "admin"] {
+            probes.push(Kiro2ApiProbe::skipped(name, "port is not reachable"));
+        }
+    }
+
+    let healthy = probes.iter().all(|p| p.ok || p.skipped)
+        && available_credentials.map(|n| n > 0).unwrap_or(true);
+
+    Kiro2ApiHealth {
+        healthy,
+        checked_at: Local::now().to_rfc3339(),
+        probes,
+        credentials,
+        available_credentials,
+    }
+}
+
+fn snapshot_health_target(state: &AppState) -> Result<Option<HealthTarget>, String> {
+    let mut runtime = state.kiro2api.lock().map_err(|e| format!("lock failed: {}", e))?;
+    Ok(runtime.as_mut().map(|r| HealthTarget {
+        alive: r.child.try_wait().map(|v| v.is_none()).unwrap_or(false),
+        port: r.port,
+        api_key: r.api_key.clone(),
+        admin_key: r.admin_key.clone(),
+    }))
+}
+
+/// 深度健康检查，可选地对指定模型做一次最小 /v1/messages 往返
+#[tauri::command]
+pub async fn check_kiro2api_health(
+    state: State<'_, AppState>,
+    probe_model: Option<String>,
+) -> Result<Kiro2ApiHealth, String> {
+    match snapshot_health_target(&state)? {
+        Some(target) => Ok(check_health(&target, probe_model.as_deref()).await),
+        None => Ok(Kiro2ApiHealth::stopped()),
     }
 }
 
@@ -779,13 +1028,15 @@ pub async fn get_kiro2api_status(state: State<'_, AppState>) -> Result<Kiro2ApiS
                 r.project_path.clone(),
                 r.log_path.clone(),
                 r.shared_accounts_file.clone(),
-                r.api_key.clone(),
             )
         })
     };
 
-    if let Some((pid, port, project_path, log_path, shared_accounts_file, api_key)) = snapshot {
-        let healthy = check_health(port, &api_key).await;
+    if let Some((pid, port, project_path, log_path, shared_accounts_file)) = snapshot {
+        let health = match snapshot_health_target(&state)? {
+            Some(target) => check_health(&target, None).await,
+            None => Kiro2ApiHealth::stopped(),
+        };
         Ok(Kiro2ApiStatus {
             running: true,
             pid: Some(pid),
@@ -794,7 +1045,7 @@ pub async fn get_kiro2api_status(state: State<'_, AppState>) -> Result<Kiro2ApiS
             project_path: Some(project_path),
             log_path: Some(log_path),
             shared_accounts_file: Some(shared_accounts_file),
-            healthy,
+            health,
             message: None,
         })
     } else {
@@ -806,7 +1057,7 @@ pub async fn get_kiro2api_status(state: State<'_, AppState>) -> Result<Kiro2ApiS
             project_path: None,
             log_path: None,
             shared_accounts_file: None,
-            healthy: false,
+            health: Kiro2ApiHealth::stopped(),
             message: None,
         })
     }
@@ -922,6 +1173,7 @@ pub async fn start_kiro2api_service(
             log_path: log_path.to_string_lossy().to_string(),
             shared_accounts_file: account_store_path().to_string_lossy().to_string(),
             api_key: config.api_key.clone(),
+            admin_key: config.admin_api_key.clone(),
         });
     }
 
@@ -1,7 +1,12 @@
 // Kiro IDE 设置命令 (读写 Kiro IDE 的 settings.json)
 
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use serde_json::Value;
+use tauri::State;
+use crate::jsonc::JsoncDocument;
+use crate::kiro_paths::kiro_settings_path;
+use crate::model_catalog::{self, MODEL_SETTING_KEY};
+use crate::state::AppState;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
@@ -10,103 +15,55 @@ pub struct KiroSettings {
     pub model_selection: Option<String>,
 }
 
-fn get_kiro_settings_path() -> Option<PathBuf> {
-    #[cfg(target_os = "windows")]
-    {
-        std::env::var("APPDATA").ok().map(|appdata| {
-            PathBuf::from(appdata).join("Kiro").join("User").join("settings.json")
-        })
-    }
-    #[cfg(target_os = "macos")]
-    {
-        std::env::var("HOME").ok().map(|home| {
-            PathBuf::from(home)
-                .join("Library")
-                .join("Application Support")
-                .join("Kiro")
-                .join("User")
-                .join("settings.json")
-        })
-    }
-    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
-    {
-        None
-    }
-}
-
 fn get_kiro_settings_inner() -> Result<KiroSettings, String> {
-    let path = get_kiro_settings_path()
-        .ok_or("无法获取 Kiro 设置路径")?;
-    
-    if !path.exists() {
-        return Ok(KiroSettings::default());
-    }
-    
-    let content = std::fs::read_to_string(&path)
-        .map_err(|e| format!("读取设置文件失败: {}", e))?;
-    
-    let json: serde_json::Value = serde_json::from_str(&content)
-        .map_err(|e| format!("解析设置文件失败: {}", e))?;
+    let doc = load_kiro_settings()?;
     
     Ok(KiroSettings {
-        http_proxy: json.get("http.proxy").and_then(|v| v.as_str()).map(|s| s.to_string()),
-        model_selection: json.get("kiroAgent.modelSelection").and_then(|v| v.as_str()).map(|s| s.to_string()),
+        http_proxy: doc.get("http.proxy").and_then(|v| v.as_str().map(|s| s.to_string())),
+        model_selection: doc.get(MODEL_SETTING_KEY).and_then(|v| v.as_str().map(|s| s.to_string())),
     })
 }
 
-fn set_kiro_proxy_inner(proxy: String) -> Result<(), String> {
-    let path = get_kiro_settings_path()
+/// 读取 settings.json（JSONC）
+pub fn load_kiro_settings() -> Result<JsoncDocument, String> {
+    let path = kiro_settings_path()
+        .ok_or("无法获取 Kiro 设置路径")?;
+    JsoncDocument::load(&path)
+        .map_err(|e| format!("解析设置文件失败: {}", e))
+}
+
+/// 读取 settings.json（JSONC），修改后写回；无法解析时拒绝写入，避免覆盖用户的设置
+pub fn edit_kiro_settings(edit: impl FnOnce(&mut JsoncDocument) -> Result<(), String>) -> Result<(), String> {
+    let path = kiro_settings_path()
         .ok_or("无法获取 Kiro 设置路径")?;
     
-    let mut settings: serde_json::Value = if path.exists() {
-        let content = std::fs::read_to_string(&path)
-            .map_err(|e| format!("读取设置文件失败: {}", e))?;
-        serde_json::from_str(&content).unwrap_or(serde_json::json!({}))
-    } else {
-        serde_json::json!({})
-    };
-    
-    if let Some(obj) = settings.as_object_mut() {
+    let mut doc = JsoncDocument::load(&path)
+        .map_err(|e| format!("设置文件格式无法解析，已取消写入: {}", e))?;
+    edit(&mut doc)?;
+    doc.save(&path)
+}
+
+fn set_kiro_proxy_inner(proxy: String) -> Result<(), String> {
+    edit_kiro_settings(|doc| {
         if proxy.is_empty() {
-            obj.remove("http.proxy");
+            doc.remove("http.proxy")?;
         } else {
-            obj.insert("http.proxy".to_string(), serde_json::Value::String(proxy));
-            obj.insert("http.proxyStrictSSL".to_string(), serde_json::Value::Bool(false));
-            obj.insert("http.proxySupport".to_string(), serde_json::Value::String("on".to_string()));
+            doc.set("http.proxy", &Value::String(proxy))?;
+            doc.set("http.proxyStrictSSL", &Value::Bool(false))?;
+            doc.set("http.proxySupport", &Value::String("on".to_string()))?;
         }
-    }
-    
-    let content = serde_json::to_string_pretty(&settings)
-        .map_err(|e| format!("序列化设置失败: {}", e))?;
-    
-    std::fs::write(&path, content)
-        .map_err(|e| format!("写入设置文件失败: {}", e))?;
-    
-    Ok(())
+        Ok(())
+    })
 }
 
 fn set_kiro_model_inner(model: String) -> Result<(), String> {
-    let path = get_kiro_settings_path()
-        .ok_or("无法获取 Kiro 设置路径")?;
-    
-    let mut settings: serde_json::Value = if path.exists() {
-        let content = std::fs::read_to_string(&path)
-            .map_err(|e| format!("读取设置文件失败: {}", e))?;
-        serde_json::from_str(&content).unwrap_or(serde_json::json!({}))
-    } else {
-        serde_json::json!({})
-    };
-    
-    if let Some(obj) = settings.as_object_mut() {
-        obj.insert("kiroAgent.modelSelection".to_string(), serde_json::Value::String(model));
+    edit_kiro_settings(|doc| doc.set(MODEL_SETTING_KEY, &Value::String(model)))
+}
+
+fn check_key(key: &str) -> Result<(), String> {
+    if key.trim().is_empty() {
+        return Err("设置键不能为空".to_string());
     }
-    
-    let content = serde_json::to_string_pretty(&settings)
-        .map_err(|e| format!("序列化设置失败: {}", e))?;
-    
-    std::fs::write(&path, content)
-        .map_err(|e| format!("写入设置文件失败: {}", e))?;
-    
     Ok(())
 }
 
@@ -124,9 +81,58 @@ pub async fn set_kiro_proxy(proxy: String) -> Result<(), String> {
         .map_err(|e| format!("Task failed: {}", e))?
 }
 
+/// 设置模型，按 account_id（界面展示模型列表所用的账号，未指定时为 IDE 当前登录的账号）的模型目录校验
 #[tauri::command]
-pub async fn set_kiro_model(model: String) -> Result<(), String> {
+pub async fn set_kiro_model(state: State<'_, AppState>, model: String, account_id: Option<String>) -> Result<(), String> {
+    model_catalog::ensure_model_available(&state, &model, account_id.as_deref()).await?;
     tokio::task::spawn_blocking(move || set_kiro_model_inner(model))
+    .await
+    .map_err(|e| format!("Task failed: {}", e))?
+}
+
+/// 读取任意设置项，不存在时返回 null
+#[tauri::command]
+pub async fn get_kiro_setting(key: String) -> Result<Option<Value>, String> {
+    tokio::task::spawn_blocking(move || Ok(load_kiro_settings()?.get(&key)))
+        .await
+        .map_err(|e| format!("Task failed: {}", e))?
+}
+
+/// 读取全部设置
+#[tauri::command]
+pub async fn get_all_kiro_settings() -> Result<Value, String> {
+    tokio::task::spawn_blocking(|| Ok(load_kiro_settings()?.value()))
         .await
         .map_err(|e| format!("Task failed: {}", e))?
 }
+
+/// 设置任意设置项（保留类型：字符串、数字、布尔、对象、数组）
+#[tauri::command]
+pub async fn set_kiro_setting(state: State<'_, AppState>, key: String, value: Value) -> Result<(), String> {
+    // 模型设置与 set_kiro_model 一样需要校验
+    if key.trim() == MODEL_SETTING_KEY {
+        model_catalog::ensure_model_setting(&state, &value).await?;
+    }
+    tokio::task::spawn_blocking(move || {
+        check_key(&key)?;
+        edit_kiro_settings(|doc| doc.set(&key, &value))
+    })
+    .await
+    .map_err(|e| format!("Task failed: {}", e))?
+}
+
+/// 删除设置项（恢复 IDE 默认值），返回该键是否存在
+#[tauri::command]
+pub async fn unset_kiro_setting(key: String) -> Result<bool, String> {
+    tokio::task::spawn_blocking(move || {
+        check_key(&key)?;
+        let mut existed = false;
+        edit_kiro_settings(|doc| {
+            existed = doc.remove(&key)?;
+            Ok(())
+        })?;
+        Ok(existed)
+    })
+    .await
+    .map_err(|e| format!("Task failed: {}", e))?
+}
@@ -1,16 +1,24 @@
 // 命令模块
 
 pub mod account_cmd;
+pub mod account_profile_cmd;
+pub mod alert_cmd;
 pub mod app_settings_cmd;
 pub mod auth_cmd;
+pub mod device_auth_cmd;
+pub mod import_cmd;
 
 pub mod kiro_settings_cmd;
 pub mod kiro2api_cmd;
 pub mod machine_guid_cmd;
 pub mod mcp_cmd;
+pub mod model_cmd;
 pub mod powers_cmd;
 pub mod proxy_cmd;
+pub mod settings_profile_cmd;
 pub mod sso_import_cmd;
 pub mod steering_cmd;
+pub mod sync_cmd;
 pub mod update_cmd;
+pub mod usage_cmd;
 pub mod web_oauth_cmd;
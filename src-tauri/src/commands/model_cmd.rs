@@ -0,0 +1,25 @@
+// 模型目录命令
+
+use tauri::State;
+
+use crate::model_catalog::{self, AccountModelCatalog, ModelAvailabilityReport};
+use crate::state::AppState;
+
+/// 获取账号可用的模型（默认使用缓存，force_refresh 为 true 时重新请求）
+#[tauri::command]
+pub async fn get_account_models(
+    state: State<'_, AppState>,
+    account_id: String,
+    force_refresh: Option<bool>,
+) -> Result<AccountModelCatalog, String> {
+    model_catalog::get_catalog(&state, &account_id, force_refresh.unwrap_or(false)).await
+}
+
+/// 各模型在全部账号中的可用情况（refresh 为 true 时重新获取全部账号的模型目录）
+#[tauri::command]
+pub async fn get_model_availability(
+    state: State<'_, AppState>,
+    refresh: Option<bool>,
+) -> Result<ModelAvailabilityReport, String> {
+    Ok(model_catalog::availability(&state, refresh.unwrap_or(false)).await)
+}
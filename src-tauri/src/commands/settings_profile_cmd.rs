@@ -0,0 +1,71 @@
+// Kiro IDE 设置方案命令
+
+use tauri::State;
+
+use crate::model_catalog::{self, MODEL_SETTING_KEY};
+use crate::settings_profile::{self, AppliedProfile, SettingDiff, SettingsProfile, SettingsProfileInput};
+use crate::state::AppState;
+
+#[tauri::command]
+pub async fn get_settings_profiles() -> Result<Vec<SettingsProfile>, String> {
+    tokio::task::spawn_blocking(settings_profile::list_profiles)
+        .await
+        .map_err(|e| format!("Task failed: {}", e))?
+}
+
+#[tauri::command]
+pub async fn save_settings_profile(state: State<'_, AppState>, profile: SettingsProfileInput) -> Result<SettingsProfile, String> {
+    if let Some(model) = profile.settings.get(MODEL_SETTING_KEY) {
+        model_catalog::ensure_model_setting(&state, model).await?;
+    }
+    tokio::task::spawn_blocking(move || settings_profile::save_profile(profile))
+        .await
+        .map_err(|e| format!("Task failed: {}", e))?
+}
+
+#[tauri::command]
+pub async fn delete_settings_profile(id: String) -> Result<(), String> {
+    tokio::task::spawn_blocking(move || settings_profile::delete_profile(&id))
+        .await
+        .map_err(|e| format!("Task failed: {}", e))?
+}
+
+/// 对比方案与当前 settings.json
+#[tauri::command]
+pub async fn diff_settings_profile(id: String) -> Result<Vec<SettingDiff>, String> {
+    tokio::task::spawn_blocking(move || settings_profile::diff_profile(&id))
+        .await
+        .map_err(|e| format!("Task failed: {}", e))?
+}
+
+/// 应用方案，返回实际修改的项
+#[tauri::command]
+pub async fn apply_settings_profile(state: State<'_, AppState>, id: String) -> Result<Vec<SettingDiff>, String> {
+    // 方案中的模型按当前登录账号重新校验（保存后账号可能已切换）
+    let lookup_id = id.clone();
+    let profile = tokio::task::spawn_blocking(move || settings_profile::get_profile(&lookup_id))
+        .await
+        .map_err(|e| format!("Task failed: {}", e))??;
+    if let Some(model) = profile.settings.get(MODEL_SETTING_KEY) {
+        model_catalog::ensure_model_setting(&state, model).await?;
+    }
+    tokio::task::spawn_blocking(move || settings_profile::apply_profile(&id))
+        .await
+        .map_err(|e| format!("Task failed: {}", e))?
+}
+
+/// 撤销最近一次应用的方案
+#[tauri::command]
+pub async fn revert_settings_profile() -> Result<AppliedProfile, String> {
+    tokio::task::spawn_blocking(settings_profile::revert_last_applied)
+        .await
+        .map_err(|e| format!("Task failed: {}", e))?
+}
+
+/// 方案应用记录（最新的在前）
+#[tauri::command]
+pub async fn get_applied_settings_profiles() -> Result<Vec<AppliedProfile>, String> {
+    tokio::task::spawn_blocking(settings_profile::list_applied)
+        .await
+        .map_err(|e| format!("Task failed: {}", e))?
+}
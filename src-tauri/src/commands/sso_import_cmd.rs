@@ -5,6 +5,8 @@ use serde::{Deserialize, Serialize};
 use tauri::State;
 use crate::state::AppState;
 use crate::account::Account;
+use crate::account_health::AccountHealth;
+use crate::usage::UsageSummary;
 use crate::kiro::get_machine_id;
 use crate::codewhisperer_client::CodeWhispererClient;
 
@@ -306,6 +308,7 @@ pub async fn import_from_sso_token(
     
     let usage = cw_client.get_usage_limits(&token_data.access_token).await.ok();
     let usage_data = serde_json::to_value(&usage).unwrap_or(serde_json::Value::Null);
+    let usage_summary = usage.as_ref().map(UsageSummary::from);
     
     // 从 usage 中提取 email
     let email = usage.as_ref()
@@ -339,8 +342,8 @@ pub async fn import_from_sso_token(
         existing.client_id_hash = Some(client_id_hash);
         existing.region = Some(region);
         existing.expires_at = Some(expires_at.to_rfc3339());
-        existing.usage_data = Some(usage_data);
-        existing.status = "正常".to_string();
+        existing.set_usage(usage_summary, usage_data);
+        existing.set_health(AccountHealth::Active, None);
         existing.user_id = user_id;
     } else {
         let mut account = Account::new(email.clone(), email.clone());
@@ -352,7 +355,7 @@ pub async fn import_from_sso_token(
         account.client_id_hash = Some(client_id_hash);
         account.region = Some(region);
         account.expires_at = Some(expires_at.to_rfc3339());
-        account.usage_data = Some(usage_data);
+        account.set_usage(usage_summary, usage_data);
         account.user_id = user_id;
         store.accounts.insert(0, account);
     }
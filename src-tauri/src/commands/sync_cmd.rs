@@ -0,0 +1,329 @@
+// 批量同步命令 - 有限并发 + 按主机限速，逐个账号发送进度事件，可取消
+// 所有账号的网络请求完成后统一写回存储并只保存一次
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, State};
+use tokio::sync::{watch, Semaphore};
+use tokio::task::JoinSet;
+
+use crate::account::Account;
+use crate::account_health::AccountHealth;
+use crate::account_sync::{self, SyncErrorKind, SyncResult};
+use crate::alerts;
+use crate::state::AppState;
+use crate::usage::UsageSummary;
+use crate::usage_history;
+
+/// 正在进行的批量同步：batch_id -> 取消信号
+static RUNNING_SYNCS: Lazy<Mutex<HashMap<String, watch::Sender<bool>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SyncOptions {
+    /// 同时进行的账号数
+    pub concurrency: usize,
+    /// 同一主机两次请求的最小间隔（毫秒）
+    pub per_host_interval_ms: u64,
+    /// 只刷新 Token，不获取用量
+    pub refresh_only: bool,
+    /// 批次 ID（用于取消和区分进度事件），为空时自动生成
+    pub batch_id: Option<String>,
+}
+
+impl Default for SyncOptions {
+    fn default() -> Self {
+        Self {
+            concurrency: 4,
+            per_host_interval_ms: 300,
+            refresh_only: false,
+            batch_id: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SyncStatus {
+    Success,
+    Expired,
+    Suspended,
+    Network,
+    Failed,
+    Cancelled,
+}
+
+impl From<SyncErrorKind> for SyncStatus {
+    fn from(kind: SyncErrorKind) -> Self {
+        match kind {
+            SyncErrorKind::Expired => SyncStatus::Expired,
+            SyncErrorKind::Suspended => SyncStatus::Suspended,
+            SyncErrorKind::Network => SyncStatus::Network,
+            SyncErrorKind::Other => SyncStatus::Failed,
+        }
+    }
+}
+
+/// 单个账号的同步结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountSyncOutcome {
+    pub account_id: String,
+    pub email: String,
+    pub status: SyncStatus,
+    pub error: Option<String>,
+}
+
+/// 进度事件 sync-accounts-progress
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SyncProgress<'a> {
+    batch_id: &'a str,
+    #[serde(flatten)]
+    outcome: &'a AccountSyncOutcome,
+    completed: usize,
+    total: usize,
+}
+
+/// 批量同步汇总
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncReport {
+    pub batch_id: String,
+    pub total: usize,
+    pub success: usize,
+    pub expired: usize,
+    pub suspended: usize,
+    pub network: usize,
+    pub failed: usize,
+    pub cancelled: usize,
+    pub results: Vec<AccountSyncOutcome>,
+}
+
+impl SyncReport {
+    fn count(&mut self, status: SyncStatus) {
+        match status {
+            SyncStatus::Success => self.success += 1,
+            SyncStatus::Expired => self.expired += 1,
+            SyncStatus::Suspended => self.suspended += 1,
+            SyncStatus::Network => self.network += 1,
+            SyncStatus::Failed => self.failed += 1,
+            SyncStatus::Cancelled => self.cancelled += 1,
+        }
+    }
+}
+
+/// 按主机限速：每个主机记录下一次允许请求的时间
+#[derive(Clone)]
+struct HostRateLimiter {
+    interval: Duration,
+    next_slot: Arc<tokio::sync::Mutex<HashMap<String, Instant>>>,
+}
+
+impl HostRateLimiter {
+    fn new(interval: Duration) -> Self {
+        Self { interval, next_slot: Arc::new(tokio::sync::Mutex::new(HashMap::new())) }
+    }
+
+    async fn wait(&self, host: &str) {
+        let delay = {
+            let mut slots = self.next_slot.lock().await;
+            let now = Instant::now();
+            let slot = slots.get(host).copied().filter(|t| *t > now).unwrap_or(now);
+            slots.insert(host.to_string(), slot + self.interval);
+            slot - now
+        };
+        if !delay.is_zero() {
+            tokio::time::sleep(delay).await;
+        }
+    }
+}
+
+/// 批量同步账号（ids 为空时同步全部账号）
+#[tauri::command]
+pub async fn sync_accounts(
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+    ids: Vec<String>,
+    options: Option<SyncOptions>,
+) -> Result<SyncReport, String> {
+    let options = options.unwrap_or_default();
+    let accounts: Vec<Account> = {
+        let store = state.store.lock().unwrap();
+        store.accounts.iter()
+            .filter(|a| ids.is_empty() || ids.contains(&a.id))
+            .cloned()
+            .collect()
+    };
+
+    let batch_id = options.batch_id.clone().unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    let (cancel_tx, cancel_rx) = watch::channel(false);
+    {
+        let mut running = RUNNING_SYNCS.lock().unwrap();
+        if running.contains_key(&batch_id) {
+            return Err(format!("批量同步 {} 正在进行中", batch_id));
+        }
+        running.insert(batch_id.clone(), cancel_tx);
+    }
+
+    let total = accounts.len();
+    println!("[sync_accounts] 批次 {} 开始同步 {} 个账号", batch_id, total);
+
+    let semaphore = Arc::new(Semaphore::new(options.concurrency.max(1)));
+    let limiter = HostRateLimiter::new(Duration::from_millis(options.per_host_interval_ms));
+    let with_usage = !options.refresh_only;
+
+    // 任务 ID -> 账号，任务异常退出时仍能给该账号记录结果
+    let mut tasks = JoinSet::new();
+    let mut task_accounts: HashMap<tokio::task::Id, Account> = HashMap::new();
+    for account in accounts {
+        let semaphore = semaphore.clone();
+        let limiter = limiter.clone();
+        let mut cancel_rx = cancel_rx.clone();
+        let task_account = account.clone();
+        let handle = tasks.spawn(async move {
+            // 等待并发名额、限速和请求本身都与取消信号竞争，取消后立即结束
+            let work = async {
+                let _permit = semaphore.acquire_owned().await;
+                limiter.wait(&account_sync::refresh_host(&task_account)).await;
+                account_sync::fetch_sync(&task_account, with_usage).await
+            };
+            let result = tokio::select! {
+                result = work => Some(result),
+                _ = cancel_rx.wait_for(|cancelled| *cancelled) => None,
+            };
+            (task_account, result)
+        });
+        task_accounts.insert(handle.id(), account);
+    }
+
+    let mut report = SyncReport { batch_id: batch_id.clone(), total, ..Default::default() };
+    let mut fetched: Vec<(String, SyncResult)> = Vec::new();
+    let mut failures: Vec<(Account, String)> = Vec::new();
+
+    while let Some(joined) = tasks.join_next_with_id().await {
+        let (account, result) = match joined {
+            Ok((_, v)) => v,
+            Err(e) => {
+                println!("[sync_accounts] 同步任务异常: {}", e);
+                let Some(account) = task_accounts.remove(&e.id()) else { continue };
+                (account, Some(Err(format!("同步任务异常: {}", e))))
+            }
+        };
+        let (status, error) = match result {
+            None => (SyncStatus::Cancelled, None),
+            Some(Ok(sync)) => {
+                // Token 已刷新，封禁等状态只能从用量接口的结果判断
+                let usage_error = sync.usage.as_ref().and_then(|u| u.error.clone());
+                let status = match (sync.usage.as_ref().map(|u| &u.health), usage_error.as_deref()) {
+                    (Some(AccountHealth::Suspended { .. }), _) => SyncStatus::Suspended,
+                    (_, Some(e)) => SyncStatus::from(account_sync::classify_error(e)),
+                    _ => SyncStatus::Success,
+                };
+                fetched.push((account.id.clone(), sync));
+                (status, usage_error)
+            }
+            Some(Err(e)) => {
+                failures.push((account.clone(), e.clone()));
+                (SyncStatus::from(account_sync::classify_error(&e)), Some(e))
+            }
+        };
+
+        let outcome = AccountSyncOutcome {
+            account_id: account.id.clone(),
+            email: account.email.clone(),
+            status,
+            error,
+        };
+        report.count(status);
+        let _ = app_handle.emit("sync-accounts-progress", SyncProgress {
+            batch_id: &batch_id,
+            outcome: &outcome,
+            completed: report.results.len() + 1,
+            total,
+        });
+        report.results.push(outcome);
+    }
+
+    RUNNING_SYNCS.lock().unwrap().remove(&batch_id);
+
+    // 记录用量历史（在阻塞线程写数据库，不占用账号存储锁）
+    let snapshots: Vec<(String, UsageSummary)> = fetched.iter()
+        .filter_map(|(id, sync)| {
+            let summary = sync.usage.as_ref().and_then(|u| u.summary.clone())?;
+            Some((id.clone(), summary))
+        })
+        .collect();
+    if !snapshots.is_empty() {
+        let recorded = tokio::task::spawn_blocking(move || {
+            for (id, summary) in &snapshots {
+                if let Err(e) = usage_history::record_snapshot(id, summary) {
+                    println!("[sync_accounts] 记录用量历史失败: {}", e);
+                }
+            }
+        }).await;
+        if let Err(e) = recorded {
+            println!("[sync_accounts] 记录用量历史失败: {}", e);
+        }
+    }
+
+    // 一次性写回并保存（同时保留用量接口的错误用于告警）
+    let updated: Vec<(Account, Option<String>)> = {
+        let mut store = state.store.lock().unwrap();
+        let mut updated = Vec::new();
+        for (id, sync) in fetched {
+            if let Some(a) = store.accounts.iter_mut().find(|a| a.id == id) {
+                let usage_error = sync.usage.as_ref().and_then(|u| u.error.clone());
+                account_sync::apply_sync(a, sync);
+                updated.push((a.clone(), usage_error));
+            }
+        }
+        for (account, error) in failures.iter_mut() {
+            if let Some(a) = store.accounts.iter_mut().find(|a| a.id == account.id) {
+                a.record_error(error);
+                *account = a.clone();
+            }
+        }
+        if !updated.is_empty() || !failures.is_empty() {
+            store.save_to_file();
+        }
+        updated
+    };
+
+    if with_usage {
+        for (account, usage_error) in &updated {
+            match usage_error {
+                Some(error) => alerts::process_sync_error(&app_handle, account, error),
+                None => alerts::process_sync_result(&app_handle, account, None),
+            };
+        }
+    }
+    for (account, error) in &failures {
+        alerts::process_sync_error(&app_handle, account, error);
+    }
+
+    println!(
+        "[sync_accounts] 批次 {} 完成: 成功 {}, 过期 {}, 封禁 {}, 网络错误 {}, 其他失败 {}, 已取消 {}",
+        batch_id, report.success, report.expired, report.suspended, report.network, report.failed, report.cancelled
+    );
+    Ok(report)
+}
+
+/// 取消批量同步（未指定 batch_id 时取消全部）；进行中的请求会被中止
+#[tauri::command]
+pub fn cancel_sync_accounts(batch_id: Option<String>) -> usize {
+    let running = RUNNING_SYNCS.lock().unwrap();
+    let mut count = 0;
+    for (id, cancel_tx) in running.iter() {
+        if batch_id.as_deref().is_none_or(|b| b == id) {
+            cancel_tx.send_replace(true);
+            count += 1;
+        }
+    }
+    count
+}
@@ -24,23 +24,7 @@ pub struct UpdateCheckResult {
 
 /// 获取 Kiro IDE 设置中的代理
 fn get_proxy_from_kiro_settings() -> Option<String> {
-    #[cfg(target_os = "windows")]
-    let path = std::env::var("APPDATA").ok().map(|appdata| {
-        std::path::PathBuf::from(appdata).join("Kiro").join("User").join("settings.json")
-    });
-    
-    #[cfg(target_os = "macos")]
-    let path = std::env::var("HOME").ok().map(|home| {
-        std::path::PathBuf::from(home)
-            .join("Library")
-            .join("Application Support")
-            .join("Kiro")
-            .join("User")
-            .join("settings.json")
-    });
-    
-    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
-    let path: Option<std::path::PathBuf> = None;
+    let path = crate::kiro_paths::kiro_settings_path();
     
     path.and_then(|p| {
         if p.exists() {
@@ -50,7 +34,7 @@ fn get_proxy_from_kiro_settings() -> Option<String> {
         }
     })
     .and_then(|content| {
-        serde_json::from_str::<serde_json::Value>(&content).ok()
+        crate::jsonc::parse(&content).ok()
     })
     .and_then(|json| {
         json.get("http.proxy")
@@ -0,0 +1,46 @@
+// 用量历史命令
+
+use crate::usage_history::{self, BurnRate, DailyUsageDelta, UsageSnapshot};
+
+/// 获取账号的用量时间序列（毫秒时间戳范围）
+#[tauri::command]
+pub async fn get_usage_history(
+    account_id: String,
+    from: Option<i64>,
+    to: Option<i64>,
+    resource_type: Option<String>,
+) -> Result<Vec<UsageSnapshot>, String> {
+    tokio::task::spawn_blocking(move || {
+        usage_history::get_series(&account_id, from, to, resource_type.as_deref())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// 获取账号最近 N 天（默认 30 天）的每日消耗
+#[tauri::command]
+pub async fn get_usage_daily_deltas(
+    account_id: String,
+    days: Option<u32>,
+    resource_type: Option<String>,
+) -> Result<Vec<DailyUsageDelta>, String> {
+    tokio::task::spawn_blocking(move || {
+        usage_history::get_daily_deltas(&account_id, days.unwrap_or(30), resource_type.as_deref())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// 估算账号的消耗速度（默认按最近 7 天计算）
+#[tauri::command]
+pub async fn get_usage_burn_rate(
+    account_id: String,
+    window_days: Option<u32>,
+    resource_type: Option<String>,
+) -> Result<BurnRate, String> {
+    tokio::task::spawn_blocking(move || {
+        usage_history::get_burn_rate(&account_id, window_days.unwrap_or(7), resource_type.as_deref())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
@@ -1,17 +1,13 @@
 // Web OAuth 命令 - 直接存储 usage_data
 
-use std::sync::{Mutex, OnceLock};
 use tauri::{AppHandle, Emitter, Manager, State, WebviewUrl, WebviewWindowBuilder};
 use crate::state::AppState;
 use crate::account::Account;
+use crate::account_health::AccountHealth;
+use crate::usage::UsageSummary;
 use crate::auth::User;
-use crate::providers::web_oauth::{WebOAuthProvider, WebOAuthInitResult};
-
-static PENDING_LOGIN: OnceLock<Mutex<Option<WebOAuthInitResult>>> = OnceLock::new();
-
-fn get_pending_login() -> &'static Mutex<Option<WebOAuthInitResult>> {
-    PENDING_LOGIN.get_or_init(|| Mutex::new(None))
-}
+use crate::login_session::{self, LOGIN_SESSION_TIMEOUT};
+use crate::providers::web_oauth::WebOAuthProvider;
 
 #[tauri::command]
 pub async fn web_oauth_initiate(provider: String) -> Result<WebOAuthInitResponse, String> {
@@ -34,7 +30,7 @@ pub async fn web_oauth_initiate(provider: String) -> Result<WebOAuthInitResponse
                 state: init_result.state.clone(),
             };
             
-            *get_pending_login().lock().unwrap() = Some(init_result);
+            login_session::register_web_oauth(init_result, LOGIN_SESSION_TIMEOUT);
             println!("========== web_oauth_initiate SUCCESS ==========\n");
             
             Ok(response)
@@ -73,10 +69,8 @@ pub async fn web_oauth_complete(
         .map(|(_, v)| v.to_string())
         .ok_or("No 'state' parameter in callback URL")?;
     
-    let init_result = {
-        let mut pending_guard = get_pending_login().lock().unwrap();
-        pending_guard.take()
-    }.ok_or("No pending authentication state found")?;
+    let init_result = login_session::take_web_oauth(&returned_state)
+        .ok_or("No pending authentication state found")?;
     
     let web_provider = WebOAuthProvider::new(&init_result.provider_id);
     let auth_result = web_provider.complete_login(
@@ -110,6 +104,7 @@ pub async fn web_oauth_complete(
         &init_result.idp,
     ).await?;
     let usage_data = serde_json::to_value(&usage).unwrap_or(serde_json::Value::Null);
+    let usage_summary = Some(UsageSummary::from(&usage));
 
     let mut store = state.store.lock().unwrap();
     
@@ -122,8 +117,8 @@ pub async fn web_oauth_complete(
         existing.expires_at = Some(auth_result.expires_at.clone());
         existing.profile_arn = auth_result.profile_arn.clone();
         existing.csrf_token = auth_result.csrf_token.clone();
-        existing.usage_data = Some(usage_data);
-        existing.status = "正常".to_string();
+        existing.set_usage(usage_summary, usage_data);
+        existing.set_health(AccountHealth::Active, None);
         existing.clone()
     } else {
         // 新建账号
@@ -135,7 +130,7 @@ pub async fn web_oauth_complete(
         account.expires_at = Some(auth_result.expires_at.clone());
         account.profile_arn = auth_result.profile_arn.clone();
         account.csrf_token = auth_result.csrf_token.clone();
-        account.usage_data = Some(usage_data);
+        account.set_usage(usage_summary, usage_data);
         store.accounts.insert(0, account.clone());
         account
     };
@@ -190,6 +185,7 @@ pub async fn web_oauth_refresh(
         idp,
     ).await.ok();
     let usage_data = serde_json::to_value(&usage).unwrap_or(serde_json::Value::Null);
+    let usage_summary = usage.as_ref().map(UsageSummary::from);
 
     let mut store = state.store.lock().unwrap();
     if let Some(a) = store.accounts.iter_mut().find(|a| a.id == account_id) {
@@ -197,8 +193,8 @@ pub async fn web_oauth_refresh(
         a.refresh_token = Some(auth_result.refresh_token);
         a.csrf_token = auth_result.csrf_token;
         a.expires_at = Some(auth_result.expires_at);
-        a.usage_data = Some(usage_data);
-        a.status = "正常".to_string();
+        a.set_usage(usage_summary, usage_data);
+        a.set_health(AccountHealth::Active, None);
         if auth_result.profile_arn.is_some() {
             a.profile_arn = auth_result.profile_arn;
         }
@@ -249,8 +245,7 @@ pub async fn web_oauth_login(
     println!("Authorize URL: {}", init_result.authorize_url);
     println!("State: {}", init_result.state);
     
-    *get_pending_login().lock().unwrap() = Some(init_result.clone());
-    println!("Saved init_result to PENDING_LOGIN, state: {}", init_result.state);
+    login_session::register_web_oauth(init_result.clone(), LOGIN_SESSION_TIMEOUT);
     
     let window_label = format!("oauth_{}", provider.to_lowercase());
     
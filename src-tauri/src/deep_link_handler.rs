@@ -1,10 +1,11 @@
 // Deep Link 回调处理
 // 处理 kiro://kiro.kiroAgent/authenticate-success?code=xxx&state=xxx 格式的 OAuth 回调
 
-use std::sync::mpsc::{self, Receiver, Sender};
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
+use crate::login_session::{self, CallbackReceiver, LOGIN_SESSION_TIMEOUT};
+
 /// OAuth 回调结果
 #[derive(Debug, Clone)]
 pub struct OAuthCallbackResult {
@@ -12,9 +13,18 @@ pub struct OAuthCallbackResult {
     pub state: String,
 }
 
+/// OAuth 回调等待器（kiro:// deep link 和本地回环监听共用）
+pub trait CallbackWaiter: Send {
+    /// 授权请求中使用的 redirect_uri
+    fn redirect_uri(&self) -> String;
+    /// 阻塞等待回调结果
+    fn wait_for_callback(&self) -> Result<OAuthCallbackResult, String>;
+}
+
 /// Deep Link OAuth 回调等待器
 pub struct DeepLinkCallbackWaiter {
-    result_rx: Arc<Mutex<Option<Receiver<Result<OAuthCallbackResult, String>>>>>,
+    state: String,
+    result_rx: Arc<Mutex<Option<CallbackReceiver>>>,
     timeout: Duration,
 }
 
@@ -23,9 +33,15 @@ impl DeepLinkCallbackWaiter {
     pub fn get_redirect_uri() -> String {
         "kiro://kiro.kiroAgent/authenticate-success".to_string()
     }
+}
+
+impl CallbackWaiter for DeepLinkCallbackWaiter {
+    fn redirect_uri(&self) -> String {
+        Self::get_redirect_uri()
+    }
 
     /// 等待回调结果
-    pub fn wait_for_callback(&self) -> Result<OAuthCallbackResult, String> {
+    fn wait_for_callback(&self) -> Result<OAuthCallbackResult, String> {
         let rx = self.result_rx.lock().unwrap().take()
             .ok_or("Callback channel already consumed")?;
 
@@ -36,50 +52,32 @@ impl DeepLinkCallbackWaiter {
     }
 }
 
-/// 全局回调发送器存储
-static PENDING_SENDER: std::sync::OnceLock<Mutex<Option<(String, Sender<Result<OAuthCallbackResult, String>>)>>> = std::sync::OnceLock::new();
-
-/// 注册一个新的回调等待器，返回接收端
-pub fn register_waiter(state: &str) -> DeepLinkCallbackWaiter {
-    let (tx, rx) = mpsc::channel();
-    
-    // 存储发送端
-    let storage = PENDING_SENDER.get_or_init(|| Mutex::new(None));
-    *storage.lock().unwrap() = Some((state.to_string(), tx));
-    
+impl Drop for DeepLinkCallbackWaiter {
+    // 超时、登录中途失败时清理会话（已收到回调的会话已被移除）
+    fn drop(&mut self) {
+        login_session::remove(&self.state);
+    }
+}
+
+/// 注册一个新的回调等待器（按 state 加入登录会话注册表）
+pub fn register_waiter(state: &str, provider: &str) -> DeepLinkCallbackWaiter {
+    let rx = login_session::register_callback(state, provider, LOGIN_SESSION_TIMEOUT);
     DeepLinkCallbackWaiter {
+        state: state.to_string(),
         result_rx: Arc::new(Mutex::new(Some(rx))),
-        timeout: Duration::from_secs(300),
+        timeout: LOGIN_SESSION_TIMEOUT,
     }
 }
 
 /// 处理 deep link URL（由 main.rs 调用）
 pub fn handle_deep_link(url: &str) -> bool {
     println!("[DeepLink] Processing URL: {}", url);
-    
-    let storage = match PENDING_SENDER.get() {
-        Some(s) => s,
-        None => {
-            println!("[DeepLink] No pending sender");
-            return false;
-        }
-    };
-    
-    let mut guard = storage.lock().unwrap();
-    let (expected_state, tx) = match guard.take() {
-        Some(s) => s,
-        None => {
-            println!("[DeepLink] No pending waiter");
-            return false;
-        }
-    };
-    
+
     // 解析 URL
     let parsed = match url::Url::parse(url) {
         Ok(u) => u,
         Err(e) => {
             println!("[DeepLink] URL parse error: {}", e);
-            let _ = tx.send(Err(format!("Invalid URL: {}", e)));
             return false;
         }
     };
@@ -87,49 +85,40 @@ pub fn handle_deep_link(url: &str) -> bool {
     // 检查是否是 kiro:// 协议
     if parsed.scheme() != "kiro" {
         println!("[DeepLink] Not kiro:// scheme");
-        *guard = Some((expected_state, tx)); // 放回去
         return false;
     }
 
     // 提取参数
     let params: std::collections::HashMap<_, _> = parsed.query_pairs().collect();
-    
-    // 检查错误
-    if let Some(error) = params.get("error") {
-        let desc = params.get("error_description")
-            .map(|s| s.to_string())
-            .unwrap_or_else(|| "Unknown error".to_string());
-        println!("[DeepLink] OAuth error: {} - {}", error, desc);
-        let _ = tx.send(Err(format!("OAuth error: {} - {}", error, desc)));
-        return true;
-    }
-    
-    let code = match params.get("code") {
-        Some(c) => c.to_string(),
-        None => {
-            println!("[DeepLink] Missing code parameter");
-            let _ = tx.send(Err("Missing code parameter".to_string()));
-            return true;
-        }
-    };
 
+    // 按 state 找到对应的登录会话
     let state = match params.get("state") {
         Some(s) => s.to_string(),
         None => {
-            println!("[DeepLink] Missing state parameter");
-            let _ = tx.send(Err("Missing state parameter".to_string()));
-            return true;
+            println!("[DeepLink] Unmatched callback: missing state parameter");
+            return false;
         }
     };
 
-    // 验证 state
-    if state != expected_state {
-        println!("[DeepLink] State mismatch: expected {}, got {}", expected_state, state);
-        let _ = tx.send(Err("State mismatch - possible CSRF attack".to_string()));
-        return true;
-    }
+    // 检查错误
+    let result = if let Some(error) = params.get("error") {
+        let desc = params.get("error_description")
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| "Unknown error".to_string());
+        println!("[DeepLink] OAuth error: {} - {}", error, desc);
+        Err(format!("OAuth error: {} - {}", error, desc))
+    } else {
+        match params.get("code") {
+            Some(code) => {
+                println!("[DeepLink] Callback success, code: {}...", &code[..20.min(code.len())]);
+                Ok(OAuthCallbackResult { code: code.to_string(), state: state.clone() })
+            }
+            None => {
+                println!("[DeepLink] Missing code parameter");
+                Err("Missing code parameter".to_string())
+            }
+        }
+    };
 
-    println!("[DeepLink] Callback success, code: {}...", &code[..20.min(code.len())]);
-    let _ = tx.send(Ok(OAuthCallbackResult { code, state }));
-    true
+    login_session::deliver(&state, result)
 }
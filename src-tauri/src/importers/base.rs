@@ -0,0 +1,130 @@
+// Base Importer - 导入器接口和预览结构
+
+use crate::account::Account;
+use serde::Serialize;
+
+/// 导入格式信息（供前端展示）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportFormatInfo {
+    pub id: String,
+    pub name: String,
+    pub requires_content: bool,
+}
+
+/// 单条记录的导入动作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportAction {
+    Add,
+    Update,
+    Skip,
+    Conflict,
+}
+
+/// 导入预览条目
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportPreviewItem {
+    pub action: ImportAction,
+    pub email: String,
+    pub provider: Option<String>,
+    pub existing_id: Option<String>,
+    /// 命中的身份键：refreshToken / userId / emailProvider / clientId
+    pub matched_by: Option<String>,
+    pub reason: Option<String>,
+}
+
+/// 导入预览 / 导入结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportPreview {
+    pub format: String,
+    pub items: Vec<ImportPreviewItem>,
+    pub added: usize,
+    pub updated: usize,
+    pub skipped: usize,
+    pub conflicts: usize,
+}
+
+/// 账号导入器接口
+pub trait AccountImporter: Send + Sync {
+    /// 获取格式 ID
+    fn get_format_id(&self) -> &str;
+
+    /// 获取展示名称
+    fn get_display_name(&self) -> &str;
+
+    /// 是否需要用户提供文件内容（扫描本地目录的导入器不需要）
+    fn requires_content(&self) -> bool {
+        true
+    }
+
+    /// 判断内容是否属于该格式
+    fn detect(&self, content: &str) -> bool;
+
+    /// 解析为待导入的账号
+    fn parse(&self, content: &str) -> Result<Vec<Account>, String>;
+}
+
+// ============================================================
+// 各导入器共用的工具函数
+// ============================================================
+
+/// 读取 JSON 对象中第一个非空的字符串字段（兼容 camelCase / snake_case）
+pub(super) fn json_str(value: &serde_json::Value, keys: &[&str]) -> Option<String> {
+    keys.iter()
+        .filter_map(|k| value.get(*k))
+        .filter_map(|v| v.as_str())
+        .map(|s| s.trim())
+        .find(|s| !s.is_empty())
+        .map(|s| s.to_string())
+}
+
+/// JSON 顶层可能是数组、单个对象，或包在 accounts / credentials / results 中
+pub(super) fn json_items(value: &serde_json::Value) -> Vec<&serde_json::Value> {
+    if let Some(list) = value.as_array() {
+        return list.iter().collect();
+    }
+    for key in ["accounts", "credentials", "results"] {
+        if let Some(list) = value.get(key).and_then(|v| v.as_array()) {
+            return list.iter().collect();
+        }
+    }
+    if value.is_object() {
+        return vec![value];
+    }
+    Vec::new()
+}
+
+/// 将 RFC3339 / 毫秒时间戳转换为账号存储使用的本地时间格式
+pub(super) fn normalize_expires_at(value: Option<&serde_json::Value>) -> Option<String> {
+    let value = value?;
+    let dt = if let Some(raw) = value.as_str() {
+        if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(raw.trim()) {
+            dt.with_timezone(&chrono::Local)
+        } else {
+            // 已经是 %Y/%m/%d %H:%M:%S 格式，原样保留
+            return Some(raw.trim().to_string()).filter(|s| !s.is_empty());
+        }
+    } else {
+        let millis = value.as_i64()?;
+        chrono::DateTime::from_timestamp_millis(millis)?.with_timezone(&chrono::Local)
+    };
+    Some(dt.format("%Y/%m/%d %H:%M:%S").to_string())
+}
+
+/// 计算 start URL 对应的 clientIdHash
+pub(super) fn client_id_hash_for(start_url: &str) -> String {
+    crate::providers::IdcProvider::compute_client_id_hash(start_url)
+}
+
+/// 导入账号的默认标签，provider 未知时不写
+pub(super) fn provider_label(provider: Option<&str>) -> String {
+    match provider {
+        Some(provider) => format!("Kiro {} 账号", provider),
+        None => "Kiro 账号".to_string(),
+    }
+}
+
+pub(super) use crate::providers::BUILDER_ID_START_URL;
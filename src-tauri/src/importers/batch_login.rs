@@ -0,0 +1,85 @@
+// Batch Login Importer - kiro-batch-login 的输出
+// 每条记录是一次登录的 AuthResult（camelCase）加上 email，失败的记录带 success: false
+
+use crate::account::{Account, UNKNOWN_EMAIL};
+use super::AccountImporter;
+use super::base::{client_id_hash_for, json_items, json_str, normalize_expires_at, BUILDER_ID_START_URL};
+
+pub struct BatchLoginImporter;
+
+/// 兼容 { email, result: {...} } 和扁平结构
+fn token_fields(item: &serde_json::Value) -> &serde_json::Value {
+    item.get("result")
+        .or_else(|| item.get("tokens"))
+        .filter(|v| v.is_object())
+        .unwrap_or(item)
+}
+
+fn is_batch_record(item: &serde_json::Value) -> bool {
+    item.get("addedAt").is_none()
+        && (item.get("provider").is_some() || item.get("idp").is_some())
+        && json_str(token_fields(item), &["refreshToken", "refresh_token"]).is_some()
+}
+
+impl AccountImporter for BatchLoginImporter {
+    fn get_format_id(&self) -> &str {
+        "batch-login"
+    }
+
+    fn get_display_name(&self) -> &str {
+        "kiro-batch-login"
+    }
+
+    fn detect(&self, content: &str) -> bool {
+        serde_json::from_str::<serde_json::Value>(content)
+            .map(|v| json_items(&v).iter().any(|i| is_batch_record(i)))
+            .unwrap_or(false)
+    }
+
+    fn parse(&self, content: &str) -> Result<Vec<Account>, String> {
+        let value: serde_json::Value = serde_json::from_str(content)
+            .map_err(|e| format!("解析 kiro-batch-login 输出失败: {}", e))?;
+
+        let mut accounts = Vec::new();
+        for item in json_items(&value) {
+            if item.get("success").and_then(|v| v.as_bool()) == Some(false) {
+                continue;
+            }
+            let tokens = token_fields(item);
+            let Some(refresh_token) = json_str(tokens, &["refreshToken", "refresh_token"]) else {
+                continue;
+            };
+
+            let email = json_str(item, &["email"])
+                .or_else(|| json_str(tokens, &["email"]))
+                .unwrap_or_else(|| UNKNOWN_EMAIL.to_string());
+            let provider = json_str(item, &["provider", "idp"])
+                .or_else(|| json_str(tokens, &["provider"]))
+                .unwrap_or_else(|| "Google".to_string());
+            let is_idc = json_str(tokens, &["authMethod"])
+                .map(|m| m.eq_ignore_ascii_case("idc"))
+                .unwrap_or(false)
+                || json_str(tokens, &["clientId"]).is_some();
+
+            let mut account = Account::new(email, format!("Kiro {} 账号", provider));
+            account.refresh_token = Some(refresh_token);
+            account.access_token = json_str(tokens, &["accessToken", "access_token"]);
+            account.expires_at = normalize_expires_at(tokens.get("expiresAt"));
+            account.profile_arn = json_str(tokens, &["profileArn"]);
+            account.csrf_token = json_str(tokens, &["csrfToken"]);
+            account.id_token = json_str(tokens, &["idToken"]);
+            account.sso_session_id = json_str(tokens, &["ssoSessionId"]);
+            if is_idc {
+                account.client_id = json_str(tokens, &["clientId"]);
+                account.client_secret = json_str(tokens, &["clientSecret"]);
+                account.region = json_str(tokens, &["region"]).or_else(|| Some("us-east-1".to_string()));
+                account.client_id_hash = json_str(tokens, &["clientIdHash"])
+                    .or_else(|| Some(client_id_hash_for(BUILDER_ID_START_URL)));
+            }
+            account.provider = Some(provider);
+            accounts.push(account);
+        }
+
+        Ok(accounts)
+    }
+}
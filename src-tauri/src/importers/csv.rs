@@ -0,0 +1,121 @@
+// CSV Importer - email, refresh_token, provider, client_id, client_secret[, region]
+// 第一行可以是表头（列顺序任意），没有表头时按上面的顺序解析
+
+use crate::account::{Account, UNKNOWN_EMAIL};
+use super::AccountImporter;
+use super::base::{client_id_hash_for, provider_label, BUILDER_ID_START_URL};
+
+const DEFAULT_COLUMNS: [&str; 6] = ["email", "refresh_token", "provider", "client_id", "client_secret", "region"];
+
+pub struct CsvImporter;
+
+/// 解析一行 CSV（支持双引号包裹和 "" 转义）
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                current.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => fields.push(std::mem::take(&mut current).trim().to_string()),
+            _ => current.push(c),
+        }
+    }
+    fields.push(current.trim().to_string());
+    fields
+}
+
+/// 表头别名归一化
+fn normalize_header(name: &str) -> String {
+    let key = name.trim().to_lowercase().replace(['-', ' '], "_");
+    match key.as_str() {
+        "refreshtoken" => "refresh_token".to_string(),
+        "clientid" => "client_id".to_string(),
+        "clientsecret" => "client_secret".to_string(),
+        "idp" => "provider".to_string(),
+        _ => key,
+    }
+}
+
+fn is_header(fields: &[String]) -> bool {
+    fields.iter().any(|f| normalize_header(f) == "refresh_token")
+}
+
+/// 形如 Kiro / AWS SSO 的 RefreshToken（aor 开头的长字符串）
+fn looks_like_refresh_token(field: &str) -> bool {
+    field.len() >= 32 && field.starts_with("aor") && !field.chars().any(char::is_whitespace)
+}
+
+impl AccountImporter for CsvImporter {
+    fn get_format_id(&self) -> &str {
+        "csv"
+    }
+
+    fn get_display_name(&self) -> &str {
+        "CSV"
+    }
+
+    fn detect(&self, content: &str) -> bool {
+        let first = content.lines().map(|l| l.trim()).find(|l| !l.is_empty());
+        match first {
+            Some(line) if !line.starts_with('[') && !line.starts_with('{') => {
+                let fields = split_csv_line(line);
+                is_header(&fields) || (fields.len() >= 2 && fields.iter().any(|f| looks_like_refresh_token(f)))
+            }
+            _ => false,
+        }
+    }
+
+    fn parse(&self, content: &str) -> Result<Vec<Account>, String> {
+        let mut lines = content.lines().map(|l| l.trim()).filter(|l| !l.is_empty() && !l.starts_with('#'));
+        let first = match lines.next() {
+            Some(line) => split_csv_line(line),
+            None => return Ok(Vec::new()),
+        };
+
+        let (columns, first_row): (Vec<String>, Option<Vec<String>>) = if is_header(&first) {
+            (first.iter().map(|h| normalize_header(h)).collect(), None)
+        } else {
+            (DEFAULT_COLUMNS.iter().map(|s| s.to_string()).collect(), Some(first))
+        };
+
+        let mut accounts = Vec::new();
+        for (row_idx, fields) in first_row.into_iter().chain(lines.map(split_csv_line)).enumerate() {
+            let get = |name: &str| -> Option<String> {
+                columns.iter()
+                    .position(|c| c == name)
+                    .and_then(|i| fields.get(i))
+                    .filter(|v| !v.is_empty())
+                    .cloned()
+            };
+
+            let refresh_token = get("refresh_token")
+                .ok_or_else(|| format!("第 {} 行缺少 refresh_token", row_idx + 1))?;
+            let email = get("email").unwrap_or_else(|| UNKNOWN_EMAIL.to_string());
+            let client_id = get("client_id");
+            let client_secret = get("client_secret");
+            let is_idc = client_id.is_some() && client_secret.is_some();
+            // Social 账号无法从凭据区分 Google / Github，未指定时留空
+            let provider = get("provider").or_else(|| is_idc.then(|| "BuilderId".to_string()));
+
+            let mut account = Account::new(email, provider_label(provider.as_deref()));
+            account.refresh_token = Some(refresh_token);
+            if is_idc {
+                account.region = Some(get("region").unwrap_or_else(|| "us-east-1".to_string()));
+                account.client_id_hash = Some(client_id_hash_for(BUILDER_ID_START_URL));
+            }
+            account.client_id = client_id;
+            account.client_secret = client_secret;
+            account.provider = provider;
+            accounts.push(account);
+        }
+
+        Ok(accounts)
+    }
+}
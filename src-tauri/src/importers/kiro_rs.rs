@@ -0,0 +1,83 @@
+// Kiro.rs Importer - kiro-rs 的 credentials.json
+// 与 kiro2api_cmd::account_to_credential 互为逆操作
+
+use crate::account::{Account, UNKNOWN_EMAIL};
+use crate::account_health::AccountHealth;
+use crate::usage::{SubscriptionSummary, UsageSummary};
+use super::AccountImporter;
+use super::base::{
+    client_id_hash_for, json_items, json_str, normalize_expires_at, provider_label,
+    BUILDER_ID_START_URL,
+};
+
+pub struct KiroRsImporter;
+
+fn is_credential(item: &serde_json::Value) -> bool {
+    item.get("refreshToken").is_some()
+        && item.get("authMethod").is_some()
+        && item.get("provider").is_none()
+        && item.get("addedAt").is_none()
+}
+
+impl AccountImporter for KiroRsImporter {
+    fn get_format_id(&self) -> &str {
+        "kiro-rs"
+    }
+
+    fn get_display_name(&self) -> &str {
+        "Kiro.rs credentials.json"
+    }
+
+    fn detect(&self, content: &str) -> bool {
+        serde_json::from_str::<serde_json::Value>(content)
+            .map(|v| {
+                let items = json_items(&v);
+                !items.is_empty() && items.iter().all(|i| is_credential(i))
+            })
+            .unwrap_or(false)
+    }
+
+    fn parse(&self, content: &str) -> Result<Vec<Account>, String> {
+        let value: serde_json::Value = serde_json::from_str(content)
+            .map_err(|e| format!("解析 credentials.json 失败: {}", e))?;
+
+        let mut accounts = Vec::new();
+        for item in json_items(&value) {
+            let Some(refresh_token) = json_str(item, &["refreshToken"]) else {
+                continue;
+            };
+            let email = json_str(item, &["email"]).unwrap_or_else(|| UNKNOWN_EMAIL.to_string());
+            let is_idc = json_str(item, &["authMethod"])
+                .map(|m| m.eq_ignore_ascii_case("idc"))
+                .unwrap_or(false);
+            // Social 账号无法从凭据区分 Google / Github，留空
+            let provider = is_idc.then(|| "BuilderId".to_string());
+
+            let mut account = Account::new(email, provider_label(provider.as_deref()));
+            account.refresh_token = Some(refresh_token);
+            account.access_token = json_str(item, &["accessToken"]);
+            account.profile_arn = json_str(item, &["profileArn"]);
+            account.expires_at = normalize_expires_at(item.get("expiresAt"));
+            account.client_id = json_str(item, &["clientId"]);
+            account.client_secret = json_str(item, &["clientSecret"]);
+            if is_idc {
+                account.region = json_str(item, &["region"]);
+                account.client_id_hash = Some(client_id_hash_for(BUILDER_ID_START_URL));
+            }
+            if let Some(title) = json_str(item, &["subscriptionTitle"]) {
+                account.usage = Some(UsageSummary {
+                    subscription: Some(SubscriptionSummary { title: Some(title), ..Default::default() }),
+                    ..Default::default()
+                });
+            }
+            if item.get("disabled").and_then(|v| v.as_bool()).unwrap_or(false) {
+                account.health = AccountHealth::Suspended { reason: Some("disabled".to_string()) };
+                account.status = account.health.status_label().to_string();
+            }
+            account.provider = provider;
+            accounts.push(account);
+        }
+
+        Ok(accounts)
+    }
+}
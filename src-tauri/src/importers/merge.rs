@@ -0,0 +1,187 @@
+// 导入合并 - 按账号身份（而不是随机 id）匹配已有账号，生成预览并写入账号存储
+
+use crate::account::{Account, AccountStore, UNKNOWN_EMAIL};
+use crate::providers::is_idc_provider;
+use serde::Deserialize;
+use super::{ImportAction, ImportPreview, ImportPreviewItem};
+
+/// 导入时命中已有账号的处理策略
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MergeStrategy {
+    /// 保留已有账号，跳过导入记录
+    KeepExisting,
+    /// 按 expires_at 取较新的凭据
+    #[default]
+    Newer,
+    /// 字段级合并：凭据取较新的一方，其余空字段用另一方补齐
+    Merge,
+}
+
+fn non_empty(value: &Option<String>) -> Option<&str> {
+    value.as_deref().filter(|s| !s.is_empty())
+}
+
+/// 判断两条记录是否属于同一身份，返回命中的身份键
+fn identity_key(existing: &Account, candidate: &Account) -> Option<&'static str> {
+    if non_empty(&candidate.refresh_token).is_some() && existing.refresh_token == candidate.refresh_token {
+        return Some("refreshToken");
+    }
+    if non_empty(&candidate.user_id).is_some() && existing.user_id == candidate.user_id {
+        return Some("userId");
+    }
+    if candidate.email != UNKNOWN_EMAIL
+        && existing.email.eq_ignore_ascii_case(&candidate.email)
+        && providers_match(existing, candidate)
+    {
+        return Some("emailProvider");
+    }
+    // 同一 start URL 的账号共用 clientId，只有双方没有可区分的邮箱 / user_id 时才按 clientId 匹配
+    if non_empty(&candidate.client_id).is_some()
+        && existing.client_id == candidate.client_id
+        && !emails_differ(existing, candidate)
+        && !has_identity_conflict(existing, candidate)
+    {
+        return Some("clientId");
+    }
+    None
+}
+
+/// provider 是否一致：任一方缺失视为匹配，BuilderId / Enterprise 都是 IdC 标签，视为同一类
+fn providers_match(existing: &Account, candidate: &Account) -> bool {
+    match (non_empty(&existing.provider), non_empty(&candidate.provider)) {
+        (Some(a), Some(b)) => a.eq_ignore_ascii_case(b) || (is_idc_provider(a) && is_idc_provider(b)),
+        _ => true,
+    }
+}
+
+/// 两条记录的邮箱都已知但不一致
+fn emails_differ(existing: &Account, candidate: &Account) -> bool {
+    existing.email != UNKNOWN_EMAIL
+        && candidate.email != UNKNOWN_EMAIL
+        && !existing.email.eq_ignore_ascii_case(&candidate.email)
+}
+
+/// 两条记录都有 user_id 但不一致，说明身份键互相矛盾
+fn has_identity_conflict(existing: &Account, candidate: &Account) -> bool {
+    match (non_empty(&existing.user_id), non_empty(&candidate.user_id)) {
+        (Some(a), Some(b)) => a != b,
+        _ => false,
+    }
+}
+
+/// 导入记录的凭据是否比已有账号更新
+fn is_newer(existing: &Account, candidate: &Account) -> bool {
+    match (existing.expires_at_time(), candidate.expires_at_time()) {
+        (Some(old), Some(new)) => new > old,
+        (None, Some(_)) => true,
+        _ => false,
+    }
+}
+
+/// 按策略把导入记录合并到已有账号，返回是否有改动
+fn merge_account(existing: &mut Account, candidate: &Account, strategy: MergeStrategy) -> bool {
+    let before = serde_json::to_value(&*existing).ok();
+    match strategy {
+        MergeStrategy::KeepExisting => {}
+        MergeStrategy::Newer => {
+            if is_newer(existing, candidate) {
+                existing.merge_credentials_from(candidate);
+            }
+        }
+        MergeStrategy::Merge => {
+            if is_newer(existing, candidate) {
+                existing.merge_credentials_from(candidate);
+            }
+            existing.fill_missing_from(candidate);
+        }
+    }
+    before != serde_json::to_value(&*existing).ok()
+}
+
+fn item(candidate: &Account, action: ImportAction, existing: Option<&Account>, matched_by: Option<&str>, reason: Option<&str>) -> ImportPreviewItem {
+    ImportPreviewItem {
+        action,
+        email: candidate.email.clone(),
+        provider: candidate.provider.clone(),
+        existing_id: existing.map(|a| a.id.clone()),
+        matched_by: matched_by.map(|k| k.to_string()),
+        reason: reason.map(|r| r.to_string()),
+    }
+}
+
+/// 把候选记录逐条合并进账号列表（同一批次内的重复记录也会互相合并）
+fn merge_into(accounts: &mut Vec<Account>, candidates: Vec<Account>, strategy: MergeStrategy) -> Vec<ImportPreviewItem> {
+    let mut items = Vec::with_capacity(candidates.len());
+
+    for mut candidate in candidates {
+        if non_empty(&candidate.refresh_token).is_none() {
+            items.push(item(&candidate, ImportAction::Skip, None, None, Some("缺少 refresh_token")));
+            continue;
+        }
+
+        let matches: Vec<(usize, &'static str)> = accounts.iter()
+            .enumerate()
+            .filter_map(|(idx, a)| identity_key(a, &candidate).map(|key| (idx, key)))
+            .collect();
+
+        match matches.as_slice() {
+            [] => {
+                // 避免与已有账号 id 冲突（例如重复导入同一份导出文件时已被按身份合并掉的情况）
+                if accounts.iter().any(|a| a.id == candidate.id) {
+                    candidate.id = uuid::Uuid::new_v4().to_string();
+                }
+                items.push(item(&candidate, ImportAction::Add, None, None, None));
+                accounts.push(candidate);
+            }
+            [(idx, key)] => {
+                let existing = &mut accounts[*idx];
+                if has_identity_conflict(existing, &candidate) {
+                    items.push(item(&candidate, ImportAction::Conflict, Some(existing), Some(key), Some("userId 不一致")));
+                } else if merge_account(existing, &candidate, strategy) {
+                    items.push(item(&candidate, ImportAction::Update, Some(existing), Some(key), Some("更新凭据")));
+                } else {
+                    let reason = match strategy {
+                        MergeStrategy::KeepExisting => "账号已存在",
+                        _ => "已有凭据不比导入记录旧",
+                    };
+                    items.push(item(&candidate, ImportAction::Skip, Some(existing), Some(key), Some(reason)));
+                }
+            }
+            [(idx, key), ..] => {
+                items.push(item(&candidate, ImportAction::Conflict, Some(&accounts[*idx]), Some(key), Some("匹配到多个已有账号")));
+            }
+        }
+    }
+
+    items
+}
+
+fn summarize(format: &str, items: Vec<ImportPreviewItem>) -> ImportPreview {
+    let count = |action: ImportAction| items.iter().filter(|i| i.action == action).count();
+    ImportPreview {
+        format: format.to_string(),
+        added: count(ImportAction::Add),
+        updated: count(ImportAction::Update),
+        skipped: count(ImportAction::Skip),
+        conflicts: count(ImportAction::Conflict),
+        items,
+    }
+}
+
+/// 预览导入结果（在账号列表副本上模拟合并）
+pub fn plan_import(store: &AccountStore, format: &str, candidates: &[Account], strategy: MergeStrategy) -> ImportPreview {
+    let mut accounts = store.accounts.clone();
+    let items = merge_into(&mut accounts, candidates.to_vec(), strategy);
+    summarize(format, items)
+}
+
+/// 执行导入，最后统一保存一次
+pub fn apply_import(store: &mut AccountStore, format: &str, candidates: Vec<Account>, strategy: MergeStrategy) -> ImportPreview {
+    let items = merge_into(&mut store.accounts, candidates, strategy);
+    let report = summarize(format, items);
+    if report.added > 0 || report.updated > 0 {
+        store.save_to_file();
+    }
+    report
+}
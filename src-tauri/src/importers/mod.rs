@@ -0,0 +1,15 @@
+// Importers 模块 - 多格式账号导入
+// 与 providers 的结构一致：base 定义接口，registry 负责注册和格式识别
+
+mod base;
+mod native;
+mod csv;
+mod kiro_rs;
+mod batch_login;
+mod sso_cache;
+mod registry;
+mod merge;
+
+pub use base::{AccountImporter, ImportAction, ImportFormatInfo, ImportPreview, ImportPreviewItem};
+pub use registry::*;
+pub use merge::{plan_import, apply_import, MergeStrategy};
@@ -0,0 +1,25 @@
+// Native Importer - 本应用导出的账号 JSON 数组
+
+use crate::account::Account;
+use super::AccountImporter;
+
+pub struct NativeImporter;
+
+impl AccountImporter for NativeImporter {
+    fn get_format_id(&self) -> &str {
+        "native"
+    }
+
+    fn get_display_name(&self) -> &str {
+        "Kiro Account Manager JSON"
+    }
+
+    fn detect(&self, content: &str) -> bool {
+        serde_json::from_str::<Vec<Account>>(content).is_ok()
+    }
+
+    fn parse(&self, content: &str) -> Result<Vec<Account>, String> {
+        serde_json::from_str::<Vec<Account>>(content)
+            .map_err(|e| format!("解析账号 JSON 失败: {}", e))
+    }
+}
@@ -0,0 +1,50 @@
+// Importer Registry - 注册所有导入器并按内容自动识别格式
+
+use super::AccountImporter;
+use super::ImportFormatInfo;
+use super::native::NativeImporter;
+use super::csv::CsvImporter;
+use super::kiro_rs::KiroRsImporter;
+use super::batch_login::BatchLoginImporter;
+use super::sso_cache::SsoCacheImporter;
+
+/// 获取所有导入器（顺序即自动识别时的优先级）
+pub fn get_importers() -> Vec<Box<dyn AccountImporter>> {
+    vec![
+        Box::new(NativeImporter),
+        Box::new(KiroRsImporter),
+        Box::new(BatchLoginImporter),
+        Box::new(CsvImporter),
+        Box::new(SsoCacheImporter),
+    ]
+}
+
+/// 按格式 ID 获取导入器
+pub fn get_importer(format_id: &str) -> Option<Box<dyn AccountImporter>> {
+    get_importers()
+        .into_iter()
+        .find(|i| i.get_format_id() == format_id)
+}
+
+/// 根据内容自动识别导入器
+pub fn detect_importer(content: &str) -> Option<Box<dyn AccountImporter>> {
+    let content = content.trim();
+    if content.is_empty() {
+        return None;
+    }
+    get_importers()
+        .into_iter()
+        .find(|i| i.detect(content))
+}
+
+/// 获取支持的导入格式
+pub fn get_import_format_infos() -> Vec<ImportFormatInfo> {
+    get_importers()
+        .iter()
+        .map(|i| ImportFormatInfo {
+            id: i.get_format_id().to_string(),
+            name: i.get_display_name().to_string(),
+            requires_content: i.requires_content(),
+        })
+        .collect()
+}
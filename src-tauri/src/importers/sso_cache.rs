@@ -0,0 +1,104 @@
+// SSO Cache Importer - 扫描 ~/.aws/sso/cache
+// 识别 Kiro IDE 的 kiro-auth-token.json（配合 {clientIdHash}.json）以及 AWS CLI 的 SSO 会话缓存
+
+use crate::account::{Account, UNKNOWN_EMAIL};
+use crate::kiro::{get_client_registration, get_sso_cache_dir};
+use super::AccountImporter;
+use super::base::{client_id_hash_for, json_str, normalize_expires_at, BUILDER_ID_START_URL};
+
+pub struct SsoCacheImporter;
+
+fn account_from_cache_file(file_name: &str, value: &serde_json::Value) -> Option<Account> {
+    let refresh_token = json_str(value, &["refreshToken"])?;
+
+    let client_id_hash = json_str(value, &["clientIdHash"]);
+    let (client_id, client_secret) = match (json_str(value, &["clientId"]), json_str(value, &["clientSecret"])) {
+        (Some(id), Some(secret)) => (Some(id), Some(secret)),
+        _ => client_id_hash
+            .as_deref()
+            .and_then(get_client_registration)
+            .map(|reg| (Some(reg.client_id), Some(reg.client_secret)))
+            .unwrap_or((None, None)),
+    };
+
+    let is_idc = json_str(value, &["authMethod"]).map(|m| m == "IdC").unwrap_or(false) || client_id.is_some();
+    let start_url = json_str(value, &["startUrl"]);
+    let provider = json_str(value, &["provider"]).unwrap_or_else(|| {
+        match (is_idc, start_url.as_deref()) {
+            (true, Some(url)) if url != BUILDER_ID_START_URL => "Enterprise".to_string(),
+            (true, _) => "BuilderId".to_string(),
+            (false, _) => "Google".to_string(),
+        }
+    });
+
+    let mut account = Account::new(
+        json_str(value, &["email"]).unwrap_or_else(|| UNKNOWN_EMAIL.to_string()),
+        format!("SSO Cache ({})", file_name),
+    );
+    account.refresh_token = Some(refresh_token);
+    account.access_token = json_str(value, &["accessToken"]);
+    account.expires_at = normalize_expires_at(value.get("expiresAt"));
+    account.profile_arn = json_str(value, &["profileArn"]);
+    if is_idc {
+        account.client_id = client_id;
+        account.client_secret = client_secret;
+        account.region = json_str(value, &["region"]).or_else(|| Some("us-east-1".to_string()));
+        account.client_id_hash = client_id_hash
+            .or_else(|| Some(client_id_hash_for(start_url.as_deref().unwrap_or(BUILDER_ID_START_URL))));
+        account.start_url = start_url;
+    }
+    account.provider = Some(provider);
+    Some(account)
+}
+
+impl AccountImporter for SsoCacheImporter {
+    fn get_format_id(&self) -> &str {
+        "sso-cache"
+    }
+
+    fn get_display_name(&self) -> &str {
+        "~/.aws/sso/cache"
+    }
+
+    fn requires_content(&self) -> bool {
+        false
+    }
+
+    fn detect(&self, _content: &str) -> bool {
+        // 扫描类导入器只能显式选择
+        false
+    }
+
+    fn parse(&self, _content: &str) -> Result<Vec<Account>, String> {
+        let dir = get_sso_cache_dir().ok_or("Cannot find home directory")?;
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut entries: Vec<_> = std::fs::read_dir(&dir)
+            .map_err(|e| format!("读取目录失败: {}", e))?
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.extension().map(|e| e == "json").unwrap_or(false))
+            .collect();
+        entries.sort();
+
+        let mut accounts = Vec::new();
+        for path in entries {
+            let Ok(content) = std::fs::read_to_string(&path) else {
+                continue;
+            };
+            let Ok(value) = serde_json::from_str::<serde_json::Value>(&content) else {
+                continue;
+            };
+            let file_name = path.file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            if let Some(account) = account_from_cache_file(&file_name, &value) {
+                accounts.push(account);
+            }
+        }
+
+        Ok(accounts)
+    }
+}
@@ -0,0 +1,588 @@
+// JSONC 编辑 - Kiro IDE 的 settings.json 允许注释（// 和 /* */）和尾随逗号
+// 按键原地修改顶层成员，保留注释、顺序和格式；无法解析时拒绝修改和写入
+
+use std::path::Path;
+
+use serde_json::Value;
+
+/// 把注释替换为空格（保留换行，字节偏移不变），字符串中的 // 不受影响
+fn blank_comments(text: &str) -> Result<Vec<u8>, String> {
+    let mut bytes = text.as_bytes().to_vec();
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'"' => i = scan_string(&bytes, i)?,
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    bytes[i] = b' ';
+                    i += 1;
+                }
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                let start = i;
+                let end = text[i + 2..].find("*/")
+                    .map(|p| i + 2 + p + 2)
+                    .ok_or_else(|| format!("块注释未结束（第 {} 行）", line_of(text, start)))?;
+                for b in &mut bytes[start..end] {
+                    if *b != b'\n' && *b != b'\r' {
+                        *b = b' ';
+                    }
+                }
+                i = end;
+            }
+            _ => i += 1,
+        }
+    }
+    Ok(bytes)
+}
+
+/// 去掉 } 或 ] 之前的尾随逗号（输入已去掉注释）
+fn blank_trailing_commas(bytes: &mut [u8]) -> Result<(), String> {
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'"' => i = scan_string(bytes, i)?,
+            b',' => {
+                let next = skip_ws(bytes, i + 1);
+                if matches!(bytes.get(next), Some(b'}') | Some(b']')) {
+                    bytes[i] = b' ';
+                }
+                i += 1;
+            }
+            _ => i += 1,
+        }
+    }
+    Ok(())
+}
+
+fn line_of(text: &str, pos: usize) -> usize {
+    text[..pos].matches('\n').count() + 1
+}
+
+fn skip_ws(bytes: &[u8], mut pos: usize) -> usize {
+    while pos < bytes.len() && bytes[pos].is_ascii_whitespace() {
+        pos += 1;
+    }
+    pos
+}
+
+/// 从起始引号开始扫描字符串，返回结束引号之后的位置
+fn scan_string(bytes: &[u8], start: usize) -> Result<usize, String> {
+    let mut i = start + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'"' => return Ok(i + 1),
+            _ => i += 1,
+        }
+    }
+    Err("字符串未结束".to_string())
+}
+
+/// 扫描一个值（输入已去掉注释），返回值结束的位置
+fn scan_value(bytes: &[u8], start: usize) -> Result<usize, String> {
+    match bytes.get(start) {
+        Some(b'"') => scan_string(bytes, start),
+        Some(b'{') | Some(b'[') => {
+            let mut depth = 0usize;
+            let mut i = start;
+            while i < bytes.len() {
+                match bytes[i] {
+                    b'"' => { i = scan_string(bytes, i)?; continue; }
+                    b'{' | b'[' => depth += 1,
+                    b'}' | b']' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            return Ok(i + 1);
+                        }
+                    }
+                    _ => {}
+                }
+                i += 1;
+            }
+            Err("对象或数组未结束".to_string())
+        }
+        Some(_) => {
+            let mut i = start;
+            while i < bytes.len() && !matches!(bytes[i], b',' | b'}' | b']') && !bytes[i].is_ascii_whitespace() {
+                i += 1;
+            }
+            Ok(i)
+        }
+        None => Err("缺少值".to_string()),
+    }
+}
+
+/// 宽松解析 JSONC（注释、尾随逗号、BOM），空文件视为空对象
+pub fn parse(text: &str) -> Result<Value, String> {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    if text.trim().is_empty() {
+        return Ok(Value::Object(Default::default()));
+    }
+    let mut bytes = blank_comments(text)?;
+    blank_trailing_commas(&mut bytes)?;
+    serde_json::from_slice(&bytes).map_err(|e| e.to_string())
+}
+
+/// 顶层成员在原文中的位置
+struct Member {
+    key: String,
+    /// 键的起始引号
+    start: usize,
+    value_start: usize,
+    value_end: usize,
+    comma: Option<usize>,
+}
+
+/// 顶层对象的结构
+struct Layout {
+    open: usize,
+    close: usize,
+    members: Vec<Member>,
+}
+
+fn scan_layout(bytes: &[u8]) -> Result<Layout, String> {
+    let pos = skip_ws(bytes, 0);
+    if bytes.get(pos) != Some(&b'{') {
+        return Err("根节点不是对象".to_string());
+    }
+    scan_object(bytes, pos)
+}
+
+/// 扫描从 open（左花括号）开始的对象
+fn scan_object(bytes: &[u8], open: usize) -> Result<Layout, String> {
+    let mut members = Vec::new();
+    let mut pos = open + 1;
+    loop {
+        pos = skip_ws(bytes, pos);
+        match bytes.get(pos) {
+            Some(b'}') => return Ok(Layout { open, close: pos, members }),
+            Some(b'"') => {
+                let start = pos;
+                let key_end = scan_string(bytes, start)?;
+                let key: String = serde_json::from_slice(&bytes[start..key_end])
+                    .map_err(|e| format!("无效的键: {}", e))?;
+                pos = skip_ws(bytes, key_end);
+                if bytes.get(pos) != Some(&b':') {
+                    return Err(format!("键 {} 后缺少冒号", key));
+                }
+                let value_start = skip_ws(bytes, pos + 1);
+                let value_end = scan_value(bytes, value_start)?;
+                pos = skip_ws(bytes, value_end);
+                let comma = (bytes.get(pos) == Some(&b',')).then_some(pos);
+                if comma.is_some() {
+                    pos += 1;
+                } else if bytes.get(pos) != Some(&b'}') {
+                    return Err(format!("键 {} 后缺少逗号", key));
+                }
+                members.push(Member { key, start, value_start, value_end, comma });
+            }
+            _ => return Err("意外的字符".to_string()),
+        }
+    }
+}
+
+/// 可原地编辑的 JSONC 文档
+pub struct JsoncDocument {
+    text: String,
+    bom: bool,
+}
+
+impl JsoncDocument {
+    /// 解析文档，无法解析或根节点不是对象时返回错误
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let bom = text.starts_with('\u{feff}');
+        let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+        let text = if text.trim().is_empty() { "{}".to_string() } else { text.to_string() };
+        let doc = JsoncDocument { text, bom };
+        if !parse(&doc.text)?.is_object() {
+            return Err("根节点不是对象".to_string());
+        }
+        doc.layout()?;
+        Ok(doc)
+    }
+
+    /// 读取文件，文件不存在时为空文档
+    pub fn load(path: &Path) -> Result<Self, String> {
+        if !path.exists() {
+            return Self::parse("");
+        }
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("读取 {:?} 失败: {}", path, e))?;
+        Self::parse(&content)
+    }
+
+    /// 原子写入（先写临时文件再覆盖）
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| format!("创建目录失败: {}", e))?;
+        }
+        let temp = path.with_extension("json.tmp");
+        std::fs::write(&temp, self.to_text()).map_err(|e| format!("写入 {:?} 失败: {}", path, e))?;
+        std::fs::rename(&temp, path).map_err(|e| format!("写入 {:?} 失败: {}", path, e))
+    }
+
+    pub fn to_text(&self) -> String {
+        if self.bom { format!("\u{feff}{}", self.text) } else { self.text.clone() }
+    }
+
+    /// 解析后的值（重复的键以最后一个为准）
+    pub fn value(&self) -> Value {
+        parse(&self.text).unwrap_or(Value::Object(Default::default()))
+    }
+
+    pub fn get(&self, key: &str) -> Option<Value> {
+        self.value().get(key).cloned()
+    }
+
+    /// 设置顶层键：已存在时只替换值，不存在时追加到末尾
+    pub fn set(&mut self, key: &str, value: &Value) -> Result<(), String> {
+        self.set_path(&[key], value)
+    }
+
+    /// 设置嵌套对象中的键，例如 ["mcpServers", "name", "disabled"]；
+    /// 前面的键必须是已存在的对象，只改动目标成员，其余内容和注释保持不变
+    pub fn set_path(&mut self, path: &[&str], value: &Value) -> Result<(), String> {
+        let (key, parents) = path.split_last().ok_or("键路径为空")?;
+        let bytes = blank_comments(&self.text)?;
+        let mut layout = scan_layout(&bytes)?;
+        let newline = self.newline();
+        let unit = self.indent_unit(&layout);
+        // 当前对象成员的缩进和右花括号所在行的缩进
+        let mut indent = unit.clone();
+        let mut close_indent = String::new();
+        for parent in parents {
+            let member = layout.members.iter().rev().find(|m| m.key == *parent)
+                .ok_or_else(|| format!("键 {} 不存在", parent))?;
+            if bytes.get(member.value_start) != Some(&b'{') {
+                return Err(format!("键 {} 不是对象", parent));
+            }
+            close_indent = self.line_indent(member.start);
+            indent = format!("{}{}", close_indent, unit);
+            layout = scan_object(&bytes, member.value_start)?;
+        }
+        let key = *key;
+        let mut text = self.text.clone();
+
+        if let Some(member) = layout.members.iter().rev().find(|m| m.key == key) {
+            let indent = self.line_indent(member.start);
+            text.replace_range(member.value_start..member.value_end, &render(value, &unit, &indent, newline));
+        } else {
+            let entry = format!(
+                "{}: {}",
+                serde_json::to_string(key).map_err(|e| e.to_string())?,
+                render(value, &unit, &indent, newline),
+            );
+            match layout.members.last() {
+                Some(last) => {
+                    let after = last.comma.map(|c| c + 1).unwrap_or(last.value_end);
+                    // 原来有尾随逗号时新成员也保留尾随逗号
+                    let trailing = if last.comma.is_some() { "," } else { "" };
+                    match self.line_end(after) {
+                        // 插入到最后一个成员所在行的末尾（越过行尾注释）
+                        Some(eol) => text.insert_str(eol, &format!("{}{}{}{}", newline, indent, entry, trailing)),
+                        None => text.insert_str(after, &format!(" {}{}", entry, trailing)),
+                    }
+                    if last.comma.is_none() {
+                        text.insert(last.value_end, ',');
+                    }
+                }
+                None => {
+                    let inner = &self.text[layout.open + 1..layout.close];
+                    let insert = if inner.contains('\n') {
+                        format!("{}{}{}", newline, indent, entry)
+                    } else {
+                        format!("{}{}{}{}{}", newline, indent, entry, newline, close_indent)
+                    };
+                    text.insert_str(layout.open + 1, &insert);
+                }
+            }
+        }
+        self.replace_text(text)
+    }
+
+    /// 删除顶层键（包括重复的键），返回是否存在
+    pub fn remove(&mut self, key: &str) -> Result<bool, String> {
+        let mut removed = false;
+        loop {
+            let layout = self.layout()?;
+            let Some(idx) = layout.members.iter().position(|m| m.key == key) else {
+                return Ok(removed);
+            };
+            let member = &layout.members[idx];
+            let line_start = self.text[..member.start].rfind('\n').map(|i| i + 1).unwrap_or(0);
+            // 删除的是最后一个成员且没有尾随逗号时，需要去掉前一个成员的逗号
+            let prev_comma = if member.comma.is_none() && idx > 0 {
+                layout.members[idx - 1].comma
+            } else {
+                None
+            };
+
+            let after = member.comma.map(|c| c + 1).unwrap_or(member.value_end);
+            let mut text = self.text.clone();
+            match (self.starts_line(member.start), self.line_end(after)) {
+                // 独占一行时连同行尾注释和换行一起删除
+                (true, Some(eol)) => {
+                    text.replace_range(line_start..self.skip_newline(eol), "");
+                    if let Some(prev_comma) = prev_comma {
+                        text.remove(prev_comma);
+                    }
+                }
+                _ => match prev_comma {
+                    Some(prev_comma) => text.replace_range(prev_comma..member.value_end, ""),
+                    None => {
+                        let end = skip_ws(self.text.as_bytes(), after).min(layout.close);
+                        text.replace_range(member.start..end, "");
+                    }
+                },
+            }
+            self.replace_text(text)?;
+            removed = true;
+        }
+    }
+
+    /// 修改后重新校验，保证不会写出无法解析的文件
+    fn replace_text(&mut self, text: String) -> Result<(), String> {
+        parse(&text).map_err(|e| format!("修改后的内容无法解析: {}", e))?;
+        self.text = text;
+        Ok(())
+    }
+
+    fn layout(&self) -> Result<Layout, String> {
+        scan_layout(&blank_comments(&self.text)?)
+    }
+
+    fn newline(&self) -> &'static str {
+        if self.text.contains("\r\n") { "\r\n" } else { "\n" }
+    }
+
+    /// pos 所在行的缩进
+    fn line_indent(&self, pos: usize) -> String {
+        let line_start = self.text[..pos].rfind('\n').map(|i| i + 1).unwrap_or(0);
+        self.text[line_start..pos].chars().take_while(|c| *c == ' ' || *c == '\t').collect()
+    }
+
+    /// pos 之前只有缩进（独占一行）
+    fn starts_line(&self, pos: usize) -> bool {
+        let line_start = self.text[..pos].rfind('\n').map(|i| i + 1).unwrap_or(0);
+        self.text[line_start..pos].trim().is_empty()
+    }
+
+    /// 缩进单位：沿用第一个独占一行的成员的缩进，默认 4 个空格（与 IDE 一致）
+    fn indent_unit(&self, layout: &Layout) -> String {
+        layout.members.iter()
+            .filter(|m| self.starts_line(m.start))
+            .map(|m| self.line_indent(m.start))
+            .find(|indent| !indent.is_empty())
+            .unwrap_or_else(|| "    ".to_string())
+    }
+
+    /// 从 pos 开始只有空白和注释直到行尾时，返回换行符的位置
+    fn line_end(&self, mut pos: usize) -> Option<usize> {
+        let bytes = self.text.as_bytes();
+        loop {
+            match bytes.get(pos) {
+                Some(b' ') | Some(b'\t') => pos += 1,
+                Some(b'\r') if bytes.get(pos + 1) == Some(&b'\n') => return Some(pos),
+                Some(b'\n') => return Some(pos),
+                Some(b'/') if bytes.get(pos + 1) == Some(&b'/') => {
+                    let eol = self.text[pos..].find('\n').map(|i| pos + i)?;
+                    return Some(if eol > 0 && bytes[eol - 1] == b'\r' { eol - 1 } else { eol });
+                }
+                Some(b'/') if bytes.get(pos + 1) == Some(&b'*') => {
+                    let end = self.text[pos + 2..].find("*/").map(|i| pos + 2 + i + 2)?;
+                    // 跨行的块注释不算行尾
+                    if self.text[pos..end].contains('\n') {
+                        return None;
+                    }
+                    pos = end;
+                }
+                _ => return None,
+            }
+        }
+    }
+
+    fn skip_newline(&self, eol: usize) -> usize {
+        if self.text[eol..].starts_with("\r\n") { eol + 2 } else { eol + 1 }
+    }
+}
+
+/// 序列化值，对象和数组按缩进展开
+fn render(value: &Value, unit: &str, indent: &str, newline: &str) -> String {
+    use serde::Serialize;
+
+    let is_empty = match value {
+        Value::Object(map) => map.is_empty(),
+        Value::Array(arr) => arr.is_empty(),
+        _ => true,
+    };
+    if is_empty {
+        return value.to_string();
+    }
+    let mut buf = Vec::new();
+    let formatter = serde_json::ser::PrettyFormatter::with_indent(unit.as_bytes());
+    let mut ser = serde_json::Serializer::with_formatter(&mut buf, formatter);
+    if value.serialize(&mut ser).is_err() {
+        return value.to_string();
+    }
+    String::from_utf8_lossy(&buf).replace('\n', &format!("{}{}", newline, indent))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// IDE 默认生成的 settings.json：行注释、块注释、行尾注释、尾随逗号
+    const IDE_SAMPLE: &str = r#"// Place your settings in this file to overwrite the default settings
+{
+    // 编辑器
+    "editor.fontSize": 14,
+    "workbench.colorTheme": "Kiro Dark", // 主题
+    /* 代理设置
+       公司网络需要 */
+    "http.proxy": "http://127.0.0.1:7890",
+    "files.exclude": {
+        "**/.git": true,
+        "**/node_modules": true,
+    },
+    "kiroAgent.modelSelection": "claude-sonnet-4",
+}
+"#;
+
+    /// 手写的紧凑格式，没有尾随逗号，两个空格缩进
+    const COMPACT_SAMPLE: &str = "{\n  \"telemetry.telemetryLevel\": \"off\",\n  \"window.zoomLevel\": 1 // 放大\n}\n";
+
+    #[test]
+    fn parses_comments_and_trailing_commas() {
+        let value = parse(IDE_SAMPLE).unwrap();
+        assert_eq!(value["http.proxy"], "http://127.0.0.1:7890");
+        assert_eq!(value["files.exclude"]["**/node_modules"], true);
+        assert_eq!(value["kiroAgent.modelSelection"], "claude-sonnet-4");
+    }
+
+    #[test]
+    fn replaces_value_in_place() {
+        let mut doc = JsoncDocument::parse(IDE_SAMPLE).unwrap();
+        doc.set("kiroAgent.modelSelection", &json!("claude-opus-4")).unwrap();
+        assert_eq!(doc.to_text(), IDE_SAMPLE.replace("\"claude-sonnet-4\"", "\"claude-opus-4\""));
+    }
+
+    #[test]
+    fn appends_new_key_keeping_trailing_comma_style() {
+        let mut doc = JsoncDocument::parse(IDE_SAMPLE).unwrap();
+        doc.set("http.proxyStrictSSL", &json!(false)).unwrap();
+        assert_eq!(
+            doc.to_text(),
+            IDE_SAMPLE.replace(
+                "\"claude-sonnet-4\",\n}",
+                "\"claude-sonnet-4\",\n    \"http.proxyStrictSSL\": false,\n}",
+            ),
+        );
+    }
+
+    #[test]
+    fn appends_after_line_comment_and_adds_comma() {
+        let mut doc = JsoncDocument::parse(COMPACT_SAMPLE).unwrap();
+        doc.set("http.proxy", &json!("http://proxy:8080")).unwrap();
+        assert_eq!(
+            doc.to_text(),
+            "{\n  \"telemetry.telemetryLevel\": \"off\",\n  \"window.zoomLevel\": 1, // 放大\n  \"http.proxy\": \"http://proxy:8080\"\n}\n",
+        );
+    }
+
+    #[test]
+    fn removes_key_with_its_line() {
+        let mut doc = JsoncDocument::parse(IDE_SAMPLE).unwrap();
+        assert!(doc.remove("workbench.colorTheme").unwrap());
+        assert_eq!(doc.to_text(), IDE_SAMPLE.replace("    \"workbench.colorTheme\": \"Kiro Dark\", // 主题\n", ""));
+        assert!(!doc.remove("workbench.colorTheme").unwrap());
+    }
+
+    #[test]
+    fn removing_last_key_drops_previous_comma() {
+        let mut doc = JsoncDocument::parse(COMPACT_SAMPLE).unwrap();
+        doc.remove("window.zoomLevel").unwrap();
+        assert_eq!(doc.to_text(), "{\n  \"telemetry.telemetryLevel\": \"off\"\n}\n");
+        assert_eq!(doc.value(), json!({ "telemetry.telemetryLevel": "off" }));
+    }
+
+    #[test]
+    fn nested_values_follow_file_indentation() {
+        let mut doc = JsoncDocument::parse(IDE_SAMPLE).unwrap();
+        doc.set("files.exclude", &json!({ "**/dist": true })).unwrap();
+        assert!(doc.to_text().contains("    \"files.exclude\": {\n        \"**/dist\": true\n    },\n"));
+        assert_eq!(doc.get("files.exclude"), Some(json!({ "**/dist": true })));
+    }
+
+    #[test]
+    fn sets_nested_member_keeping_comments() {
+        let sample = "{\n    \"mcpServers\": {\n        // 本地服务\n        \"fetch\": { \"command\": \"uvx\" },\n        \"git\": {\n            \"command\": \"git-mcp\", // 常用\n            \"disabled\": false\n        }\n    }\n}\n";
+        let mut doc = JsoncDocument::parse(sample).unwrap();
+        doc.set_path(&["mcpServers", "git", "disabled"], &json!(true)).unwrap();
+        assert_eq!(doc.to_text(), sample.replace("\"disabled\": false", "\"disabled\": true"));
+
+        doc.set_path(&["mcpServers", "fetch", "disabled"], &json!(true)).unwrap();
+        assert!(doc.to_text().contains("// 本地服务"));
+        assert!(doc.to_text().contains("// 常用"));
+        assert_eq!(doc.get("mcpServers").unwrap()["fetch"], json!({ "command": "uvx", "disabled": true }));
+        assert!(doc.set_path(&["mcpServers", "missing", "disabled"], &json!(true)).is_err());
+    }
+
+    #[test]
+    fn empty_file_and_empty_object() {
+        let mut doc = JsoncDocument::parse("").unwrap();
+        doc.set("http.proxy", &json!("http://a")).unwrap();
+        assert_eq!(doc.to_text(), "{\n    \"http.proxy\": \"http://a\"\n}");
+
+        let mut doc = JsoncDocument::parse("{\n    // 暂无设置\n}\n").unwrap();
+        doc.set("a", &json!(1)).unwrap();
+        assert_eq!(doc.to_text(), "{\n    \"a\": 1\n    // 暂无设置\n}\n");
+    }
+
+    #[test]
+    fn single_line_object() {
+        let mut doc = JsoncDocument::parse(r#"{"a": 1, "b": 2}"#).unwrap();
+        doc.set("c", &json!(3)).unwrap();
+        assert_eq!(doc.to_text(), r#"{"a": 1, "b": 2, "c": 3}"#);
+        doc.remove("a").unwrap();
+        assert_eq!(doc.to_text(), r#"{"b": 2, "c": 3}"#);
+        doc.remove("c").unwrap();
+        assert_eq!(doc.to_text(), r#"{"b": 2}"#);
+    }
+
+    #[test]
+    fn preserves_crlf_and_bom() {
+        let sample = "\u{feff}{\r\n\t\"a\": 1 // x\r\n}\r\n";
+        let mut doc = JsoncDocument::parse(sample).unwrap();
+        doc.set("b", &json!([1, 2])).unwrap();
+        assert_eq!(doc.to_text(), "\u{feff}{\r\n\t\"a\": 1, // x\r\n\t\"b\": [\r\n\t\t1,\r\n\t\t2\r\n\t]\r\n}\r\n");
+    }
+
+    #[test]
+    fn comment_markers_inside_strings_are_kept() {
+        let sample = "{\n    \"a\": \"http://x/*y*/\", // c\n    \"b\": \"\\\"//\"\n}";
+        let mut doc = JsoncDocument::parse(sample).unwrap();
+        assert_eq!(doc.get("a"), Some(json!("http://x/*y*/")));
+        doc.set("b", &json!("ok")).unwrap();
+        assert_eq!(doc.to_text(), "{\n    \"a\": \"http://x/*y*/\", // c\n    \"b\": \"ok\"\n}");
+    }
+
+    #[test]
+    fn duplicate_keys_edit_last_and_remove_all() {
+        let mut doc = JsoncDocument::parse("{\n    \"a\": 1,\n    \"a\": 2\n}").unwrap();
+        assert_eq!(doc.get("a"), Some(json!(2)));
+        doc.set("a", &json!(3)).unwrap();
+        assert_eq!(doc.to_text(), "{\n    \"a\": 1,\n    \"a\": 3\n}");
+        doc.remove("a").unwrap();
+        assert_eq!(doc.to_text(), "{\n}");
+    }
+
+    #[test]
+    fn rejects_unparseable_files() {
+        assert!(JsoncDocument::parse("{\n    \"a\": 1\n    \"b\": 2\n}").is_err());
+        assert!(JsoncDocument::parse("{\n    /* 未结束\n    \"a\": 1\n}").is_err());
+        assert!(JsoncDocument::parse("[1, 2]").is_err());
+        assert!(JsoncDocument::parse("{\"a\": }").is_err());
+    }
+}
@@ -3,6 +3,8 @@
 use serde::{Deserialize, Serialize};
 use rusqlite::{Connection, OpenFlags};
 
+use crate::kiro_paths::kiro_global_storage_dir;
+
 // ===== Kiro IDE 本地 Token =====
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -18,6 +20,8 @@ pub struct KiroLocalToken {
     // IdC 专用
     pub client_id_hash: Option<String>,
     pub region: Option<String>,
+    // 企业 IdC 的组织 start URL
+    pub start_url: Option<String>,
 }
 
 /// IdC 客户端注册信息 (从 {clientIdHash}.json 读取)
@@ -29,16 +33,20 @@ pub struct ClientRegistration {
     pub expires_at: Option<String>,
 }
 
-#[tauri::command]
-pub fn get_kiro_local_token() -> Option<KiroLocalToken> {
+/// 获取 AWS SSO 缓存目录 (~/.aws/sso/cache)
+pub fn get_sso_cache_dir() -> Option<std::path::PathBuf> {
     let home = std::env::var("USERPROFILE")
         .or_else(|_| std::env::var("HOME"))
         .ok()?;
-    let path = std::path::Path::new(&home)
+    Some(std::path::Path::new(&home)
         .join(".aws")
         .join("sso")
-        .join("cache")
-        .join("kiro-auth-token.json");
+        .join("cache"))
+}
+
+#[tauri::command]
+pub fn get_kiro_local_token() -> Option<KiroLocalToken> {
+    let path = get_sso_cache_dir()?.join("kiro-auth-token.json");
     
     let content = std::fs::read_to_string(&path).ok()?;
     serde_json::from_str(&content).ok()
@@ -46,14 +54,7 @@ pub fn get_kiro_local_token() -> Option<KiroLocalToken> {
 
 /// 读取 IdC 客户端注册信息
 pub fn get_client_registration(client_id_hash: &str) -> Option<ClientRegistration> {
-    let home = std::env::var("USERPROFILE")
-        .or_else(|_| std::env::var("HOME"))
-        .ok()?;
-    let path = std::path::Path::new(&home)
-        .join(".aws")
-        .join("sso")
-        .join("cache")
-        .join(format!("{}.json", client_id_hash));
+    let path = get_sso_cache_dir()?.join(format!("{}.json", client_id_hash));
     
     let content = std::fs::read_to_string(&path).ok()?;
     serde_json::from_str(&content).ok()
@@ -70,35 +71,11 @@ pub struct KiroTelemetryInfo {
     pub service_machine_id: Option<String>,
 }
 
-/// 获取 Kiro 数据目录
-fn get_kiro_data_dir() -> Option<std::path::PathBuf> {
-    #[cfg(target_os = "windows")]
-    {
-        std::env::var("APPDATA").ok().map(|p| std::path::PathBuf::from(p).join("Kiro"))
-    }
-    #[cfg(target_os = "macos")]
-    {
-        std::env::var("HOME").ok().map(|p| {
-            std::path::PathBuf::from(p)
-                .join("Library")
-                .join("Application Support")
-                .join("Kiro")
-        })
-    }
-    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
-    {
-        None
-    }
-}
-
 fn get_kiro_telemetry_info_inner() -> Option<KiroTelemetryInfo> {
-    let kiro_dir = get_kiro_data_dir()?;
+    let storage_dir = kiro_global_storage_dir()?;
     
     // 从 storage.json 读取
-    let storage_path = kiro_dir
-        .join("User")
-        .join("globalStorage")
-        .join("storage.json");
+    let storage_path = storage_dir.join("storage.json");
     
     let content = std::fs::read_to_string(&storage_path).ok()?;
     let json: serde_json::Value = serde_json::from_str(&content).ok()?;
@@ -111,10 +88,7 @@ fn get_kiro_telemetry_info_inner() -> Option<KiroTelemetryInfo> {
     };
     
     // 从 state.vscdb 读取 serviceMachineId
-    let db_path = kiro_dir
-        .join("User")
-        .join("globalStorage")
-        .join("state.vscdb");
+    let db_path = storage_dir.join("state.vscdb");
     
     if db_path.exists() {
         // 只读模式打开，避免被 Kiro IDE 占用时出错
@@ -157,7 +131,9 @@ pub fn get_machine_id() -> String {
 
 // ===== 切换账号 =====
 
-use crate::process::{check_kiro_running, kill_kiro, launch_kiro};
+use crate::process::{check_kiro_running, kill_kiro, launch_kiro, ShutdownStage};
+use crate::account_profile;
+use crate::switch_history;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -166,6 +142,16 @@ pub struct SwitchAccountResult {
     pub message: String,
     pub kiro_was_running: bool,
     pub kiro_restarted: bool,
+    /// 切换前的快照，可用 revert_last_switch 撤销
+    pub snapshot_id: Option<String>,
+    /// 新 Token 是否通过了用量接口验证（网络错误或 AccessToken 过期时为 false，但不回滚）
+    pub verified: bool,
+    /// 关闭 IDE 的方式（未关闭时为空）
+    pub shutdown_stage: Option<ShutdownStage>,
+    /// 是否应用了账号绑定的 IDE 配置
+    pub profile_applied: bool,
+    /// IDE 配置应用失败的原因（已尝试恢复原状，回滚失败时会附在错误中；不影响切换结果）
+    pub profile_error: Option<String>,
 }
 
 /// 切换账号参数
@@ -189,18 +175,33 @@ pub struct SwitchAccountParams {
     pub client_secret: Option<String>,
     #[serde(default)]
     pub region: Option<String>,
+    /// 企业 IdC 的组织 start URL
+    #[serde(default)]
+    pub start_url: Option<String>,
     // 选项
     #[serde(default)]
     pub reset_machine_id: Option<bool>,
     #[serde(default)]
     pub auto_restart: Option<bool>,
+    /// 写入后用用量接口验证新 Token，失败时回滚（默认开启）
+    #[serde(default)]
+    pub verify: Option<bool>,
+    /// 账号 ID，有绑定的 IDE 配置时切换后自动应用
+    #[serde(default)]
+    pub account_id: Option<String>,
 }
 
 /// 切换 Kiro 账号（直接写入 Token 文件，仅重置机器ID时才关闭IDE）
+/// 写入前快照原有的认证缓存文件，写入后验证新 Token，失败时自动回滚
 #[tauri::command]
 pub async fn switch_kiro_account(params: SwitchAccountParams) -> Result<SwitchAccountResult, String> {
+    let should_verify = params.verify.unwrap_or(true);
+    let verify_provider = params.provider.clone();
+    let verify_token = params.access_token.clone();
+    let account_id = params.account_id.clone();
+
     // 使用 spawn_blocking 避免阻塞异步运行时
-    tokio::task::spawn_blocking(move || {
+    let (mut result, dir_path, needs_restart) = tokio::task::spawn_blocking(move || {
         let kiro_was_running = check_kiro_running();
         let should_reset = params.reset_machine_id.unwrap_or(false);
         let should_restart = params.auto_restart.unwrap_or(true);
@@ -213,20 +214,22 @@ pub async fn switch_kiro_account(params: SwitchAccountParams) -> Result<SwitchAc
         let client_id = params.client_id;
         let client_secret = params.client_secret;
         let region = params.region;
+        let start_url = params.start_url;
+        // 缺少 clientIdHash 时按 start URL 计算，保证与 {clientIdHash}.json 对应
+        let client_id_hash = client_id_hash.filter(|h| !h.is_empty()).or_else(|| {
+            (auth_method == "IdC").then(|| crate::providers::IdcProvider::compute_client_id_hash(
+                start_url.as_deref().unwrap_or(crate::providers::BUILDER_ID_START_URL),
+            ))
+        });
         
-        // 1. 只在需要重置机器 ID 时才关闭 IDE
-        if should_reset && kiro_was_running {
-            kill_kiro()?;
-            // 等待进程退出
-            std::thread::sleep(std::time::Duration::from_millis(300));
-        }
-        
-        // 2. 如果需要重置机器 ID
-        if should_reset {
-            let _ = reset_kiro_machine_id_inner();
-        }
+        // 1. 只在需要重置机器 ID 时才关闭 IDE（kill_kiro 会等待进程退出）
+        let shutdown_stage = if should_reset && kiro_was_running {
+            Some(kill_kiro()?)
+        } else {
+            None
+        };
         
-        // 3. 替换 Token
+        // 2. 快照将被覆盖的文件（重置机器 ID 时包括 storage.json / state.vscdb）
         let home = std::env::var("USERPROFILE")
             .or_else(|_| std::env::var("HOME"))
             .map_err(|_| "Cannot find home directory")?;
@@ -240,85 +243,203 @@ pub async fn switch_kiro_account(params: SwitchAccountParams) -> Result<SwitchAc
             .map_err(|e| format!("Failed to create directory: {}", e))?;
         
         let file_path = dir_path.join("kiro-auth-token.json");
+
+        let mut snapshot_files = vec![file_path.clone()];
+        if auth_method == "IdC" {
+            if let Some(hash) = client_id_hash.as_ref() {
+                snapshot_files.push(dir_path.join(format!("{}.json", hash)));
+            }
+        }
+        if should_reset {
+            if let Some(storage_dir) = kiro_global_storage_dir() {
+                snapshot_files.push(storage_dir.join("storage.json"));
+                snapshot_files.push(storage_dir.join("state.vscdb"));
+            }
+        }
+        let needs_restart = kiro_was_running && should_restart;
+        let snapshot = match switch_history::create_snapshot(&snapshot_files, &provider, &auth_method) {
+            Ok(snapshot) => snapshot,
+            Err(e) => {
+                if needs_restart {
+                    let _ = launch_kiro();
+                }
+                return Err(e);
+            }
+        };
+
+        // 3. 如果需要重置机器 ID
+        if should_reset {
+            let _ = reset_kiro_machine_id_inner();
+        }
         
-        let expires_at = chrono::Utc::now() + chrono::Duration::hours(1);
+        // 4. 替换 Token
+        let write_result = (|| -> Result<(), String> {
+            let expires_at = chrono::Utc::now() + chrono::Duration::hours(1);
         
-        // 根据 auth_method 构建不同的 token 数据
-        let token_data = if auth_method == "IdC" {
-            // IdC 账号: clientIdHash + region
-            let hash = client_id_hash.clone().unwrap_or_default();
-            let data = serde_json::json!({
-                "accessToken": access_token,
-                "refreshToken": refresh_token,
-                "expiresAt": expires_at.to_rfc3339(),
-                "authMethod": "IdC",
-                "provider": provider,
-                "clientIdHash": hash,
-                "region": region.clone().unwrap_or_else(|| "us-east-1".to_string())
-            });
-            data
-        } else {
-            // Social 账号: profileArn
-            let arn = profile_arn.unwrap_or_else(|| 
-                "arn:aws:codewhisperer:us-east-1:699475941385:profile/EHGA3GRVQMUK".to_string()
-            );
-            serde_json::json!({
-                "accessToken": access_token,
-                "refreshToken": refresh_token,
-                "profileArn": arn,
-                "expiresAt": expires_at.to_rfc3339(),
-                "authMethod": "social",
-                "provider": provider
-            })
-        };
+            // 根据 auth_method 构建不同的 token 数据
+            let token_data = if auth_method == "IdC" {
+                // IdC 账号: clientIdHash + region
+                let hash = client_id_hash.clone().unwrap_or_default();
+                let mut data = serde_json::json!({
+                    "accessToken": access_token,
+                    "refreshToken": refresh_token,
+                    "expiresAt": expires_at.to_rfc3339(),
+                    "authMethod": "IdC",
+                    "provider": provider,
+                    "clientIdHash": hash,
+                    "region": region.clone().unwrap_or_else(|| "us-east-1".to_string())
+                });
+                // 企业 IdC 需要 startUrl 才能在 IDE 中刷新
+                if let Some(url) = start_url.as_ref() {
+                    data["startUrl"] = serde_json::Value::String(url.clone());
+                }
+                data
+            } else {
+                // Social 账号: profileArn
+                let arn = profile_arn.unwrap_or_else(|| 
+                    "arn:aws:codewhisperer:us-east-1:699475941385:profile/EHGA3GRVQMUK".to_string()
+                );
+                serde_json::json!({
+                    "accessToken": access_token,
+                    "refreshToken": refresh_token,
+                    "profileArn": arn,
+                    "expiresAt": expires_at.to_rfc3339(),
+                    "authMethod": "social",
+                    "provider": provider
+                })
+            };
         
-        let content = serde_json::to_string_pretty(&token_data)
-            .map_err(|e| format!("Failed to serialize: {}", e))?;
+            let content = serde_json::to_string_pretty(&token_data)
+                .map_err(|e| format!("Failed to serialize: {}", e))?;
         
-        // 原子写入：先写临时文件，再覆盖
-        let temp_file_path = dir_path.join("kiro-auth-token.json.tmp");
-        std::fs::write(&temp_file_path, &content)
-            .map_err(|e| format!("Failed to write temp file: {}", e))?;
-        std::fs::rename(&temp_file_path, &file_path)
-            .map_err(|e| format!("Failed to rename file: {}", e))?;
+            // 原子写入：先写临时文件，再覆盖
+            let temp_file_path = dir_path.join("kiro-auth-token.json.tmp");
+            std::fs::write(&temp_file_path, &content)
+                .map_err(|e| format!("Failed to write temp file: {}", e))?;
+            std::fs::rename(&temp_file_path, &file_path)
+                .map_err(|e| format!("Failed to rename file: {}", e))?;
         
-        // IdC 账号还需要写入 Client Registration 文件
-        if auth_method == "IdC" {
-            if let (Some(hash), Some(cid), Some(csec)) = (client_id_hash, client_id, client_secret) {
-                let client_reg_path = dir_path.join(format!("{}.json", hash));
-                let client_reg_temp_path = dir_path.join(format!("{}.json.tmp", hash));
-                let client_expires = chrono::Utc::now() + chrono::Duration::days(90);
-                let client_reg_data = serde_json::json!({
-                    "clientId": cid,
-                    "clientSecret": csec,
-                    "expiresAt": client_expires.to_rfc3339()
-                });
-                let client_reg_content = serde_json::to_string_pretty(&client_reg_data)
-                    .map_err(|e| format!("Failed to serialize client registration: {}", e))?;
-                // 原子写入
-                std::fs::write(&client_reg_temp_path, client_reg_content)
-                    .map_err(|e| format!("Failed to write client registration temp: {}", e))?;
-                std::fs::rename(&client_reg_temp_path, &client_reg_path)
-                    .map_err(|e| format!("Failed to rename client registration: {}", e))?;
+            // IdC 账号还需要写入 Client Registration 文件
+            if auth_method == "IdC" {
+                if let (Some(hash), Some(cid), Some(csec)) = (client_id_hash, client_id, client_secret) {
+                    let client_reg_path = dir_path.join(format!("{}.json", hash));
+                    let client_reg_temp_path = dir_path.join(format!("{}.json.tmp", hash));
+                    let client_expires = chrono::Utc::now() + chrono::Duration::days(90);
+                    let client_reg_data = serde_json::json!({
+                        "clientId": cid,
+                        "clientSecret": csec,
+                        "expiresAt": client_expires.to_rfc3339()
+                    });
+                    let client_reg_content = serde_json::to_string_pretty(&client_reg_data)
+                        .map_err(|e| format!("Failed to serialize client registration: {}", e))?;
+                    // 原子写入
+                    std::fs::write(&client_reg_temp_path, client_reg_content)
+                        .map_err(|e| format!("Failed to write client registration temp: {}", e))?;
+                    std::fs::rename(&client_reg_temp_path, &client_reg_path)
+                        .map_err(|e| format!("Failed to rename client registration: {}", e))?;
+                }
+            }
+            Ok(())
+        })();
+
+        if let Err(e) = write_result {
+            let _ = switch_history::restore_snapshot(&dir_path, &snapshot.id, Some(&e));
+            if needs_restart {
+                let _ = launch_kiro();
             }
+            return Err(format!("{}（已回滚）", e));
         }
-        
-        // 4. 切换完成
-        let kiro_restarted = if kiro_was_running && should_restart {
-            launch_kiro().is_ok()
-        } else {
-            false
-        };
-        
-        Ok(SwitchAccountResult {
+
+        Ok((SwitchAccountResult {
             success: true,
             message: format!("Switched to {} ({}) account", provider, auth_method),
             kiro_was_running,
-            kiro_restarted,
-        })
+            kiro_restarted: false,
+            snapshot_id: Some(snapshot.id),
+            verified: false,
+            shutdown_stage,
+            profile_applied: false,
+            profile_error: None,
+        }, dir_path, needs_restart))
+    }).await.map_err(|e| format!("Task failed: {}", e))??;
+
+    // 5. 验证新 Token：账号被封禁 / Token 失效时回滚，并按需重启 IDE
+    if should_verify {
+        match verify_switched_token(&verify_provider, &verify_token).await {
+            Ok(verified) => result.verified = verified,
+            Err(e) => {
+                let snapshot_id = result.snapshot_id.clone().unwrap_or_default();
+                let reason = e.clone();
+                let restored = tokio::task::spawn_blocking(move || {
+                    switch_history::restore_snapshot(&dir_path, &snapshot_id, Some(&reason))
+                }).await.map_err(|e| format!("Task failed: {}", e))?;
+                if needs_restart {
+                    let _ = tokio::task::spawn_blocking(launch_kiro).await;
+                }
+                return Err(match restored {
+                    Ok(_) => format!("新账号验证失败，已回滚: {}", e),
+                    Err(re) => format!("新账号验证失败: {}，回滚失败: {}", e, re),
+                });
+            }
+        }
+    }
+
+    // 6. 应用账号绑定的 IDE 配置（失败时回滚已写入的文件，不影响切换结果）
+    if let Some(account_id) = account_id {
+        match tokio::task::spawn_blocking(move || account_profile::apply_for_account(&account_id)).await {
+            Ok(Ok(applied)) => result.profile_applied = applied,
+            Ok(Err(e)) => {
+                println!("[Switch] 应用账号 IDE 配置失败: {}", e);
+                result.profile_error = Some(e);
+            }
+            Err(e) => result.profile_error = Some(format!("Task failed: {}", e)),
+        }
+    }
+
+    // 7. 切换完成，按需重启 IDE
+    if needs_restart {
+        result.kiro_restarted = tokio::task::spawn_blocking(|| launch_kiro().is_ok())
+            .await
+            .unwrap_or(false);
+    }
+    Ok(result)
+}
+
+/// 验证切换后的 Token，Ok(false) 表示无法确认（网络错误 / AccessToken 过期，IDE 会自行刷新）
+async fn verify_switched_token(provider: &str, access_token: &str) -> Result<bool, String> {
+    use crate::account_health::AccountHealth;
+
+    let usage = crate::account_sync::fetch_usage(provider, access_token).await;
+    match (&usage.health, usage.error) {
+        (_, None) => Ok(true),
+        (AccountHealth::Suspended { .. }
+            | AccountHealth::RefreshTokenInvalid
+            | AccountHealth::ClientRegistrationExpired, Some(e)) => Err(e),
+        (_, Some(e)) => {
+            println!("[Switch] 无法验证新 Token: {}", e);
+            Ok(false)
+        }
+    }
+}
+
+/// 撤销最近一次切换，恢复切换前的认证缓存文件
+#[tauri::command]
+pub async fn revert_last_switch() -> Result<switch_history::SwitchSnapshot, String> {
+    tokio::task::spawn_blocking(|| {
+        let dir_path = get_sso_cache_dir().ok_or("Cannot find home directory")?;
+        let snapshot = switch_history::last_revertible().ok_or("没有可撤销的切换记录")?;
+        switch_history::restore_snapshot(&dir_path, &snapshot.id, None)
     }).await.map_err(|e| format!("Task failed: {}", e))?
 }
 
+/// 切换历史（最新的在前）
+#[tauri::command]
+pub async fn get_switch_history() -> Vec<switch_history::SwitchSnapshot> {
+    tokio::task::spawn_blocking(switch_history::list_snapshots)
+        .await
+        .unwrap_or_default()
+}
+
 // ===== 重置机器 ID =====
 
 /// 生成新的机器 ID（64位十六进制字符串）
@@ -343,17 +464,14 @@ fn generate_dev_device_id() -> String {
 
 /// 重置机器 ID（内部函数）
 fn reset_kiro_machine_id_inner() -> Result<KiroTelemetryInfo, String> {
-    let kiro_dir = get_kiro_data_dir()
+    let storage_dir = kiro_global_storage_dir()
         .ok_or("Cannot find Kiro data directory")?;
     
     let new_machine_id = generate_machine_id();
     let new_sqm_id = generate_sqm_id();
     let new_dev_device_id = generate_dev_device_id();
     
-    let storage_path = kiro_dir
-        .join("User")
-        .join("globalStorage")
-        .join("storage.json");
+    let storage_path = storage_dir.join("storage.json");
     
     let content = std::fs::read_to_string(&storage_path)
         .map_err(|e| format!("Failed to read storage.json: {}", e))?;
@@ -371,10 +489,7 @@ fn reset_kiro_machine_id_inner() -> Result<KiroTelemetryInfo, String> {
     std::fs::write(&storage_path, new_content)
         .map_err(|e| format!("Failed to write storage.json: {}", e))?;
     
-    let db_path = kiro_dir
-        .join("User")
-        .join("globalStorage")
-        .join("state.vscdb");
+    let db_path = storage_dir.join("state.vscdb");
     
     let mut new_service_machine_id = None;
     if db_path.exists() {
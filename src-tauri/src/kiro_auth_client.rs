@@ -1,5 +1,6 @@
 use crate::browser::open_browser;
 use reqwest::Client;
+use crate::account_health::{parse_retry_after, retry_after_error};
 use serde::Deserialize;
 use std::time::Duration;
 
@@ -167,6 +168,7 @@ impl KiroAuthServiceClient {
             .map_err(|e| format!("Kiro Auth Service request failed: {}", e))?;
 
         let status = resp.status();
+        let retry_after = parse_retry_after(resp.headers());
         let bytes = resp
             .bytes()
             .await
@@ -182,6 +184,12 @@ impl KiroAuthServiceClient {
             if status.as_u16() == 401 {
                 return Err("RefreshToken 已过期或无效".to_string());
             }
+            if status.as_u16() == 429 || status.is_server_error() {
+                return Err(retry_after_error(
+                    retry_after,
+                    &format!("Kiro Auth Service token refresh failed: {}", status),
+                ));
+            }
             return Err(format!(
                 "Kiro Auth Service token refresh failed: {} - {}",
                 status,
@@ -0,0 +1,127 @@
+// Kiro IDE 路径解析 - 用户数据目录（User/settings.json、User/globalStorage 所在目录）
+// Windows: %APPDATA%\Kiro；macOS: ~/Library/Application Support/Kiro；
+// Linux: $XDG_CONFIG_HOME/Kiro、~/.config/Kiro、Flatpak（~/.var/app/<id>/config/Kiro）；
+// 便携版: $KIRO_PORTABLE/user-data；应用设置中的 kiroDataDir 优先
+
+use std::path::PathBuf;
+
+use serde::Serialize;
+
+use crate::commands::app_settings_cmd::get_kiro_data_dir_override;
+
+/// 解析结果（用于设置页展示）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KiroPaths {
+    pub data_dir: Option<String>,
+    pub settings_path: Option<String>,
+    /// override / portable / xdg / flatpak / default
+    pub source: Option<String>,
+    /// 是否存在 User 目录（Kiro IDE 至少启动过一次）
+    pub exists: bool,
+}
+
+#[cfg(not(target_os = "windows"))]
+fn home_dir() -> Option<PathBuf> {
+    dirs::home_dir().or_else(|| {
+        std::env::var("USERPROFILE").or_else(|_| std::env::var("HOME")).ok().map(PathBuf::from)
+    })
+}
+
+/// 用户指定的目录既可以是数据目录，也可以是其中的 User 目录
+fn normalize_override(path: &str) -> PathBuf {
+    let path = PathBuf::from(path.trim());
+    if path.file_name().is_some_and(|n| n == "User") && !path.join("User").exists() {
+        path.parent().map(|p| p.to_path_buf()).unwrap_or(path)
+    } else {
+        path
+    }
+}
+
+/// 候选目录，按优先级排列
+fn candidates() -> Vec<(PathBuf, &'static str)> {
+    let mut list = Vec::new();
+
+    if let Ok(portable) = std::env::var("KIRO_PORTABLE") {
+        if !portable.is_empty() {
+            list.push((PathBuf::from(portable).join("user-data"), "portable"));
+        }
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        if let Ok(appdata) = std::env::var("APPDATA") {
+            list.push((PathBuf::from(appdata).join("Kiro"), "default"));
+        }
+    }
+    #[cfg(target_os = "macos")]
+    {
+        if let Some(home) = home_dir() {
+            list.push((home.join("Library").join("Application Support").join("Kiro"), "default"));
+        }
+    }
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    {
+        // XDG 规范要求绝对路径，相对路径忽略
+        if let Ok(xdg) = std::env::var("XDG_CONFIG_HOME") {
+            let xdg = PathBuf::from(xdg);
+            if xdg.is_absolute() {
+                list.push((xdg.join("Kiro"), "xdg"));
+            }
+        }
+        if let Some(home) = home_dir() {
+            list.push((home.join(".config").join("Kiro"), "default"));
+            // Flatpak 应用的配置在 ~/.var/app/<app-id>/config 下，app-id 不固定，按名称查找
+            if let Ok(entries) = std::fs::read_dir(home.join(".var").join("app")) {
+                for entry in entries.flatten() {
+                    let is_kiro = entry.file_name().to_string_lossy().to_lowercase().contains("kiro");
+                    if is_kiro {
+                        list.push((entry.path().join("config").join("Kiro"), "flatpak"));
+                    }
+                }
+            }
+        }
+    }
+
+    list
+}
+
+fn resolve() -> Option<(PathBuf, &'static str)> {
+    if let Some(custom) = get_kiro_data_dir_override() {
+        return Some((normalize_override(&custom), "override"));
+    }
+    let list = candidates();
+    // 优先使用已经存在 User 目录的候选，都不存在时使用默认位置
+    list.iter()
+        .find(|(p, _)| p.join("User").is_dir())
+        .cloned()
+        .or_else(|| list.iter().find(|(_, source)| *source == "default").cloned())
+        .or_else(|| list.into_iter().next())
+}
+
+/// Kiro IDE 用户数据目录
+pub fn kiro_data_dir() -> Option<PathBuf> {
+    resolve().map(|(p, _)| p)
+}
+
+/// Kiro IDE 的 settings.json
+pub fn kiro_settings_path() -> Option<PathBuf> {
+    Some(kiro_data_dir()?.join("User").join("settings.json"))
+}
+
+/// Kiro IDE 的 globalStorage 目录（storage.json、state.vscdb）
+pub fn kiro_global_storage_dir() -> Option<PathBuf> {
+    Some(kiro_data_dir()?.join("User").join("globalStorage"))
+}
+
+/// 当前解析到的 Kiro IDE 路径
+#[tauri::command]
+pub fn get_kiro_paths() -> KiroPaths {
+    let resolved = resolve();
+    KiroPaths {
+        data_dir: resolved.as_ref().map(|(p, _)| p.to_string_lossy().to_string()),
+        settings_path: resolved.as_ref().map(|(p, _)| p.join("User").join("settings.json").to_string_lossy().to_string()),
+        source: resolved.as_ref().map(|(_, s)| s.to_string()),
+        exists: resolved.as_ref().is_some_and(|(p, _)| p.join("User").is_dir()),
+    }
+}
@@ -0,0 +1,122 @@
+// 登录会话注册表 - 以 OAuth state 为键
+// 支持多个登录同时进行，回调按 state 路由到对应会话；每个会话有独立的超时，过期后自动清理
+
+use std::collections::HashMap;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use once_cell::sync::Lazy;
+
+use crate::deep_link_handler::OAuthCallbackResult;
+use crate::providers::web_oauth::WebOAuthInitResult;
+
+/// 登录会话默认超时（与回调等待时间一致）
+pub const LOGIN_SESSION_TIMEOUT: Duration = Duration::from_secs(300);
+
+pub type CallbackSender = Sender<Result<OAuthCallbackResult, String>>;
+pub type CallbackReceiver = Receiver<Result<OAuthCallbackResult, String>>;
+
+enum SessionKind {
+    /// Desktop 社交登录：回调结果发给阻塞等待的 login()
+    Callback(CallbackSender),
+    /// Web OAuth：保存 InitiateLogin 结果，等待 web_oauth_complete 取走
+    WebOAuth(WebOAuthInitResult),
+}
+
+struct LoginSession {
+    provider: String,
+    kind: SessionKind,
+    expires_at: Instant,
+}
+
+static LOGIN_SESSIONS: Lazy<Mutex<HashMap<String, LoginSession>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// 清理已过期的会话（调用方持有锁）
+fn purge_expired(sessions: &mut HashMap<String, LoginSession>) {
+    let now = Instant::now();
+    sessions.retain(|state, session| {
+        let alive = session.expires_at > now;
+        if !alive {
+            println!("[LoginSession] 会话已过期: {} ({})", state, session.provider);
+        }
+        alive
+    });
+}
+
+fn insert(state: &str, provider: &str, kind: SessionKind, timeout: Duration) {
+    let mut sessions = LOGIN_SESSIONS.lock().unwrap();
+    purge_expired(&mut sessions);
+    sessions.insert(state.to_string(), LoginSession {
+        provider: provider.to_string(),
+        kind,
+        expires_at: Instant::now() + timeout,
+    });
+    println!("[LoginSession] 注册会话: {} ({})，进行中 {} 个", state, provider, sessions.len());
+}
+
+/// 注册一个等待回调的会话，返回接收端
+pub fn register_callback(state: &str, provider: &str, timeout: Duration) -> CallbackReceiver {
+    let (tx, rx) = mpsc::channel();
+    insert(state, provider, SessionKind::Callback(tx), timeout);
+    rx
+}
+
+/// 注册 Web OAuth 会话
+pub fn register_web_oauth(init_result: WebOAuthInitResult, timeout: Duration) {
+    let state = init_result.state.clone();
+    let provider = init_result.provider_id.clone();
+    insert(&state, &provider, SessionKind::WebOAuth(init_result), timeout);
+}
+
+/// 把回调结果投递给 state 对应的会话，没有匹配的会话时返回 false
+pub fn deliver(state: &str, result: Result<OAuthCallbackResult, String>) -> bool {
+    let mut sessions = LOGIN_SESSIONS.lock().unwrap();
+    purge_expired(&mut sessions);
+
+    match sessions.remove(state) {
+        Some(LoginSession { kind: SessionKind::Callback(tx), provider, .. }) => {
+            println!("[LoginSession] 回调已路由到会话: {} ({})", state, provider);
+            if tx.send(result).is_err() {
+                println!("[LoginSession] 会话 {} 的等待方已退出", state);
+            }
+            true
+        }
+        Some(other) => {
+            println!("[LoginSession] 会话 {} 不是回调会话 ({})，忽略该回调", state, other.provider);
+            sessions.insert(state.to_string(), other);
+            false
+        }
+        None => {
+            println!("[LoginSession] 未匹配的回调: state={}，进行中的会话 {} 个", state, sessions.len());
+            false
+        }
+    }
+}
+
+/// 取走 Web OAuth 会话
+pub fn take_web_oauth(state: &str) -> Option<WebOAuthInitResult> {
+    let mut sessions = LOGIN_SESSIONS.lock().unwrap();
+    purge_expired(&mut sessions);
+
+    match sessions.remove(state) {
+        Some(LoginSession { kind: SessionKind::WebOAuth(init_result), .. }) => Some(init_result),
+        Some(other) => {
+            println!("[LoginSession] 会话 {} 不是 Web OAuth 会话 ({})", state, other.provider);
+            sessions.insert(state.to_string(), other);
+            None
+        }
+        None => {
+            println!("[LoginSession] 未匹配的 Web OAuth 回调: state={}", state);
+            None
+        }
+    }
+}
+
+/// 结束会话（超时 / 取消 / 登录失败时清理）
+pub fn remove(state: &str) {
+    if let Some(session) = LOGIN_SESSIONS.lock().unwrap().remove(state) {
+        println!("[LoginSession] 移除会话: {} ({})", state, session.provider);
+    }
+}
@@ -0,0 +1,141 @@
+// 本地回环 OAuth 回调监听
+// 在 127.0.0.1 的随机端口上接收 http://127.0.0.1:<port>/oauth/callback?code=xxx&state=xxx
+// 用于 kiro:// 协议未注册（或被其他程序占用）的环境，收到一次有效回调或超时后关闭
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use tiny_http::{Header, Request, Response, Server};
+
+use crate::deep_link_handler::{CallbackWaiter, OAuthCallbackResult};
+
+const CALLBACK_PATH: &str = "/oauth/callback";
+
+/// 本地回环回调等待器
+pub struct LoopbackCallbackWaiter {
+    server: Mutex<Option<Server>>,
+    port: u16,
+    expected_state: String,
+    timeout: Duration,
+}
+
+impl LoopbackCallbackWaiter {
+    /// 绑定 127.0.0.1 的随机端口
+    pub fn bind(state: &str) -> Result<Self, String> {
+        let server = Server::http("127.0.0.1:0")
+            .map_err(|e| format!("启动本地回调监听失败: {}", e))?;
+        let port = server.server_addr().to_ip()
+            .map(|addr| addr.port())
+            .ok_or("无法获取本地回调监听端口")?;
+        println!("[Loopback] Listening on 127.0.0.1:{}", port);
+
+        Ok(Self {
+            server: Mutex::new(Some(server)),
+            port,
+            expected_state: state.to_string(),
+            timeout: Duration::from_secs(300),
+        })
+    }
+
+    /// 解析一次回调请求，返回 None 表示不是回调请求（继续等待）
+    fn parse_callback(&self, url: &str) -> Option<Result<OAuthCallbackResult, String>> {
+        let parsed = url::Url::parse(&format!("http://127.0.0.1:{}{}", self.port, url)).ok()?;
+        if parsed.path() != CALLBACK_PATH {
+            return None;
+        }
+        let params: std::collections::HashMap<_, _> = parsed.query_pairs().collect();
+
+        if let Some(error) = params.get("error") {
+            let desc = params.get("error_description")
+                .map(|s| s.to_string())
+                .unwrap_or_else(|| "Unknown error".to_string());
+            println!("[Loopback] OAuth error: {} - {}", error, desc);
+            return Some(Err(format!("OAuth error: {} - {}", error, desc)));
+        }
+
+        let Some(code) = params.get("code") else {
+            return Some(Err("Missing code parameter".to_string()));
+        };
+        let Some(state) = params.get("state") else {
+            return Some(Err("Missing state parameter".to_string()));
+        };
+        if *state != self.expected_state {
+            println!("[Loopback] State mismatch: expected {}, got {}", self.expected_state, state);
+            return Some(Err("State mismatch - possible CSRF attack".to_string()));
+        }
+
+        Some(Ok(OAuthCallbackResult { code: code.to_string(), state: state.to_string() }))
+    }
+}
+
+impl CallbackWaiter for LoopbackCallbackWaiter {
+    fn redirect_uri(&self) -> String {
+        format!("http://127.0.0.1:{}{}", self.port, CALLBACK_PATH)
+    }
+
+    fn wait_for_callback(&self) -> Result<OAuthCallbackResult, String> {
+        // 取出 server，函数返回时随之关闭监听
+        let server = self.server.lock().unwrap().take()
+            .ok_or("Callback listener already consumed")?;
+        let deadline = Instant::now() + self.timeout;
+
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Err("OAuth callback timeout (5 minutes)".to_string());
+            }
+
+            let request = match server.recv_timeout(remaining) {
+                Ok(Some(request)) => request,
+                Ok(None) => continue,
+                Err(e) => return Err(format!("本地回调监听出错: {}", e)),
+            };
+
+            match self.parse_callback(request.url()) {
+                // favicon 等无关请求
+                None => respond(request, 404, "Not Found"),
+                // state 不匹配的请求可能来自其他页面，拒绝后继续等待真正的回调
+                Some(Err(e)) if e.starts_with("State mismatch") => {
+                    respond(request, 400, &result_page(false, &e));
+                }
+                Some(result) => {
+                    match &result {
+                        Ok(_) => respond(request, 200, &result_page(true, "")),
+                        Err(e) => respond(request, 400, &result_page(false, e)),
+                    }
+                    println!("[Loopback] Callback received, listener closed");
+                    return result;
+                }
+            }
+        }
+    }
+}
+
+fn respond(request: Request, status: u16, body: &str) {
+    let header = Header::from_bytes(&b"Content-Type"[..], &b"text/html; charset=utf-8"[..])
+        .expect("static header");
+    let response = Response::from_string(body)
+        .with_status_code(status)
+        .with_header(header);
+    if let Err(e) = request.respond(response) {
+        println!("[Loopback] Failed to send response: {}", e);
+    }
+}
+
+/// 回调结果页
+fn result_page(success: bool, error: &str) -> String {
+    let (title, message) = if success {
+        ("登录成功 / Login successful", "可以关闭此页面并返回 Kiro Account Manager。<br>You can close this page and return to Kiro Account Manager.".to_string())
+    } else {
+        ("登录失败 / Login failed", html_escape(error))
+    };
+    format!(
+        "<!DOCTYPE html><html><head><meta charset=\"utf-8\"><title>{title}</title></head>\
+         <body style=\"font-family:sans-serif;text-align:center;padding-top:80px\">\
+         <h2>{title}</h2><p>{message}</p></body></html>"
+    )
+}
+
+fn html_escape(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
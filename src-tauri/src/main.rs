@@ -1,5 +1,6 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod alerts;
 mod auth;
 mod auth_social;
 mod aws_sso_client;
@@ -7,8 +8,18 @@ mod browser;
 mod codewhisperer_client;
 mod commands;
 mod deep_link_handler;
+mod loopback_server;
+mod login_session;
+mod token_watcher;
+mod switch_history;
+mod importers;
 
 mod kiro;
+mod kiro_paths;
+mod jsonc;
+mod settings_profile;
+mod account_profile;
+mod model_catalog;
 mod kiro_auth_client;
 mod mcp;
 mod powers;
@@ -16,7 +27,14 @@ mod process;
 mod providers;
 mod state;
 mod steering;
+mod usage;
+mod usage_history;
 mod account;
+mod account_health;
+mod active_account;
+mod account_export;
+mod account_query;
+mod account_sync;
 
 use account::AccountStore;
 use auth::AuthState;
@@ -28,25 +46,36 @@ use tauri::{Listener, Manager};
 use browser::detect_installed_browsers;
 use commands::account_cmd::{
     get_accounts, delete_account, delete_accounts, update_account, sync_account,
-    refresh_account_token, verify_account, add_account_by_social, add_local_kiro_account,
-    add_account_by_idc, import_accounts, export_accounts
+    query_accounts, tag_accounts, untag_accounts, update_account_meta, get_account_tags_and_groups,
+    refresh_account_token, verify_account, add_account_by_social, add_local_kiro_account, get_active_account,
+    add_account_by_idc, import_accounts, export_accounts, export_accounts_with_profile
 };
+use commands::account_profile_cmd::*;
+use commands::model_cmd::*;
+use commands::alert_cmd::*;
 use commands::app_settings_cmd::*;
 use commands::auth_cmd::*;
+use commands::import_cmd::*;
 use commands::kiro2api_cmd::*;
 use commands::kiro_settings_cmd::*;
 use commands::machine_guid_cmd::*;
 use commands::mcp_cmd::*;
 use commands::powers_cmd::*;
 use commands::proxy_cmd::*;
+use commands::settings_profile_cmd::*;
 use commands::sso_import_cmd::*;
 use commands::update_cmd::*;
+use commands::usage_cmd::*;
+use commands::sync_cmd::*;
+use commands::device_auth_cmd::*;
 use commands::web_oauth_cmd::*;
 use commands::steering_cmd::*;
 use kiro::{
     get_kiro_local_token, get_kiro_telemetry_info, reset_kiro_machine_id, switch_kiro_account,
+    revert_last_switch, get_switch_history,
 };
-use process::{close_kiro_ide, is_kiro_ide_running, start_kiro_ide};
+use kiro_paths::get_kiro_paths;
+use process::{close_kiro_ide, get_kiro_ide_info, is_kiro_ide_running, start_kiro_ide};
 
 fn main() {
     tauri::Builder::default()
@@ -57,6 +86,7 @@ fn main() {
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_deep_link::init())
+        .plugin(tauri_plugin_notification::init())
         .setup(|app| {
             // 监听 deep link 事件 (使用 kiro:// 协议)
             #[cfg(any(target_os = "linux", all(debug_assertions, windows)))]
@@ -77,13 +107,15 @@ fn main() {
                     let _ = window.set_focus();
                 }
             });
+
+            // 监听 IDE 刷新的 Token 并写回账号列表
+            token_watcher::start(app.handle().clone());
             
             Ok(())
         })
         .manage(AppState {
             store: Mutex::new(AccountStore::new()),
             auth: AuthState::new(),
-            pending_login: Mutex::new(None),
             kiro2api: Mutex::new(None),
         })
         .invoke_handler(tauri::generate_handler![
@@ -91,37 +123,83 @@ fn main() {
             get_accounts,
             delete_account,
             delete_accounts,
+            query_accounts,
+            tag_accounts,
+            untag_accounts,
+            update_account_meta,
+            get_account_tags_and_groups,
             update_account,
             sync_account,
             refresh_account_token,
+            sync_accounts,
+            cancel_sync_accounts,
             verify_account,
             add_account_by_social,
             add_local_kiro_account,
+            get_active_account,
             add_account_by_idc,
             import_accounts,
             export_accounts,
+            export_accounts_with_profile,
+            // 用量历史
+            get_usage_history,
+            get_usage_daily_deltas,
+            get_usage_burn_rate,
+            // 用量告警
+            get_recent_alerts,
+            clear_alerts,
+            get_import_formats,
+            preview_import_accounts,
+            import_accounts_with_format,
             // Auth 命令
             get_current_user,
             logout,
             kiro_login,
+            reauthenticate_account,
+            start_device_login,
+            start_device_reauth,
+            cancel_device_login,
             get_supported_providers,
             handle_kiro_social_callback,
             add_kiro_account,
             // Kiro IDE 命令
             get_kiro_local_token,
             switch_kiro_account,
+            revert_last_switch,
+            get_switch_history,
             get_kiro_telemetry_info,
             reset_kiro_machine_id,
             // 进程管理命令
             close_kiro_ide,
             start_kiro_ide,
             is_kiro_ide_running,
+            get_kiro_ide_info,
             // Kiro IDE 设置命令
             get_kiro_settings,
+            get_kiro_paths,
             set_kiro_proxy,
             set_kiro_model,
+            get_kiro_setting,
+            get_all_kiro_settings,
+            set_kiro_setting,
+            unset_kiro_setting,
+            get_settings_profiles,
+            save_settings_profile,
+            delete_settings_profile,
+            diff_settings_profile,
+            apply_settings_profile,
+            revert_settings_profile,
+            get_applied_settings_profiles,
+            get_account_ide_profile,
+            save_account_ide_profile,
+            apply_account_ide_profile,
+            restore_account_ide_profile,
+            get_applied_account_ide_profiles,
+            get_account_models,
+            get_model_availability,
             // Kiro2API 服务命令
             get_kiro2api_status,
+            check_kiro2api_health,
             get_kiro2api_request_logs,
             start_kiro2api_service,
             stop_kiro2api_service,
@@ -0,0 +1,330 @@
+// 模型目录 - 按账号获取 ListAvailableModels 返回的可用模型并缓存
+// 缓存保存在 <应用数据目录>/model-catalog.json，用于校验模型选择和展示各账号的模型可用性
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Local};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+use crate::account::{get_app_data_dir, Account};
+use crate::account_sync;
+use crate::active_account;
+use crate::auth::PROFILE_ARN;
+use crate::codewhisperer_client::{AvailableModel, CodeWhispererClient};
+use crate::kiro::get_machine_id;
+use crate::state::AppState;
+
+/// 缓存有效期
+const CACHE_TTL_HOURS: i64 = 12;
+/// Token 剩余有效期不足时先刷新
+const TOKEN_REFRESH_MARGIN_SECS: i64 = 120;
+/// 批量获取时的并发数
+const FETCH_CONCURRENCY: usize = 4;
+
+/// Kiro settings.json 中选择模型的键
+pub const MODEL_SETTING_KEY: &str = "kiroAgent.modelSelection";
+
+/// 串行化缓存文件的读改写
+static CACHE_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+/// 一个账号的模型目录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountModelCatalog {
+    pub account_id: String,
+    pub fetched_at: String,
+    pub models: Vec<AvailableModel>,
+    pub default_model: Option<String>,
+}
+
+impl AccountModelCatalog {
+    fn fetched_at_time(&self) -> Option<DateTime<Local>> {
+        chrono::NaiveDateTime::parse_from_str(&self.fetched_at, "%Y/%m/%d %H:%M:%S")
+            .ok()
+            .and_then(|dt| dt.and_local_timezone(Local).single())
+    }
+
+    fn is_fresh(&self) -> bool {
+        self.fetched_at_time()
+            .is_some_and(|t| Local::now() - t < chrono::Duration::hours(CACHE_TTL_HOURS))
+    }
+
+    fn contains(&self, model_id: &str) -> bool {
+        self.models.iter().any(|m| m.model_id == model_id)
+    }
+}
+
+/// 一个模型在各账号中的可用情况
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelAvailability {
+    pub model_id: String,
+    pub model_name: Option<String>,
+    pub rate_multiplier: Option<f64>,
+    /// 可以使用该模型的账号
+    pub account_ids: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelAvailabilityReport {
+    pub models: Vec<ModelAvailability>,
+    /// 已获取模型目录的账号数
+    pub cached_accounts: usize,
+    /// 还没有模型目录的账号
+    pub uncached_account_ids: Vec<String>,
+    /// 本次获取失败的账号及原因
+    pub errors: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CatalogStore {
+    #[serde(default)]
+    accounts: HashMap<String, AccountModelCatalog>,
+}
+
+fn store_path() -> PathBuf {
+    get_app_data_dir().join("model-catalog.json")
+}
+
+fn load_store() -> CatalogStore {
+    std::fs::read_to_string(store_path())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_store(store: &CatalogStore) -> Result<(), String> {
+    let path = store_path();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).ok();
+    }
+    let content = serde_json::to_string_pretty(store)
+        .map_err(|e| format!("序列化模型目录失败: {}", e))?;
+    let temp = path.with_extension("json.tmp");
+    std::fs::write(&temp, content).map_err(|e| format!("写入模型目录失败: {}", e))?;
+    std::fs::rename(&temp, &path).map_err(|e| format!("写入模型目录失败: {}", e))
+}
+
+fn update_store(edit: impl FnOnce(&mut CatalogStore)) -> Result<(), String> {
+    let _guard = CACHE_LOCK.lock().unwrap();
+    let mut store = load_store();
+    edit(&mut store);
+    save_store(&store)
+}
+
+/// 账号的 Token 即将过期时先刷新并写回账号列表，返回可用的 access token
+async fn ensure_access_token(state: &AppState, account: &Account) -> Result<String, String> {
+    let valid_until = Local::now() + chrono::Duration::seconds(TOKEN_REFRESH_MARGIN_SECS);
+    if let Some(token) = account.access_token.clone() {
+        if account.expires_at_time().is_some_and(|t| t > valid_until) {
+            return Ok(token);
+        }
+    }
+
+    println!("[ModelCatalog] {} 的 Token 即将过期，先刷新", account.email);
+    let result = account_sync::fetch_sync(account, false).await?;
+    let token = result.access_token.clone();
+    let mut store = state.store.lock().unwrap();
+    if let Some(a) = store.accounts.iter_mut().find(|a| a.id == account.id) {
+        account_sync::apply_sync(a, result);
+        store.save_to_file();
+    }
+    Ok(token)
+}
+
+/// 用已刷新的 Token 请求账号的模型目录（不读写缓存）
+async fn request_catalog(account: &Account, access_token: &str) -> Result<AccountModelCatalog, String> {
+    // Social 账号需要 profileArn，IdC 账号不需要
+    let profile_arn = (!account.is_idc())
+        .then(|| account.profile_arn.clone().unwrap_or_else(|| PROFILE_ARN.to_string()));
+
+    let client = CodeWhispererClient::new(&get_machine_id());
+    let (models, default_model) = client
+        .list_available_models(access_token, profile_arn.as_deref())
+        .await?;
+
+    Ok(AccountModelCatalog {
+        account_id: account.id.clone(),
+        fetched_at: Local::now().format("%Y/%m/%d %H:%M:%S").to_string(),
+        models,
+        default_model,
+    })
+}
+
+fn find_account(state: &AppState, account_id: &str) -> Result<Account, String> {
+    let store = state.store.lock().unwrap();
+    store.accounts.iter()
+        .find(|a| a.id == account_id)
+        .cloned()
+        .ok_or_else(|| format!("账号不存在: {}", account_id))
+}
+
+/// 获取账号的模型目录，缓存未过期且不强制刷新时直接返回缓存
+pub async fn get_catalog(state: &AppState, account_id: &str, force_refresh: bool) -> Result<AccountModelCatalog, String> {
+    if !force_refresh {
+        if let Some(cached) = load_store().accounts.remove(account_id).filter(|c| c.is_fresh()) {
+            return Ok(cached);
+        }
+    }
+
+    let account = find_account(state, account_id)?;
+    let access_token = ensure_access_token(state, &account).await?;
+    let catalog = request_catalog(&account, &access_token).await?;
+    let saved = catalog.clone();
+    update_store(|store| { store.accounts.insert(saved.account_id.clone(), saved); })?;
+    println!("[ModelCatalog] 已获取 {} 的模型目录，共 {} 个模型", account.email, catalog.models.len());
+    Ok(catalog)
+}
+
+/// 获取全部账号的模型目录（refresh 为 false 时只获取没有缓存或已过期的账号）
+async fn refresh_catalogs(state: &AppState, accounts: &[Account], refresh: bool) -> HashMap<String, String> {
+    let cached = load_store().accounts;
+    let targets: Vec<Account> = accounts.iter()
+        .filter(|a| a.refresh_token.is_some())
+        .filter(|a| refresh || !cached.get(&a.id).is_some_and(|c| c.is_fresh()))
+        .cloned()
+        .collect();
+    if targets.is_empty() {
+        return HashMap::new();
+    }
+
+    // 先逐个刷新即将过期的 Token（需要写回账号列表），再并发请求模型目录
+    let mut prepared = Vec::new();
+    let mut errors = HashMap::new();
+    for account in targets {
+        match ensure_access_token(state, &account).await {
+            Ok(token) => prepared.push((account, token)),
+            Err(e) => { errors.insert(account.id.clone(), e); }
+        }
+    }
+
+    let semaphore = Arc::new(Semaphore::new(FETCH_CONCURRENCY));
+    let mut tasks = JoinSet::new();
+    for (account, token) in prepared {
+        let semaphore = semaphore.clone();
+        tasks.spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            let result = request_catalog(&account, &token).await;
+            (account.id, result)
+        });
+    }
+
+    let mut fetched = Vec::new();
+    while let Some(joined) = tasks.join_next().await {
+        match joined {
+            Ok((_, Ok(catalog))) => fetched.push(catalog),
+            Ok((account_id, Err(e))) => { errors.insert(account_id, e); }
+            Err(e) => println!("[ModelCatalog] 获取任务异常: {}", e),
+        }
+    }
+
+    println!("[ModelCatalog] 已获取 {} 个账号的模型目录，失败 {} 个", fetched.len(), errors.len());
+    if let Err(e) = update_store(|store| {
+        for catalog in fetched {
+            store.accounts.insert(catalog.account_id.clone(), catalog);
+        }
+    }) {
+        println!("[ModelCatalog] {}", e);
+    }
+    errors
+}
+
+/// 各模型在全部账号中的可用情况
+pub async fn availability(state: &AppState, refresh: bool) -> ModelAvailabilityReport {
+    let accounts = state.store.lock().unwrap().get_all();
+    let errors = refresh_catalogs(state, &accounts, refresh).await;
+    let catalogs = load_store().accounts;
+
+    let mut report = ModelAvailabilityReport { errors, ..Default::default() };
+    let mut by_model: HashMap<String, ModelAvailability> = HashMap::new();
+    for account in &accounts {
+        let Some(catalog) = catalogs.get(&account.id) else {
+            report.uncached_account_ids.push(account.id.clone());
+            continue;
+        };
+        report.cached_accounts += 1;
+        for model in &catalog.models {
+            by_model.entry(model.model_id.clone())
+                .or_insert_with(|| ModelAvailability {
+                    model_id: model.model_id.clone(),
+                    model_name: model.model_name.clone(),
+                    rate_multiplier: model.rate_multiplier,
+                    account_ids: Vec::new(),
+                })
+                .account_ids.push(account.id.clone());
+        }
+    }
+
+    report.models = by_model.into_values().collect();
+    report.models.sort_by(|a, b| b.account_ids.len().cmp(&a.account_ids.len()).then_with(|| a.model_id.cmp(&b.model_id)));
+    report
+}
+
+/// 校验模型 ID：有该账号的模型目录缓存时按该账号校验，否则按全部已缓存的目录校验
+/// 还没有任何模型目录时无法校验，直接通过
+pub fn validate_model(model_id: &str, account_id: Option<&str>) -> Result<(), String> {
+    let model_id = model_id.trim();
+    if model_id.is_empty() {
+        return Err("模型不能为空".to_string());
+    }
+
+    let catalogs = load_store().accounts;
+    let scoped: Vec<&AccountModelCatalog> = match account_id.and_then(|id| catalogs.get(id)) {
+        Some(catalog) => vec![catalog],
+        None => catalogs.values().collect(),
+    };
+    if scoped.is_empty() {
+        println!("[ModelCatalog] 没有模型目录缓存，跳过校验: {}", model_id);
+        return Ok(());
+    }
+    if scoped.iter().any(|c| c.contains(model_id)) {
+        return Ok(());
+    }
+
+    let mut available: Vec<&str> = scoped.iter()
+        .flat_map(|c| c.models.iter().map(|m| m.model_id.as_str()))
+        .collect();
+    available.sort();
+    available.dedup();
+    Err(format!("模型 {} 不在可用模型列表中，可用模型: {}", model_id, available.join(", ")))
+}
+
+/// 写入模型前校验：按指定账号（未指定时为 IDE 当前登录的账号）的模型目录校验，没有缓存时先获取
+/// 无法确定账号或获取失败时按全部已缓存的目录校验
+pub async fn ensure_model_available(state: &AppState, model_id: &str, account_id: Option<&str>) -> Result<(), String> {
+    let account_id = match account_id {
+        Some(id) => Some(id.to_string()),
+        None => active_account::resolve(state).await.account_id,
+    };
+    if let Some(id) = account_id.as_deref() {
+        if let Err(e) = get_catalog(state, id, false).await {
+            println!("[ModelCatalog] 获取 {} 的模型目录失败，按全部缓存校验: {}", id, e);
+        }
+    }
+    validate_model(model_id, account_id.as_deref())
+}
+
+/// 校验写入 kiroAgent.modelSelection 的值（设置项 / 设置方案）
+pub async fn ensure_model_setting(state: &AppState, value: &serde_json::Value) -> Result<(), String> {
+    let model_id = value.as_str().ok_or("模型必须是字符串")?;
+    ensure_model_available(state, model_id, None).await
+}
+
+/// 删除账号时清理对应的模型目录
+pub fn remove_catalogs(account_ids: &[String]) -> Result<(), String> {
+    let _guard = CACHE_LOCK.lock().unwrap();
+    let mut store = load_store();
+    let before = store.accounts.len();
+    store.accounts.retain(|id, _| !account_ids.contains(id));
+    if store.accounts.len() != before {
+        save_store(&store)?;
+    }
+    Ok(())
+}
@@ -1,66 +1,384 @@
 // 进程管理相关功能
+// 安装位置：应用设置中的 kiroIdePath 优先，其次是各平台的默认位置
+// Linux 额外查找 /usr/share/kiro、/opt、AppImage 和 .desktop 文件的 Exec 行
 
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
+use serde::{Deserialize, Serialize};
+
+use crate::commands::app_settings_cmd::{get_kiro_ide_path_override, get_kiro_shutdown_timeout};
+
 #[cfg(target_os = "windows")]
 use std::os::windows::process::CommandExt;
 
 #[cfg(target_os = "windows")]
 const CREATE_NO_WINDOW: u32 = 0x08000000;
 
-/// 检查 Kiro IDE 是否正在运行（内部函数，同步）
+/// 启动结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KiroLaunchInfo {
+    pub pid: Option<u32>,
+    pub path: String,
+    pub version: Option<String>,
+}
+
+/// Kiro IDE 运行状态和安装信息（用于设置页展示）
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KiroIdeInfo {
+    pub running: bool,
+    pub pids: Vec<u32>,
+    /// 检测到的安装位置
+    pub path: Option<String>,
+    /// configured / default / appimage / desktop / path
+    pub source: Option<String>,
+    pub version: Option<String>,
+}
+
+/// 解析到的 Kiro IDE 安装
+struct KiroInstall {
+    /// 安装位置：Windows / Linux 为可执行文件，macOS 为 .app 目录
+    path: PathBuf,
+    /// 启动参数（Linux .desktop 的 Exec 行可能带参数，首项为程序）
+    #[cfg_attr(any(target_os = "windows", target_os = "macos"), allow(dead_code))]
+    command: Vec<String>,
+    source: &'static str,
+}
+
+impl KiroInstall {
+    fn new(path: PathBuf, source: &'static str) -> Self {
+        KiroInstall { command: vec![path.to_string_lossy().to_string()], path, source }
+    }
+}
+
+/// 从安装目录的 resources/app/package.json 读取版本号
+fn read_version(path: &Path) -> Option<String> {
+    let app_dir = if path.extension().is_some_and(|e| e == "app") {
+        path.join("Contents").join("Resources").join("app")
+    } else {
+        // 可执行文件可能是符号链接（如 /usr/bin/kiro）
+        let real = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+        real.parent()?.join("resources").join("app")
+    };
+    let content = std::fs::read_to_string(app_dir.join("package.json")).ok()?;
+    let json: serde_json::Value = serde_json::from_str(&content).ok()?;
+    json.get("version").and_then(|v| v.as_str()).map(|s| s.to_string())
+}
+
+// ===== 进程查找 =====
+
+/// 查找 Kiro IDE 的进程 ID（按 PID 升序，主进程通常在最前）
 #[cfg(target_os = "windows")]
-pub fn check_kiro_running() -> bool {
+pub fn find_kiro_pids() -> Vec<u32> {
     let output = Command::new("tasklist")
-        .args(["/FI", "IMAGENAME eq Kiro.exe", "/NH"])
+        .args(["/FI", "IMAGENAME eq Kiro.exe", "/NH", "/FO", "CSV"])
         .creation_flags(CREATE_NO_WINDOW)
         .output();
-    
-    match output {
-        Ok(out) => String::from_utf8_lossy(&out.stdout).contains("Kiro.exe"),
-        Err(_) => false
-    }
+
+    let Ok(out) = output else {
+        return Vec::new();
+    };
+    // "Kiro.exe","1234","Console","1","100,000 K"
+    let mut pids: Vec<u32> = String::from_utf8_lossy(&out.stdout)
+        .lines()
+        .filter(|line| line.starts_with("\"Kiro.exe\""))
+        .filter_map(|line| line.split("\",\"").nth(1)?.parse().ok())
+        .collect();
+    pids.sort();
+    pids
 }
 
 #[cfg(target_os = "macos")]
+pub fn find_kiro_pids() -> Vec<u32> {
+    let pgrep = |args: [&str; 2]| -> Vec<u32> {
+        Command::new("pgrep")
+            .args(args)
+            .output()
+            .map(|out| {
+                String::from_utf8_lossy(&out.stdout)
+                    .lines()
+                    .filter_map(|l| l.trim().parse().ok())
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+
+    // 1. 精确匹配 "Kiro"，2. 模糊匹配 Kiro.app 下的进程
+    let mut pids = pgrep(["-x", "Kiro"]);
+    if pids.is_empty() {
+        pids = pgrep(["-f", "Kiro.app"]);
+    }
+    pids.sort();
+    pids
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+pub fn find_kiro_pids() -> Vec<u32> {
+    let own_pid = std::process::id();
+    let Ok(entries) = std::fs::read_dir("/proc") else {
+        return Vec::new();
+    };
+    let mut pids: Vec<u32> = entries
+        .flatten()
+        .filter_map(|e| e.file_name().to_str()?.parse::<u32>().ok())
+        .filter(|pid| *pid != own_pid)
+        .filter(|pid| linux_process_exe(*pid).is_some_and(|exe| is_kiro_exe(&exe)))
+        .collect();
+    pids.sort();
+    pids
+}
+
+/// /proc/<pid>/exe 指向的可执行文件（只能读取当前用户的进程）
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn linux_process_exe(pid: u32) -> Option<PathBuf> {
+    let exe = std::fs::read_link(format!("/proc/{}/exe", pid)).ok()?;
+    // 升级后旧进程的 exe 会带 " (deleted)" 后缀
+    let exe = exe.to_string_lossy();
+    Some(PathBuf::from(exe.strip_suffix(" (deleted)").unwrap_or(&exe)))
+}
+
+/// 可执行文件名为 kiro（deb/rpm/tar.gz、AppImage 挂载目录、Flatpak 均如此）
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn is_kiro_exe(exe: &Path) -> bool {
+    exe.file_name().is_some_and(|n| n.to_string_lossy().eq_ignore_ascii_case("kiro"))
+}
+
+/// 检查 Kiro IDE 是否正在运行（内部函数，同步）
 pub fn check_kiro_running() -> bool {
-    // 尝试多种方式检测 Kiro 进程
-    // 1. 精确匹配 "Kiro"
-    let output = Command::new("pgrep")
-        .args(["-x", "Kiro"])
-        .output();
-    
-    if let Ok(out) = output {
-        if out.status.success() {
-            return true;
+    !find_kiro_pids().is_empty()
+}
+
+// ===== 安装位置 =====
+
+#[cfg(target_os = "windows")]
+fn find_kiro_install() -> Option<KiroInstall> {
+    if let Some(custom) = get_kiro_ide_path_override() {
+        let path = PathBuf::from(custom.trim());
+        let path = if path.is_dir() { path.join("Kiro.exe") } else { path };
+        return Some(KiroInstall::new(path, "configured"));
+    }
+
+    let mut candidates = Vec::new();
+    if let Ok(localappdata) = std::env::var("LOCALAPPDATA") {
+        candidates.push(PathBuf::from(localappdata).join("Programs").join("Kiro").join("Kiro.exe"));
+    }
+    if let Ok(program_files) = std::env::var("ProgramFiles") {
+        candidates.push(PathBuf::from(program_files).join("Kiro").join("Kiro.exe"));
+    }
+    candidates.into_iter()
+        .find(|p| p.is_file())
+        .map(|p| KiroInstall::new(p, "default"))
+}
+
+#[cfg(target_os = "macos")]
+fn find_kiro_install() -> Option<KiroInstall> {
+    if let Some(custom) = get_kiro_ide_path_override() {
+        let path = PathBuf::from(custom.trim());
+        // 也接受 Kiro.app/Contents/MacOS/Kiro 这样的可执行文件路径
+        let app = path.ancestors()
+            .find(|p| p.extension().is_some_and(|e| e == "app"))
+            .map(|p| p.to_path_buf())
+            .unwrap_or(path);
+        return Some(KiroInstall::new(app, "configured"));
+    }
+
+    let mut candidates = vec![PathBuf::from("/Applications/Kiro.app")];
+    if let Some(home) = dirs::home_dir() {
+        candidates.push(home.join("Applications").join("Kiro.app"));
+    }
+    candidates.into_iter()
+        .find(|p| p.is_dir())
+        .map(|p| KiroInstall::new(p, "default"))
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn find_kiro_install() -> Option<KiroInstall> {
+    if let Some(custom) = get_kiro_ide_path_override() {
+        let path = PathBuf::from(custom.trim());
+        if path.extension().is_some_and(|e| e == "desktop") {
+            return linux_install_from_desktop(&path, "configured");
         }
+        let path = if path.is_dir() { path.join("kiro") } else { path };
+        return Some(KiroInstall::new(path, "configured"));
     }
-    
-    // 2. 模糊匹配包含 "Kiro" 的进程
-    let output = Command::new("pgrep")
-        .args(["-f", "Kiro.app"])
-        .output();
-    
-    match output {
-        Ok(out) => out.status.success(),
-        Err(_) => false
+
+    // 1. 常见安装目录（deb/rpm 安装到 /usr/share/kiro，tar.gz 通常解压到 /opt）
+    let mut candidates = vec![
+        PathBuf::from("/usr/share/kiro/kiro"),
+        PathBuf::from("/opt/Kiro/kiro"),
+        PathBuf::from("/opt/kiro/kiro"),
+    ];
+    let home = dirs::home_dir();
+    if let Some(home) = &home {
+        candidates.push(home.join(".local").join("share").join("kiro").join("kiro"));
+    }
+    if let Some(path) = candidates.into_iter().find(|p| p.is_file()) {
+        return Some(KiroInstall::new(path, "default"));
     }
+
+    // 2. AppImage
+    if let Some(home) = &home {
+        let dirs = [home.join("Applications"), home.join(".local").join("bin"), home.join("Downloads")];
+        for dir in dirs {
+            if let Some(image) = linux_find_appimage(&dir) {
+                return Some(KiroInstall::new(image, "appimage"));
+            }
+        }
+    }
+
+    // 3. .desktop 文件的 Exec 行（Flatpak、Snap 等）
+    let mut app_dirs = Vec::new();
+    if let Some(home) = &home {
+        app_dirs.push(home.join(".local").join("share").join("applications"));
+        app_dirs.push(home.join(".local").join("share").join("flatpak").join("exports").join("share").join("applications"));
+    }
+    app_dirs.push(PathBuf::from("/usr/share/applications"));
+    app_dirs.push(PathBuf::from("/usr/local/share/applications"));
+    app_dirs.push(PathBuf::from("/var/lib/flatpak/exports/share/applications"));
+    app_dirs.push(PathBuf::from("/var/lib/snapd/desktop/applications"));
+    for dir in app_dirs {
+        let Ok(entries) = std::fs::read_dir(&dir) else { continue };
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_lowercase();
+            // 跳过本应用自己的 .desktop（kiro-account-manager）
+            if name.ends_with(".desktop") && name.contains("kiro") && !name.contains("account") {
+                if let Some(install) = linux_install_from_desktop(&entry.path(), "desktop") {
+                    return Some(install);
+                }
+            }
+        }
+    }
+
+    // 4. PATH 中的 kiro 命令
+    std::env::var_os("PATH")
+        .and_then(|paths| std::env::split_paths(&paths).map(|p| p.join("kiro")).find(|p| p.is_file()))
+        .map(|p| KiroInstall::new(p, "path"))
 }
 
 #[cfg(not(any(target_os = "windows", target_os = "macos")))]
-pub fn check_kiro_running() -> bool {
-    false
+fn linux_find_appimage(dir: &Path) -> Option<PathBuf> {
+    let mut images: Vec<PathBuf> = std::fs::read_dir(dir).ok()?
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| {
+            let name = p.file_name().map(|n| n.to_string_lossy().to_lowercase()).unwrap_or_default();
+            name.starts_with("kiro") && name.ends_with(".appimage") && p.is_file()
+        })
+        .collect();
+    // 有多个版本时取文件名最大的（通常是最新版本）
+    images.sort();
+    images.pop()
+}
+
+/// 解析 .desktop 文件 [Desktop Entry] 中的 Exec 行
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn linux_install_from_desktop(desktop: &Path, source: &'static str) -> Option<KiroInstall> {
+    let content = std::fs::read_to_string(desktop).ok()?;
+    let mut in_entry = false;
+    let exec = content.lines().map(str::trim).find_map(|line| {
+        if line.starts_with('[') {
+            in_entry = line == "[Desktop Entry]";
+            return None;
+        }
+        if in_entry { line.strip_prefix("Exec=") } else { None }
+    })?;
+
+    // 去掉 %U、%F 等字段代码
+    let command: Vec<String> = split_exec(exec).into_iter()
+        .filter(|arg| !(arg.len() == 2 && arg.starts_with('%')))
+        .collect();
+    let program = PathBuf::from(command.first()?);
+    let path = if program.is_absolute() {
+        program
+    } else {
+        std::env::var_os("PATH")
+            .and_then(|paths| std::env::split_paths(&paths).map(|p| p.join(&program)).find(|p| p.is_file()))
+            .unwrap_or(program)
+    };
+    Some(KiroInstall { path, command, source })
+}
+
+/// 按 Desktop Entry 规范拆分 Exec 行（支持双引号和反斜杠转义）
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn split_exec(exec: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut has_arg = false;
+    let mut chars = exec.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => { in_quotes = !in_quotes; has_arg = true; }
+            '\\' if in_quotes => { if let Some(next) = chars.next() { current.push(next); } }
+            c if c.is_whitespace() && !in_quotes => {
+                if has_arg {
+                    args.push(std::mem::take(&mut current));
+                    has_arg = false;
+                }
+            }
+            c => { current.push(c); has_arg = true; }
+        }
+    }
+    if has_arg {
+        args.push(current);
+    }
+    args
 }
 
-/// 关闭 Kiro IDE（内部函数）
+// ===== 关闭 / 启动 =====
+
+/// 关闭 Kiro IDE 的方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ShutdownStage {
+    /// 本来就没有运行
+    NotRunning,
+    /// 正常关闭（SIGTERM / WM_CLOSE）后在超时时间内退出
+    Graceful,
+    /// 超时后强制结束
+    Forced,
+}
+
+/// 强制结束后等待进程消失的时间
+const FORCE_KILL_WAIT: std::time::Duration = std::time::Duration::from_secs(3);
+const EXIT_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(200);
+
+/// 在 timeout 内轮询等待 Kiro IDE 退出
+fn wait_for_exit(timeout: std::time::Duration) -> bool {
+    let start = std::time::Instant::now();
+    loop {
+        if !check_kiro_running() {
+            return true;
+        }
+        if start.elapsed() >= timeout {
+            return false;
+        }
+        std::thread::sleep(EXIT_POLL_INTERVAL);
+    }
+}
+
+/// 请求 Kiro IDE 正常关闭（不带 /F 的 taskkill 会发送 WM_CLOSE）
 #[cfg(target_os = "windows")]
-pub fn kill_kiro() -> Result<(), String> {
+fn request_close() -> Result<(), String> {
+    Command::new("taskkill")
+        .args(["/IM", "Kiro.exe"])
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()
+        .map_err(|e| format!("Failed to execute taskkill: {}", e))?;
+    // 后台子进程没有窗口，taskkill 会对它们报错，是否成功以进程是否退出为准
+    Ok(())
+}
+
+#[cfg(target_os = "windows")]
+fn force_kill() -> Result<(), String> {
     let output = Command::new("taskkill")
-        .args(["/IM", "Kiro.exe", "/F"])
+        .args(["/IM", "Kiro.exe", "/F", "/T"])
         .creation_flags(CREATE_NO_WINDOW)
         .output()
         .map_err(|e| format!("Failed to execute taskkill: {}", e))?;
-    
+
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
         if !stderr.contains("not found") && !stderr.contains("没有找到") {
@@ -70,68 +388,204 @@ pub fn kill_kiro() -> Result<(), String> {
     Ok(())
 }
 
+/// 主进程（父进程不是 Kiro 的进程），SIGTERM 只发给主进程，由它关闭子进程
 #[cfg(target_os = "macos")]
-pub fn kill_kiro() -> Result<(), String> {
-    let output = Command::new("pkill")
+fn main_pids() -> Vec<u32> {
+    // pgrep -x Kiro 只匹配主进程，Kiro Helper 等子进程名称不同
+    Command::new("pgrep")
         .args(["-x", "Kiro"])
         .output()
-        .map_err(|e| format!("Failed to execute pkill: {}", e))?;
-    
-    if !output.status.success() {
+        .map(|out| {
+            String::from_utf8_lossy(&out.stdout)
+                .lines()
+                .filter_map(|l| l.trim().parse().ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn main_pids() -> Vec<u32> {
+    let pids = find_kiro_pids();
+    pids.iter()
+        .copied()
+        .filter(|pid| {
+            // /proc/<pid>/stat: pid (comm) state ppid ...，comm 可能含空格，从最后一个 ')' 之后解析
+            let ppid = std::fs::read_to_string(format!("/proc/{}/stat", pid))
+                .ok()
+                .and_then(|stat| {
+                    let rest = &stat[stat.rfind(')')? + 1..];
+                    rest.split_whitespace().nth(1)?.parse::<u32>().ok()
+                });
+            ppid.is_none_or(|ppid| !pids.contains(&ppid))
+        })
+        .collect()
+}
+
+#[cfg(not(target_os = "windows"))]
+fn send_signal(signal: &str, pids: &[u32]) -> Result<(), String> {
+    if pids.is_empty() {
+        return Ok(());
+    }
+    let output = Command::new("kill")
+        .arg(signal)
+        .args(pids.iter().map(|p| p.to_string()))
+        .output()
+        .map_err(|e| format!("Failed to execute kill: {}", e))?;
+
+    // 部分进程可能已经退出，只要没有残留就算成功
+    if !output.status.success() && check_kiro_running() {
         let stderr = String::from_utf8_lossy(&output.stderr);
-        if !stderr.contains("No matching") {
-            return Err(format!("Failed to close Kiro IDE: {}", stderr));
-        }
+        return Err(format!("Failed to close Kiro IDE: {}", stderr));
     }
     Ok(())
 }
 
-#[cfg(not(any(target_os = "windows", target_os = "macos")))]
-pub fn kill_kiro() -> Result<(), String> {
-    Err("Unsupported platform".to_string())
+#[cfg(not(target_os = "windows"))]
+fn request_close() -> Result<(), String> {
+    let mut pids = main_pids();
+    if pids.is_empty() {
+        pids = find_kiro_pids();
+    }
+    send_signal("-TERM", &pids)
+}
+
+#[cfg(not(target_os = "windows"))]
+fn force_kill() -> Result<(), String> {
+    send_signal("-KILL", &find_kiro_pids())
+}
+
+/// 关闭 Kiro IDE（内部函数）：先正常关闭，超时后强制结束
+pub fn kill_kiro() -> Result<ShutdownStage, String> {
+    if !check_kiro_running() {
+        return Ok(ShutdownStage::NotRunning);
+    }
+
+    let timeout = get_kiro_shutdown_timeout();
+    match request_close() {
+        Ok(()) => {
+            if wait_for_exit(timeout) {
+                println!("[Process] Kiro IDE 已正常关闭");
+                return Ok(ShutdownStage::Graceful);
+            }
+            println!("[Process] Kiro IDE 在 {:?} 内未退出，强制结束", timeout);
+        }
+        Err(e) => println!("[Process] 正常关闭失败: {}，强制结束", e),
+    }
+
+    force_kill()?;
+    if !wait_for_exit(FORCE_KILL_WAIT) {
+        return Err("Failed to close Kiro IDE: process still running after force kill".to_string());
+    }
+    Ok(ShutdownStage::Forced)
 }
 
 /// 启动 Kiro IDE（内部函数）
 #[cfg(target_os = "windows")]
-pub fn launch_kiro() -> Result<(), String> {
-    let localappdata = std::env::var("LOCALAPPDATA")
-        .map_err(|_| "Cannot find LOCALAPPDATA")?;
-    
-    let kiro_path = std::path::Path::new(&localappdata)
-        .join("Programs")
-        .join("Kiro")
-        .join("Kiro.exe");
-    
-    if !kiro_path.exists() {
-        return Err(format!("Kiro IDE not found at: {}", kiro_path.display()));
-    }
-    
-    Command::new(&kiro_path)
+pub fn launch_kiro() -> Result<KiroLaunchInfo, String> {
+    let install = find_kiro_install()
+        .ok_or("Kiro IDE not found, please set the IDE path in settings")?;
+
+    if !install.path.exists() {
+        return Err(format!("Kiro IDE not found at: {}", install.path.display()));
+    }
+
+    let child = Command::new(&install.path)
         .spawn()
         .map_err(|e| format!("Failed to start Kiro IDE: {}", e))?;
-    
-    Ok(())
+
+    Ok(KiroLaunchInfo {
+        pid: Some(child.id()),
+        path: install.path.to_string_lossy().to_string(),
+        version: read_version(&install.path),
+    })
 }
 
 #[cfg(target_os = "macos")]
-pub fn launch_kiro() -> Result<(), String> {
-    let kiro_path = "/Applications/Kiro.app";
-    
-    if !std::path::Path::new(kiro_path).exists() {
-        return Err(format!("Kiro IDE not found at: {}", kiro_path));
+pub fn launch_kiro() -> Result<KiroLaunchInfo, String> {
+    let install = find_kiro_install()
+        .ok_or("Kiro IDE not found, please set the IDE path in settings")?;
+
+    if !install.path.exists() {
+        return Err(format!("Kiro IDE not found at: {}", install.path.display()));
     }
-    
+
     Command::new("open")
-        .args(["-a", "Kiro"])
+        .arg("-a")
+        .arg(&install.path)
         .spawn()
         .map_err(|e| format!("Failed to start Kiro IDE: {}", e))?;
-    
-    Ok(())
+
+    // open 立即返回，稍等片刻再查找 Kiro 进程
+    let mut pid = None;
+    for _ in 0..10 {
+        std::thread::sleep(std::time::Duration::from_millis(300));
+        pid = find_kiro_pids().first().copied();
+        if pid.is_some() {
+            break;
+        }
+    }
+
+    Ok(KiroLaunchInfo {
+        pid,
+        path: install.path.to_string_lossy().to_string(),
+        version: read_version(&install.path),
+    })
 }
 
 #[cfg(not(any(target_os = "windows", target_os = "macos")))]
-pub fn launch_kiro() -> Result<(), String> {
-    Err("Unsupported platform".to_string())
+pub fn launch_kiro() -> Result<KiroLaunchInfo, String> {
+    let install = find_kiro_install()
+        .ok_or("Kiro IDE not found, please set the IDE path in settings")?;
+
+    if install.source != "desktop" && !install.path.is_file() {
+        return Err(format!("Kiro IDE not found at: {}", install.path.display()));
+    }
+
+    let (program, args) = install.command.split_first()
+        .ok_or("Invalid Kiro IDE launch command")?;
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn()
+        .map_err(|e| format!("Failed to start Kiro IDE: {}", e))?;
+    println!("[Process] 已启动 Kiro IDE ({}): {:?}", install.source, install.command);
+
+    // 在后台线程等待子进程退出并回收，避免 IDE 关闭后留下僵尸进程
+    let pid = child.id();
+    std::thread::spawn(move || {
+        let _ = child.wait();
+    });
+
+    Ok(KiroLaunchInfo {
+        pid: Some(pid),
+        path: install.path.to_string_lossy().to_string(),
+        version: read_version(&install.path),
+    })
+}
+
+/// 运行状态和安装信息
+fn kiro_ide_info() -> KiroIdeInfo {
+    let pids = find_kiro_pids();
+    let install = find_kiro_install();
+
+    // Linux 上优先读取正在运行的进程的版本（AppImage 挂载目录中才有 package.json）
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    let running_version = pids.first()
+        .and_then(|pid| linux_process_exe(*pid))
+        .and_then(|exe| read_version(&exe));
+    #[cfg(any(target_os = "windows", target_os = "macos"))]
+    let running_version: Option<String> = None;
+
+    KiroIdeInfo {
+        running: !pids.is_empty(),
+        version: running_version.or_else(|| install.as_ref().and_then(|i| read_version(&i.path))),
+        path: install.as_ref().map(|i| i.path.to_string_lossy().to_string()),
+        source: install.map(|i| i.source.to_string()),
+        pids,
+    }
 }
 
 // ===== Tauri Commands (异步，避免阻塞主线程) =====
@@ -144,21 +598,25 @@ pub async fn is_kiro_ide_running() -> bool {
         .unwrap_or(false)
 }
 
-/// 关闭 Kiro IDE 进程
+/// 获取 Kiro IDE 运行状态、安装位置和版本
 #[tauri::command]
-pub async fn close_kiro_ide() -> Result<bool, String> {
-    tokio::task::spawn_blocking(|| {
-        let was_running = check_kiro_running();
-        kill_kiro()?;
-        Ok(was_running)
-    })
-    .await
-    .map_err(|e| format!("Task failed: {}", e))?
+pub async fn get_kiro_ide_info() -> KiroIdeInfo {
+    tokio::task::spawn_blocking(kiro_ide_info)
+        .await
+        .unwrap_or_default()
+}
+
+/// 关闭 Kiro IDE 进程，返回结束进程的阶段
+#[tauri::command]
+pub async fn close_kiro_ide() -> Result<ShutdownStage, String> {
+    tokio::task::spawn_blocking(kill_kiro)
+        .await
+        .map_err(|e| format!("Task failed: {}", e))?
 }
 
 /// 启动 Kiro IDE
 #[tauri::command]
-pub async fn start_kiro_ide() -> Result<(), String> {
+pub async fn start_kiro_ide() -> Result<KiroLaunchInfo, String> {
     tokio::task::spawn_blocking(launch_kiro)
         .await
         .map_err(|e| format!("Task failed: {}", e))?
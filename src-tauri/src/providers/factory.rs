@@ -38,15 +38,47 @@ pub fn get_provider_config(provider: &str) -> Option<ProviderConfig> {
             provider_id: "BuilderId".to_string(),
             auth_method: AuthMethod::Idc,
             region: "us-east-1".to_string(),
-            start_url: Some("https://view.awsapps.com/start".to_string()),
+            start_url: Some(BUILDER_ID_START_URL.to_string()),
         }),
         _ => None,
     }
 }
 
+/// Builder ID 的 start URL
+pub const BUILDER_ID_START_URL: &str = "https://view.awsapps.com/start";
+
+/// 企业 IAM Identity Center 配置（组织的 start URL + SSO 区域）
+pub fn get_enterprise_config(start_url: &str, region: Option<&str>) -> Result<ProviderConfig, String> {
+    let start_url = normalize_start_url(start_url)?;
+    let region = region.map(|r| r.trim()).filter(|r| !r.is_empty()).unwrap_or("us-east-1");
+    if !region.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+        return Err(format!("无效的区域: {}", region));
+    }
+    Ok(ProviderConfig {
+        provider_id: "Enterprise".to_string(),
+        auth_method: AuthMethod::Idc,
+        region: region.to_string(),
+        start_url: Some(start_url),
+    })
+}
+
+/// 规范化 start URL（去掉首尾空白和末尾的 /），要求 https
+pub fn normalize_start_url(start_url: &str) -> Result<String, String> {
+    let url = start_url.trim().trim_end_matches('/');
+    if !url.starts_with("https://") || url.len() <= "https://".len() {
+        return Err(format!("无效的 Start URL: {}", start_url));
+    }
+    Ok(url.to_string())
+}
+
+/// 是否是 IdC 账号（BuilderId / Enterprise）
+pub fn is_idc_provider(provider: &str) -> bool {
+    provider == "BuilderId" || provider == "Enterprise"
+}
+
 /// 获取支持的 providers
 pub fn get_supported_providers() -> Vec<&'static str> {
-    vec!["Google", "Github", "BuilderId"]
+    vec!["Google", "Github", "BuilderId", "Enterprise"]
 }
 
 /// 创建 Social Provider
@@ -4,11 +4,12 @@
 use crate::aws_sso_client::{AWSSSOClient, DevicePollResult};
 use crate::browser::open_browser;
 use sha2::{Digest, Sha256};
-use super::{AuthResult, AuthProvider, RefreshMetadata};
+use super::{AuthResult, AuthProvider, RefreshMetadata, BUILDER_ID_START_URL};
 use async_trait::async_trait;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Duration;
+use tokio::sync::Notify;
 
-const BUILDER_ID_START_URL: &str = "https://view.awsapps.com/start";
 
 pub struct IdcProvider {
     provider_id: String,
@@ -30,8 +31,8 @@ impl IdcProvider {
         self.start_url.as_deref().unwrap_or(BUILDER_ID_START_URL)
     }
 
-    /// 计算 clientIdHash
-    fn compute_client_id_hash(start_url: &str) -> String {
+    /// 计算 clientIdHash（IDE 缓存中 {clientIdHash}.json 的文件名）
+    pub fn compute_client_id_hash(start_url: &str) -> String {
         let mut hasher = Sha256::new();
         hasher.update(start_url.as_bytes());
         let hash = hasher.finalize();
@@ -39,9 +40,48 @@ impl IdcProvider {
     }
 }
 
-#[async_trait]
-impl AuthProvider for IdcProvider {
-    async fn login(&self) -> Result<AuthResult, String> {
+/// 已发起的设备授权（注册的客户端 + 设备码）
+#[derive(Debug, Clone)]
+pub struct DeviceFlow {
+    pub client_id: String,
+    pub client_secret: String,
+    pub device_code: String,
+    pub user_code: String,
+    pub verification_uri: String,
+    pub verification_uri_complete: Option<String>,
+    pub expires_in: i64,
+    pub interval: u64,
+}
+
+/// 轮询过程中的状态变化
+#[derive(Debug, Clone, Copy)]
+pub enum DevicePollEvent {
+    Pending { interval: u64 },
+    SlowDown { interval: u64 },
+}
+
+/// 设备授权的取消信号
+#[derive(Default)]
+pub struct DeviceFlowCancel {
+    cancelled: AtomicBool,
+    notify: Notify,
+}
+
+impl DeviceFlowCancel {
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+        // notify_one 在没有等待者时保留通知，轮询进入等待前取消也能立即唤醒
+        self.notify.notify_one();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+}
+
+impl IdcProvider {
+    /// Step 1-3: 注册客户端并发起设备授权
+    pub async fn start_device_flow(&self) -> Result<DeviceFlow, String> {
         let provider = &self.provider_id;
         let region = &self.region;
         let start_url = self.get_start_url();
@@ -69,41 +109,72 @@ impl AuthProvider for IdcProvider {
         println!("[IdC] User Code: {}", device_auth.user_code);
         println!("[IdC] Verification URI: {}", device_auth.verification_uri);
 
-        // Step 4: 打开浏览器让用户输入 user_code
-        let verification_url = device_auth.verification_uri_complete
-            .as_ref()
-            .unwrap_or(&device_auth.verification_uri);
-        println!("[IdC] Opening browser: {}", verification_url);
-        open_browser(verification_url)?;
+        Ok(DeviceFlow {
+            client_id: client_reg.client_id,
+            client_secret: client_reg.client_secret,
+            device_code: device_auth.device_code,
+            user_code: device_auth.user_code,
+            verification_uri: device_auth.verification_uri,
+            verification_uri_complete: device_auth.verification_uri_complete,
+            expires_in: device_auth.expires_in,
+            interval: device_auth.interval.unwrap_or(5).max(1) as u64,
+        })
+    }
+
+    /// Step 5-6: 轮询等待用户授权，可通过 cancel 中止
+    pub async fn poll_device_flow(
+        &self,
+        flow: &DeviceFlow,
+        cancel: &DeviceFlowCancel,
+        on_event: impl Fn(DevicePollEvent),
+    ) -> Result<AuthResult, String> {
+        let provider = &self.provider_id;
+        let region = &self.region;
+        let sso_client = AWSSSOClient::new(region);
 
-        // Step 5: 轮询等待用户授权
         println!("[IdC] Waiting for user authorization...");
-        let mut interval = device_auth.interval.unwrap_or(5) as u64;
-        let timeout = std::time::Instant::now() + Duration::from_secs(device_auth.expires_in as u64);
+        let mut interval = flow.interval;
+        let timeout = std::time::Instant::now() + Duration::from_secs(flow.expires_in.max(0) as u64);
 
         let token_response = loop {
             if std::time::Instant::now() > timeout {
                 return Err("设备授权超时，请重试".to_string());
             }
+            if cancel.is_cancelled() {
+                return Err("登录已取消".to_string());
+            }
 
-            tokio::time::sleep(Duration::from_secs(interval)).await;
+            tokio::select! {
+                _ = tokio::time::sleep(Duration::from_secs(interval)) => {}
+                _ = cancel.notify.notified() => {}
+            }
+            if cancel.is_cancelled() {
+                return Err("登录已取消".to_string());
+            }
 
-            match sso_client.poll_device_token(
-                &client_reg.client_id,
-                &client_reg.client_secret,
-                &device_auth.device_code,
-            ).await? {
+            let poll = sso_client.poll_device_token(
+                &flow.client_id,
+                &flow.client_secret,
+                &flow.device_code,
+            ).await;
+            // 请求期间被取消时丢弃结果
+            if cancel.is_cancelled() {
+                return Err("登录已取消".to_string());
+            }
+            match poll? {
                 DevicePollResult::Success(token) => {
                     println!("[IdC] Authorization successful!");
                     break token;
                 }
                 DevicePollResult::Pending => {
                     // 继续轮询
+                    on_event(DevicePollEvent::Pending { interval });
                     continue;
                 }
                 DevicePollResult::SlowDown => {
                     // 增加轮询间隔
                     interval += 5;
+                    on_event(DevicePollEvent::SlowDown { interval });
                     continue;
                 }
                 DevicePollResult::Expired => {
@@ -117,7 +188,7 @@ impl AuthProvider for IdcProvider {
 
         // Step 6: 构建 AuthResult
         let expires_at = chrono::Local::now() + chrono::Duration::seconds(token_response.expires_in);
-        let client_id_hash = Self::compute_client_id_hash(start_url);
+        let client_id_hash = Self::compute_client_id_hash(self.get_start_url());
 
         println!("[IdC] {} login successful! {}", provider, serde_json::to_string_pretty(&serde_json::json!({
             "expiresIn": token_response.expires_in,
@@ -136,8 +207,8 @@ impl AuthProvider for IdcProvider {
             token_type: token_response.token_type,
             expires_in: token_response.expires_in,
             region: Some(region.clone()),
-            client_id: Some(client_reg.client_id),
-            client_secret: Some(client_reg.client_secret),
+            client_id: Some(flow.client_id.clone()),
+            client_secret: Some(flow.client_secret.clone()),
             client_id_hash: Some(client_id_hash),
             sso_session_id: token_response.aws_sso_app_session_id,
             profile_arn: None,
@@ -145,6 +216,22 @@ impl AuthProvider for IdcProvider {
             session_token: None,
         })
     }
+}
+
+#[async_trait]
+impl AuthProvider for IdcProvider {
+    async fn login(&self) -> Result<AuthResult, String> {
+        let flow = self.start_device_flow().await?;
+
+        // Step 4: 打开浏览器让用户输入 user_code
+        let verification_url = flow.verification_uri_complete
+            .as_ref()
+            .unwrap_or(&flow.verification_uri);
+        println!("[IdC] Opening browser: {}", verification_url);
+        open_browser(verification_url)?;
+
+        self.poll_device_flow(&flow, &DeviceFlowCancel::default(), |_| {}).await
+    }
 
     async fn refresh_token(&self, refresh_token: &str, metadata: RefreshMetadata) -> Result<AuthResult, String> {
         // IdC 刷新需要 client_id 和 client_secret
@@ -9,6 +9,6 @@ pub mod web_oauth;
 
 pub use base::{AuthResult, AuthProvider, RefreshMetadata};
 pub use social::SocialProvider;
-pub use idc::IdcProvider;
+pub use idc::{DeviceFlowCancel, DevicePollEvent, IdcProvider};
 pub use factory::*;
 // WebOAuthProvider 通过 web_oauth 模块访问
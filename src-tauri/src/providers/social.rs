@@ -2,7 +2,9 @@
 // 参考 kiro-batch-login/src/providers/social-provider.js
 
 use crate::kiro_auth_client::KiroAuthServiceClient;
-use crate::deep_link_handler::{DeepLinkCallbackWaiter, register_waiter};
+use crate::commands::app_settings_cmd::use_loopback_callback;
+use crate::deep_link_handler::{register_waiter, CallbackWaiter};
+use crate::loopback_server::LoopbackCallbackWaiter;
 use crate::auth_social;
 use super::{AuthResult, AuthProvider, RefreshMetadata};
 use serde::Deserialize;
@@ -59,27 +61,35 @@ impl AuthProvider for SocialProvider {
     async fn login(&self) -> Result<AuthResult, String> {
         let provider = &self.provider_id;
 
-        // Step 1: 使用 deep link 作为回调 URI
-        let redirect_uri = DeepLinkCallbackWaiter::get_redirect_uri();
-
-        // Step 2: 生成 PKCE 参数
+        // Step 1: 生成 PKCE 参数
         let state = uuid::Uuid::new_v4().to_string();
         let code_verifier = auth_social::generate_code_verifier_social();
         let code_challenge = auth_social::generate_code_challenge_social(&code_verifier);
 
+        // Step 2: 注册回调等待器（默认 kiro:// deep link，可在设置中改为本地回环监听）
+        let waiter: Box<dyn CallbackWaiter> = if use_loopback_callback() {
+            match LoopbackCallbackWaiter::bind(&state) {
+                Ok(w) => Box::new(w),
+                Err(e) => {
+                    println!("[Social] {}，改用 deep link 回调", e);
+                    Box::new(register_waiter(&state, provider))
+                }
+            }
+        } else {
+            Box::new(register_waiter(&state, provider))
+        };
+        let redirect_uri = waiter.redirect_uri();
+
         println!("\n[Social] Starting {} authentication...", provider);
         println!("Redirect URI: {}", redirect_uri);
         println!("State: {}", state);
 
-        // Step 3: 注册回调等待器
-        let waiter = register_waiter(&state);
-
-        // Step 4: 打开浏览器登录
+        // Step 3: 打开浏览器登录
         let client = KiroAuthServiceClient::new();
         client.login(provider, &redirect_uri, &code_challenge, &state).await?;
 
-        // Step 5: 等待 deep link 回调
-        println!("[Social] Waiting for deep link callback...");
+        // Step 4: 等待回调
+        println!("[Social] Waiting for OAuth callback...");
         let callback = tokio::task::spawn_blocking(move || waiter.wait_for_callback())
             .await
             .map_err(|e| format!("Failed to join callback waiter: {}", e))?
@@ -87,13 +97,13 @@ impl AuthProvider for SocialProvider {
         
         println!("[Social] Callback received, state: {}", callback.state);
 
-        // Step 6: 交换 token
+        // Step 5: 交换 token
         println!("[Social] Exchanging code for tokens...");
         let token_response: SocialTokenResponse = client
             .create_token(&callback.code, &code_verifier, &redirect_uri, None)
             .await?;
 
-        // Step 7: 构建 AuthResult
+        // Step 6: 构建 AuthResult
         let expires_at = chrono::Local::now() + chrono::Duration::seconds(token_response.expires_in);
 
         println!("[Social] {} login successful! {}", provider, serde_json::to_string_pretty(&serde_json::json!({
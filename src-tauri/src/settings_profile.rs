@@ -0,0 +1,252 @@
+// Kiro IDE 设置方案 - 一组 settings.json 键值，可与当前设置对比、应用和撤销
+// 方案和应用记录保存在 <应用数据目录>/settings-profiles.json
+
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::account::get_app_data_dir;
+use crate::commands::kiro_settings_cmd::{edit_kiro_settings, load_kiro_settings};
+use crate::jsonc::JsoncDocument;
+
+/// 最多保留的应用记录数量
+const MAX_APPLIED: usize = 20;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsProfile {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    /// 键值对，值为 null 表示删除该键
+    pub settings: Map<String, Value>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// 新建或更新方案的参数（id 为空时新建）
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsProfileInput {
+    pub id: Option<String>,
+    pub name: String,
+    pub description: Option<String>,
+    pub settings: Map<String, Value>,
+}
+
+/// 一次应用记录，用于撤销
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppliedProfile {
+    pub profile_id: String,
+    pub profile_name: String,
+    pub applied_at: String,
+    /// 应用前的值，原来不存在的键为 null
+    pub previous: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ProfileStore {
+    #[serde(default)]
+    profiles: Vec<SettingsProfile>,
+    /// 应用记录，最新的在最后
+    #[serde(default)]
+    applied: Vec<AppliedProfile>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DiffStatus {
+    Added,
+    Changed,
+    Removed,
+    Unchanged,
+}
+
+/// 方案中一个键与当前设置的差异
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingDiff {
+    pub key: String,
+    pub current: Option<Value>,
+    pub desired: Option<Value>,
+    pub status: DiffStatus,
+}
+
+fn store_path() -> PathBuf {
+    get_app_data_dir().join("settings-profiles.json")
+}
+
+fn now() -> String {
+    chrono::Local::now().format("%Y/%m/%d %H:%M:%S").to_string()
+}
+
+fn load_store() -> Result<ProfileStore, String> {
+    let path = store_path();
+    if !path.exists() {
+        return Ok(ProfileStore::default());
+    }
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| format!("读取设置方案失败: {}", e))?;
+    serde_json::from_str(&content)
+        .map_err(|e| format!("解析设置方案失败: {}", e))
+}
+
+fn save_store(store: &ProfileStore) -> Result<(), String> {
+    let path = store_path();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).ok();
+    }
+    let content = serde_json::to_string_pretty(store)
+        .map_err(|e| format!("序列化设置方案失败: {}", e))?;
+    let temp = path.with_extension("json.tmp");
+    std::fs::write(&temp, content).map_err(|e| format!("写入设置方案失败: {}", e))?;
+    std::fs::rename(&temp, &path).map_err(|e| format!("写入设置方案失败: {}", e))
+}
+
+fn find_profile(store: &ProfileStore, id: &str) -> Result<SettingsProfile, String> {
+    store.profiles.iter()
+        .find(|p| p.id == id)
+        .cloned()
+        .ok_or_else(|| format!("设置方案不存在: {}", id))
+}
+
+/// 对比一组键值与当前设置
+pub fn diff_settings(doc: &JsoncDocument, settings: &Map<String, Value>) -> Vec<SettingDiff> {
+    let current = doc.value();
+    settings.iter()
+        .map(|(key, desired)| {
+            let current = current.get(key).cloned();
+            let desired = (!desired.is_null()).then(|| desired.clone());
+            let status = match (&current, &desired) {
+                (None, None) => DiffStatus::Unchanged,
+                (None, Some(_)) => DiffStatus::Added,
+                (Some(_), None) => DiffStatus::Removed,
+                (Some(c), Some(d)) if c == d => DiffStatus::Unchanged,
+                _ => DiffStatus::Changed,
+            };
+            SettingDiff { key: key.clone(), current, desired, status }
+        })
+        .collect()
+}
+
+/// 把一组键值写入文档（null 表示删除），返回被修改的键原来的值
+pub fn apply_settings(doc: &mut JsoncDocument, settings: &Map<String, Value>) -> Result<Map<String, Value>, String> {
+    let mut previous = Map::new();
+    for diff in diff_settings(doc, settings) {
+        if diff.status == DiffStatus::Unchanged {
+            continue;
+        }
+        match &diff.desired {
+            Some(value) => doc.set(&diff.key, value)?,
+            None => { doc.remove(&diff.key)?; }
+        }
+        previous.insert(diff.key, diff.current.unwrap_or(Value::Null));
+    }
+    Ok(previous)
+}
+
+pub fn get_profile(id: &str) -> Result<SettingsProfile, String> {
+    find_profile(&load_store()?, id)
+}
+
+pub fn list_profiles() -> Result<Vec<SettingsProfile>, String> {
+    Ok(load_store()?.profiles)
+}
+
+pub fn save_profile(input: SettingsProfileInput) -> Result<SettingsProfile, String> {
+    let name = input.name.trim().to_string();
+    if name.is_empty() {
+        return Err("方案名称不能为空".to_string());
+    }
+    if input.settings.keys().any(|k| k.trim().is_empty()) {
+        return Err("设置键不能为空".to_string());
+    }
+
+    let mut store = load_store()?;
+    let profile = match input.id.as_deref().and_then(|id| store.profiles.iter_mut().find(|p| p.id == id)) {
+        Some(existing) => {
+            existing.name = name;
+            existing.description = input.description;
+            existing.settings = input.settings;
+            existing.updated_at = now();
+            existing.clone()
+        }
+        None => {
+            let profile = SettingsProfile {
+                id: uuid::Uuid::new_v4().to_string(),
+                name,
+                description: input.description,
+                settings: input.settings,
+                created_at: now(),
+                updated_at: now(),
+            };
+            store.profiles.push(profile.clone());
+            profile
+        }
+    };
+    save_store(&store)?;
+    Ok(profile)
+}
+
+pub fn delete_profile(id: &str) -> Result<(), String> {
+    let mut store = load_store()?;
+    store.profiles.retain(|p| p.id != id);
+    save_store(&store)
+}
+
+pub fn diff_profile(id: &str) -> Result<Vec<SettingDiff>, String> {
+    let profile = find_profile(&load_store()?, id)?;
+    Ok(diff_settings(&load_kiro_settings()?, &profile.settings))
+}
+
+/// 应用方案，返回实际修改的差异
+pub fn apply_profile(id: &str) -> Result<Vec<SettingDiff>, String> {
+    let mut store = load_store()?;
+    let profile = find_profile(&store, id)?;
+
+    let mut changes = Vec::new();
+    let mut previous = Map::new();
+    edit_kiro_settings(|doc| {
+        changes = diff_settings(doc, &profile.settings);
+        changes.retain(|d| d.status != DiffStatus::Unchanged);
+        previous = apply_settings(doc, &profile.settings)?;
+        Ok(())
+    })?;
+
+    if !previous.is_empty() {
+        store.applied.push(AppliedProfile {
+            profile_id: profile.id.clone(),
+            profile_name: profile.name.clone(),
+            applied_at: now(),
+            previous,
+        });
+        let excess = store.applied.len().saturating_sub(MAX_APPLIED);
+        store.applied.drain(..excess);
+        save_store(&store)?;
+    }
+    println!("[SettingsProfile] 已应用方案 {}，修改 {} 项", profile.name, changes.len());
+    Ok(changes)
+}
+
+/// 撤销最近一次应用，恢复被修改的键
+pub fn revert_last_applied() -> Result<AppliedProfile, String> {
+    let mut store = load_store()?;
+    let applied = store.applied.last().cloned().ok_or("没有可撤销的设置方案")?;
+
+    edit_kiro_settings(|doc| apply_settings(doc, &applied.previous).map(|_| ()))?;
+
+    store.applied.pop();
+    save_store(&store)?;
+    println!("[SettingsProfile] 已撤销方案 {}", applied.profile_name);
+    Ok(applied)
+}
+
+/// 应用记录，最新的在前
+pub fn list_applied() -> Result<Vec<AppliedProfile>, String> {
+    let mut applied = load_store()?.applied;
+    applied.reverse();
+    Ok(applied)
+}
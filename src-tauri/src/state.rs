@@ -5,14 +5,6 @@ use std::sync::Mutex;
 use crate::auth::AuthState;
 use crate::account::AccountStore;
 
-#[derive(Clone)]
-pub struct PendingLogin {
-    pub provider: String,
-    pub code_verifier: String,
-    pub state: String,
-    pub machineid: String,
-}
-
 pub struct Kiro2ApiRuntime {
     pub child: Child,
     pub pid: u32,
@@ -21,6 +13,7 @@ pub struct Kiro2ApiRuntime {
     pub log_path: String,
     pub shared_accounts_file: String,
     pub api_key: String,
+    pub admin_key: String,
 }
 
 impl Drop for Kiro2ApiRuntime {
@@ -35,6 +28,5 @@ impl Drop for Kiro2ApiRuntime {
 pub struct AppState {
     pub store: Mutex<AccountStore>,
     pub auth: AuthState,
-    pub pending_login: Mutex<Option<PendingLogin>>,
     pub kiro2api: Mutex<Option<Kiro2ApiRuntime>>,
 }
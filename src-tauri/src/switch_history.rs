@@ -0,0 +1,220 @@
+// 切换账号历史 - 切换前快照 IDE 的认证缓存文件（重置机器 ID 时还包括 storage.json / state.vscdb），
+// 用于验证失败时回滚和手动撤销
+// 快照保存在 <应用数据目录>/switch-history/<id>/，meta.json 记录元数据
+// 快照文件包含明文凭证，只保留最近几次的文件，更早的快照只保留元数据
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::account::get_app_data_dir;
+
+/// 最多保留的快照记录数量
+const MAX_SNAPSHOTS: usize = 20;
+/// 保留文件（可恢复）的快照数量
+const MAX_SNAPSHOTS_WITH_FILES: usize = 2;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotFile {
+    pub name: String,
+    /// 原文件的完整路径（旧版本快照为空，表示 SSO 缓存目录下的 name）
+    #[serde(default)]
+    pub path: Option<String>,
+    /// 切换前文件是否存在（不存在时回滚需要删除）
+    pub existed: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SwitchSnapshot {
+    pub id: String,
+    pub created_at: String,
+    /// 切换前 IDE 登录的账号
+    pub from_provider: Option<String>,
+    pub from_auth_method: Option<String>,
+    pub to_provider: String,
+    pub to_auth_method: String,
+    pub files: Vec<SnapshotFile>,
+    #[serde(default)]
+    pub reverted: bool,
+    pub reverted_at: Option<String>,
+    /// 自动回滚的原因（手动撤销时为空）
+    pub revert_reason: Option<String>,
+    /// 快照文件已清理，无法恢复
+    #[serde(default)]
+    pub purged: bool,
+}
+
+impl SnapshotFile {
+    fn target(&self, cache_dir: &Path) -> PathBuf {
+        match self.path.as_ref() {
+            Some(path) => PathBuf::from(path),
+            None => cache_dir.join(&self.name),
+        }
+    }
+}
+
+fn history_dir() -> PathBuf {
+    get_app_data_dir().join("switch-history")
+}
+
+fn now() -> String {
+    chrono::Local::now().format("%Y/%m/%d %H:%M:%S").to_string()
+}
+
+fn save_meta(snapshot: &SwitchSnapshot) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(snapshot)
+        .map_err(|e| format!("序列化快照失败: {}", e))?;
+    std::fs::write(history_dir().join(&snapshot.id).join("meta.json"), content)
+        .map_err(|e| format!("写入快照失败: {}", e))
+}
+
+/// 原子复制：先写临时文件再覆盖
+fn copy_atomic(from: &Path, to: &Path) -> Result<(), String> {
+    let mut temp = to.as_os_str().to_owned();
+    temp.push(".tmp");
+    let temp = PathBuf::from(temp);
+    std::fs::copy(from, &temp).map_err(|e| format!("复制 {:?} 失败: {}", from, e))?;
+    std::fs::rename(&temp, to).map_err(|e| format!("覆盖 {:?} 失败: {}", to, e))
+}
+
+/// 快照目录只允许当前用户访问
+#[cfg(unix)]
+fn restrict_permissions(dir: &Path) {
+    use std::os::unix::fs::PermissionsExt;
+    let _ = std::fs::set_permissions(dir, std::fs::Permissions::from_mode(0o700));
+}
+
+#[cfg(not(unix))]
+fn restrict_permissions(_dir: &Path) {}
+
+/// 切换前快照即将被覆盖的文件（各文件名不能重复）
+pub fn create_snapshot(
+    paths: &[PathBuf],
+    to_provider: &str,
+    to_auth_method: &str,
+) -> Result<SwitchSnapshot, String> {
+    let id = format!("{}-{}", chrono::Local::now().format("%Y%m%d%H%M%S"), &uuid::Uuid::new_v4().to_string()[..8]);
+    let dir = history_dir().join(&id);
+    std::fs::create_dir_all(&dir).map_err(|e| format!("创建快照目录失败: {}", e))?;
+    restrict_permissions(&dir);
+
+    let previous = crate::kiro::get_kiro_local_token();
+    let mut files = Vec::new();
+    for path in paths {
+        let name = path.file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .ok_or_else(|| format!("无效的快照路径: {:?}", path))?;
+        let existed = path.exists();
+        if existed {
+            std::fs::copy(path, dir.join(&name)).map_err(|e| format!("快照 {} 失败: {}", name, e))?;
+        }
+        files.push(SnapshotFile { name, path: Some(path.to_string_lossy().to_string()), existed });
+    }
+
+    let snapshot = SwitchSnapshot {
+        id,
+        created_at: now(),
+        from_provider: previous.as_ref().and_then(|t| t.provider.clone()),
+        from_auth_method: previous.as_ref().and_then(|t| t.auth_method.clone()),
+        to_provider: to_provider.to_string(),
+        to_auth_method: to_auth_method.to_string(),
+        files,
+        reverted: false,
+        reverted_at: None,
+        revert_reason: None,
+        purged: false,
+    };
+    save_meta(&snapshot)?;
+    prune();
+    println!("[SwitchHistory] 已创建快照 {}", snapshot.id);
+    Ok(snapshot)
+}
+
+/// 把快照中的文件恢复到缓存目录，reason 为自动回滚的原因
+pub fn restore_snapshot(cache_dir: &Path, id: &str, reason: Option<&str>) -> Result<SwitchSnapshot, String> {
+    let dir = history_dir().join(id);
+    let content = std::fs::read_to_string(dir.join("meta.json"))
+        .map_err(|_| format!("快照不存在: {}", id))?;
+    let mut snapshot: SwitchSnapshot = serde_json::from_str(&content)
+        .map_err(|e| format!("解析快照失败: {}", e))?;
+    if snapshot.purged {
+        return Err("该快照的文件已清理，无法恢复".to_string());
+    }
+
+    for file in &snapshot.files {
+        let target = file.target(cache_dir);
+        if file.existed {
+            copy_atomic(&dir.join(&file.name), &target)?;
+        } else if target.exists() {
+            std::fs::remove_file(&target).map_err(|e| format!("删除 {} 失败: {}", file.name, e))?;
+        }
+    }
+
+    snapshot.reverted = true;
+    snapshot.reverted_at = Some(now());
+    snapshot.revert_reason = reason.map(|r| r.to_string());
+    save_meta(&snapshot)?;
+    println!("[SwitchHistory] 已恢复快照 {}", id);
+    Ok(snapshot)
+}
+
+/// 全部快照，最新的在前
+pub fn list_snapshots() -> Vec<SwitchSnapshot> {
+    let Ok(entries) = std::fs::read_dir(history_dir()) else {
+        return Vec::new();
+    };
+    let mut snapshots: Vec<SwitchSnapshot> = entries
+        .filter_map(|e| e.ok())
+        .filter_map(|e| std::fs::read_to_string(e.path().join("meta.json")).ok())
+        .filter_map(|content| serde_json::from_str(&content).ok())
+        .collect();
+    // id 以时间开头，按 id 倒序即按时间倒序
+    snapshots.sort_by(|a, b| b.id.cmp(&a.id));
+    snapshots
+}
+
+/// 最近一次未撤销的快照
+pub fn last_revertible() -> Option<SwitchSnapshot> {
+    list_snapshots().into_iter().find(|s| !s.reverted)
+}
+
+/// 删除快照中的凭证文件，只保留元数据
+fn purge_files(snapshot: &mut SwitchSnapshot) {
+    let dir = history_dir().join(&snapshot.id);
+    for file in &snapshot.files {
+        let _ = std::fs::remove_file(dir.join(&file.name));
+    }
+    snapshot.purged = true;
+    let _ = save_meta(snapshot);
+}
+
+fn prune() {
+    for (index, mut snapshot) in list_snapshots().into_iter().enumerate() {
+        if index >= MAX_SNAPSHOTS {
+            let _ = std::fs::remove_dir_all(history_dir().join(&snapshot.id));
+        } else if index >= MAX_SNAPSHOTS_WITH_FILES && !snapshot.purged {
+            purge_files(&mut snapshot);
+        }
+    }
+}
+
+/// 删除账号时清理包含这些 RefreshToken 的快照文件
+pub fn purge_for_refresh_tokens(refresh_tokens: &[String]) {
+    if refresh_tokens.is_empty() {
+        return;
+    }
+    for mut snapshot in list_snapshots().into_iter().filter(|s| !s.purged) {
+        let token_file = history_dir().join(&snapshot.id).join("kiro-auth-token.json");
+        let matched = std::fs::read_to_string(token_file)
+            .ok()
+            .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+            .and_then(|v| v.get("refreshToken").and_then(|t| t.as_str()).map(|t| t.to_string()))
+            .is_some_and(|t| refresh_tokens.contains(&t));
+        if matched {
+            println!("[SwitchHistory] 清理快照 {} 中已删除账号的凭证", snapshot.id);
+            purge_files(&mut snapshot);
+        }
+    }
+}
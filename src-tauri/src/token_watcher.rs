@@ -0,0 +1,232 @@
+// Kiro IDE Token 回写
+// 切换账号后 IDE 会自己刷新 ~/.aws/sso/cache/kiro-auth-token.json 并可能轮换 RefreshToken，
+// 这里轮询该文件，检测到 IDE 改写后按身份找到对应账号，把新 Token 写回账号列表
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::account::Account;
+use crate::account_health::AccountHealth;
+use crate::active_account;
+use crate::account_sync::{self, SyncResult};
+use crate::commands::app_settings_cmd::get_ide_token_sync_settings;
+use crate::kiro::{get_sso_cache_dir, KiroLocalToken};
+use crate::state::AppState;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// 写回方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TokenSyncResolution {
+    /// 只有 IDE 一侧变化（RefreshToken 轮换或 AccessToken 刷新），直接写回
+    IdeRotated,
+    /// 两侧都变化，按冲突策略采用 IDE 的 Token
+    ConflictIdeWins,
+    /// 两侧都变化，按冲突策略保留账号列表中的 Token
+    ConflictManagerWins,
+}
+
+/// 事件 kiro-token-synced
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenSyncEvent {
+    pub account_id: String,
+    pub email: String,
+    pub resolution: TokenSyncResolution,
+}
+
+fn token_file_path() -> Option<PathBuf> {
+    Some(get_sso_cache_dir()?.join("kiro-auth-token.json"))
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// 读取 Token 文件（IDE 写入过程中可能读到不完整内容，解析失败时返回 None，下次再读）
+fn read_token(path: &Path) -> Option<KiroLocalToken> {
+    let content = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn file_expires_at(token: &KiroLocalToken) -> Option<chrono::DateTime<chrono::Local>> {
+    token.expires_at.as_deref()
+        .and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok())
+        .map(|dt| dt.with_timezone(&chrono::Local))
+}
+
+/// 启动后台轮询
+pub fn start(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let Some(path) = token_file_path() else {
+            println!("[TokenWatcher] 找不到 SSO 缓存目录，不启动");
+            return;
+        };
+        let mut last_modified = modified_time(&path);
+        let mut last_token = read_token(&path);
+        let mut ticker = tokio::time::interval(POLL_INTERVAL);
+        println!("[TokenWatcher] 开始监听 {:?}", path);
+
+        loop {
+            ticker.tick().await;
+
+            let modified = modified_time(&path);
+            if modified == last_modified {
+                continue;
+            }
+            if modified.is_none() {
+                // 文件被删除（IDE 退出登录）
+                last_modified = None;
+                if last_token.take().is_some() {
+                    emit_active_account(&app).await;
+                }
+                continue;
+            }
+            let Some(current) = read_token(&path) else { continue };
+            last_modified = modified;
+
+            let previous = last_token.replace(current.clone());
+            let unchanged = previous.as_ref().is_some_and(|p| {
+                p.refresh_token == current.refresh_token && p.access_token == current.access_token
+            });
+            if unchanged {
+                continue;
+            }
+
+            let (enabled, policy) = get_ide_token_sync_settings();
+            if enabled {
+                let state = app.state::<AppState>();
+                match sync_from_ide(&state, previous.as_ref(), &current, &policy).await {
+                    Ok(Some(event)) => {
+                        println!("[TokenWatcher] {} 已同步 ({:?})", event.email, event.resolution);
+                        let _ = app.emit("kiro-token-synced", &event);
+                    }
+                    Ok(None) => {}
+                    Err(e) => println!("[TokenWatcher] {}", e),
+                }
+            }
+            emit_active_account(&app).await;
+        }
+    });
+}
+
+/// 通知前端当前 IDE 登录的账号（事件 active-account-changed）
+async fn emit_active_account(app: &AppHandle) {
+    let state = app.state::<AppState>();
+    let active = active_account::resolve(&state).await;
+    let _ = app.emit("active-account-changed", &active);
+}
+
+/// 把 IDE 改写后的 Token 写回对应账号
+///
+/// 冲突：账号列表中的 RefreshToken 既不是文件旧值也不是新值，说明管理器一侧也刷新过。
+/// policy 为 "ide" 时采用文件，"manager" 时保留账号列表，"newest"（默认）比较过期时间取较新者，无法比较时采用文件。
+/// 按 email（忽略大小写）匹配账号：provider 双方都已知时必须一致，且只能有一个候选，
+/// 同一邮箱在多个 provider 下都有账号时不猜测
+fn find_by_email<'a>(accounts: &'a [Account], email: &str, provider: Option<&str>) -> Option<&'a Account> {
+    let mut candidates = accounts.iter().filter(|a| {
+        a.email.eq_ignore_ascii_case(email)
+            && match (a.provider.as_deref(), provider) {
+                (Some(a), Some(b)) => a.eq_ignore_ascii_case(b),
+                _ => true,
+            }
+    });
+    let account = candidates.next()?;
+    candidates.next().is_none().then_some(account)
+}
+
+async fn sync_from_ide(
+    state: &AppState,
+    previous: Option<&KiroLocalToken>,
+    current: &KiroLocalToken,
+    policy: &str,
+) -> Result<Option<TokenSyncEvent>, String> {
+    let (Some(access_token), Some(refresh_token)) = (current.access_token.clone(), current.refresh_token.clone()) else {
+        return Ok(None);
+    };
+    let previous_refresh = previous.and_then(|p| p.refresh_token.clone());
+
+    // 1. 按 RefreshToken 匹配：新值已在账号列表中（本程序写入的）或是旧值（IDE 轮换）
+    let matched = {
+        let store = state.store.lock().unwrap();
+        store.accounts.iter()
+            .find(|a| a.refresh_token.as_deref() == Some(refresh_token.as_str()))
+            .or_else(|| previous_refresh.as_deref().and_then(|old| {
+                store.accounts.iter().find(|a| a.refresh_token.as_deref() == Some(old))
+            }))
+            .map(|a| (a.id.clone(), a.access_token.as_deref() == Some(access_token.as_str())))
+    };
+    if let Some((_, true)) = matched {
+        // 与账号列表一致（通常是切换账号时本程序写入的）
+        return Ok(None);
+    }
+
+    // 2. 按身份匹配：用新 AccessToken 查询用量接口拿到 userId / email
+    let provider = current.provider.clone().unwrap_or_else(|| "Google".to_string());
+    let usage = account_sync::fetch_usage(&provider, &access_token).await;
+    let (account_id, conflict) = match matched {
+        Some((id, _)) => (id, false),
+        None => {
+            let user = usage.summary.as_ref().and_then(|s| s.user.clone())
+                .ok_or_else(|| format!("无法识别 IDE Token 对应的账号: {}", usage.error.clone().unwrap_or_default()))?;
+            let store = state.store.lock().unwrap();
+            let account = store.accounts.iter()
+                .find(|a| user.user_id.is_some() && a.user_id == user.user_id)
+                .or_else(|| user.email.as_deref().and_then(|email| find_by_email(&store.accounts, email, current.provider.as_deref())))
+                .ok_or_else(|| format!("IDE Token 对应的账号不在账号列表中: {}", user.email.unwrap_or_default()))?;
+            (account.id.clone(), true)
+        }
+    };
+
+    let expires_in = file_expires_at(current)
+        .map(|t| (t - chrono::Local::now()).num_seconds().max(0))
+        .unwrap_or(3600);
+
+    // 写回在同一把锁内完成并保存
+    let mut store = state.store.lock().unwrap();
+    let account = store.accounts.iter_mut()
+        .find(|a| a.id == account_id)
+        .ok_or("Account not found")?;
+
+    let resolution = if !conflict {
+        TokenSyncResolution::IdeRotated
+    } else {
+        let ide_wins = match policy {
+            "ide" => true,
+            "manager" => false,
+            _ => match (file_expires_at(current), account.expires_at_time()) {
+                (Some(file), Some(store)) => file >= store,
+                _ => true,
+            },
+        };
+        if ide_wins { TokenSyncResolution::ConflictIdeWins } else { TokenSyncResolution::ConflictManagerWins }
+    };
+    println!("[TokenWatcher] {} 的 Token 被 IDE 更新，{:?}", account.email, resolution);
+
+    if resolution != TokenSyncResolution::ConflictManagerWins {
+        // 用量接口失败（网络等）时不覆盖健康状态
+        let usage = (usage.summary.is_some() || matches!(usage.health, AccountHealth::Suspended { .. }))
+            .then_some(usage);
+        account_sync::apply_sync(account, SyncResult {
+            access_token,
+            refresh_token: Some(refresh_token),
+            expires_in,
+            profile_arn: current.profile_arn.clone(),
+            id_token: None,
+            sso_session_id: None,
+            usage,
+        });
+    }
+
+    let event = TokenSyncEvent {
+        account_id: account.id.clone(),
+        email: account.email.clone(),
+        resolution,
+    };
+    store.save_to_file();
+    Ok(Some(event))
+}
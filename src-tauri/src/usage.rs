@@ -0,0 +1,307 @@
+// 统一用量模型 - 把三个 usage API 的响应归一化为 UsageSummary
+// Desktop API（auth.rs）、CodeWhisperer API（codewhisperer_client.rs）、Web Portal API（providers/web_oauth.rs）
+
+use serde::{Deserialize, Serialize};
+
+use crate::auth::DesktopUsageResponse;
+use crate::codewhisperer_client::CodeWhispererUsageResponse;
+use crate::providers::web_oauth::GetUserUsageAndLimitsResponse;
+
+/// 订阅信息
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubscriptionSummary {
+    pub title: Option<String>,
+    #[serde(rename = "type")]
+    pub subscription_type: Option<String>,
+    pub overage_capability: Option<String>,
+    pub upgrade_capability: Option<String>,
+}
+
+/// usage API 返回的用户信息
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageUser {
+    pub email: Option<String>,
+    pub user_id: Option<String>,
+}
+
+/// 免费试用额度
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FreeTrialUsage {
+    pub status: Option<String>,
+    pub usage_limit: f64,
+    pub current_usage: f64,
+    pub usage_limit_with_precision: Option<f64>,
+    pub current_usage_with_precision: Option<f64>,
+    /// 秒级时间戳
+    pub expiry: Option<f64>,
+}
+
+/// 奖励额度
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BonusUsage {
+    pub code: Option<String>,
+    pub display_name: Option<String>,
+    pub description: Option<String>,
+    pub usage_limit: f64,
+    pub current_usage: f64,
+    /// 秒级时间戳
+    pub expires_at: Option<f64>,
+    pub redeemed_at: Option<f64>,
+    pub status: Option<String>,
+}
+
+/// 超额计费信息
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OverageUsage {
+    pub rate: Option<f64>,
+    pub cap: Option<f64>,
+    pub current_overages: Option<f64>,
+    pub charges: Option<f64>,
+}
+
+/// 单个资源类型的用量
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceUsage {
+    pub resource_type: Option<String>,
+    pub display_name: Option<String>,
+    pub unit: Option<String>,
+    pub currency: Option<String>,
+    pub usage_limit: f64,
+    pub current_usage: f64,
+    pub usage_limit_with_precision: Option<f64>,
+    pub current_usage_with_precision: Option<f64>,
+    /// 秒级时间戳
+    pub next_date_reset: Option<f64>,
+    pub free_trial: Option<FreeTrialUsage>,
+    #[serde(default)]
+    pub bonuses: Vec<BonusUsage>,
+    pub overage: Option<OverageUsage>,
+}
+
+impl ResourceUsage {
+    /// 主额度 + 试用 + 奖励
+    pub fn total_limit(&self) -> f64 {
+        self.usage_limit_with_precision.unwrap_or(self.usage_limit)
+            + self.free_trial.as_ref()
+                .map(|t| t.usage_limit_with_precision.unwrap_or(t.usage_limit))
+                .unwrap_or(0.0)
+            + self.bonuses.iter().map(|b| b.usage_limit).sum::<f64>()
+    }
+
+    /// 主额度 + 试用 + 奖励的已用量
+    pub fn total_used(&self) -> f64 {
+        self.current_usage_with_precision.unwrap_or(self.current_usage)
+            + self.free_trial.as_ref()
+                .map(|t| t.current_usage_with_precision.unwrap_or(t.current_usage))
+                .unwrap_or(0.0)
+            + self.bonuses.iter().map(|b| b.current_usage).sum::<f64>()
+    }
+}
+
+/// 归一化后的账号用量
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageSummary {
+    pub subscription: Option<SubscriptionSummary>,
+    pub user: Option<UsageUser>,
+    pub overage_status: Option<String>,
+    pub days_until_reset: Option<i32>,
+    /// 秒级时间戳
+    pub next_date_reset: Option<f64>,
+    #[serde(default)]
+    pub resources: Vec<ResourceUsage>,
+}
+
+impl UsageSummary {
+    /// 主资源（第一条 breakdown，即界面上展示的额度）
+    pub fn primary(&self) -> Option<&ResourceUsage> {
+        self.resources.first()
+    }
+
+    /// 主资源剩余额度
+    pub fn remaining(&self) -> Option<f64> {
+        self.primary().map(|r| r.total_limit() - r.total_used())
+    }
+
+    /// 从已保存的原始响应恢复（兼容旧版本只存了 usage_data 的账号）
+    pub fn from_raw(raw: &serde_json::Value) -> Option<Self> {
+        if !raw.is_object() {
+            return None;
+        }
+        // CodeWhisperer 响应字段是另外两种的超集
+        let parsed: CodeWhispererUsageResponse = serde_json::from_value(raw.clone()).ok()?;
+        let mut summary = UsageSummary::from(&parsed);
+        // Web Portal 的订阅类型字段名是 subscriptionType
+        if let Some(sub) = summary.subscription.as_mut() {
+            if sub.subscription_type.is_none() {
+                sub.subscription_type = raw.pointer("/subscriptionInfo/subscriptionType")
+                    .and_then(|v| v.as_str())
+                    .map(|s| s.to_string());
+            }
+        }
+        Some(summary)
+    }
+}
+
+fn to_f64(value: Option<i32>) -> f64 {
+    value.map(|v| v as f64).unwrap_or(0.0)
+}
+
+impl From<&DesktopUsageResponse> for UsageSummary {
+    fn from(resp: &DesktopUsageResponse) -> Self {
+        Self {
+            subscription: resp.subscription_info.as_ref().map(|s| SubscriptionSummary {
+                title: s.subscription_title.clone(),
+                subscription_type: s.subscription_type.clone(),
+                overage_capability: s.overage_capability.clone(),
+                upgrade_capability: s.upgrade_capability.clone(),
+            }),
+            user: resp.user_info.as_ref().map(|u| UsageUser {
+                email: u.email.clone(),
+                user_id: u.user_id.clone(),
+            }),
+            overage_status: None,
+            days_until_reset: resp.days_until_reset,
+            next_date_reset: resp.next_date_reset,
+            resources: resp.usage_breakdown_list.iter().flatten().map(|b| ResourceUsage {
+                currency: b.currency.clone(),
+                usage_limit: to_f64(b.usage_limit),
+                current_usage: to_f64(b.current_usage),
+                next_date_reset: b.next_date_reset.or(resp.next_date_reset),
+                free_trial: b.free_trial_info.as_ref().map(|t| FreeTrialUsage {
+                    status: t.free_trial_status.clone(),
+                    usage_limit: to_f64(t.usage_limit),
+                    current_usage: to_f64(t.current_usage),
+                    expiry: t.free_trial_expiry,
+                    ..Default::default()
+                }),
+                bonuses: b.bonuses.iter().flatten().map(|bonus| BonusUsage {
+                    code: bonus.bonus_code.clone(),
+                    display_name: bonus.display_name.clone(),
+                    description: bonus.description.clone(),
+                    usage_limit: bonus.usage_limit.unwrap_or(0.0),
+                    current_usage: bonus.current_usage.unwrap_or(0.0),
+                    expires_at: bonus.expires_at,
+                    redeemed_at: bonus.redeemed_at,
+                    status: bonus.status.clone(),
+                }).collect(),
+                overage: (b.overage_rate.is_some() || b.overage_cap.is_some()).then(|| OverageUsage {
+                    rate: b.overage_rate,
+                    cap: b.overage_cap.map(|c| c as f64),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }).collect(),
+        }
+    }
+}
+
+impl From<&CodeWhispererUsageResponse> for UsageSummary {
+    fn from(resp: &CodeWhispererUsageResponse) -> Self {
+        Self {
+            subscription: resp.subscription_info.as_ref().map(|s| SubscriptionSummary {
+                title: s.subscription_title.clone(),
+                subscription_type: s.subscription_type.clone(),
+                overage_capability: s.overage_capability.clone(),
+                upgrade_capability: s.upgrade_capability.clone(),
+            }),
+            user: resp.user_info.as_ref().map(|u| UsageUser {
+                email: u.email.clone(),
+                user_id: u.user_id.clone(),
+            }),
+            overage_status: resp.overage_configuration.as_ref().and_then(|o| o.overage_status.clone()),
+            days_until_reset: resp.days_until_reset,
+            next_date_reset: resp.next_date_reset,
+            resources: resp.usage_breakdown_list.iter().flatten().map(|b| ResourceUsage {
+                resource_type: b.resource_type.clone(),
+                display_name: b.display_name.clone(),
+                unit: b.unit.clone(),
+                currency: b.currency.clone(),
+                usage_limit: to_f64(b.usage_limit),
+                current_usage: to_f64(b.current_usage),
+                usage_limit_with_precision: b.usage_limit_with_precision,
+                current_usage_with_precision: b.current_usage_with_precision,
+                next_date_reset: b.next_date_reset.or(resp.next_date_reset),
+                free_trial: b.free_trial_info.as_ref().map(|t| FreeTrialUsage {
+                    status: t.free_trial_status.clone(),
+                    usage_limit: to_f64(t.usage_limit),
+                    current_usage: to_f64(t.current_usage),
+                    usage_limit_with_precision: t.usage_limit_with_precision,
+                    current_usage_with_precision: t.current_usage_with_precision,
+                    expiry: t.free_trial_expiry,
+                }),
+                bonuses: b.bonuses.iter().flatten().map(|bonus| BonusUsage {
+                    code: bonus.bonus_code.clone(),
+                    display_name: bonus.display_name.clone(),
+                    usage_limit: bonus.usage_limit.unwrap_or(0.0),
+                    current_usage: bonus.current_usage.unwrap_or(0.0),
+                    expires_at: bonus.expires_at,
+                    status: bonus.status.clone(),
+                    ..Default::default()
+                }).collect(),
+                overage: (b.overage_rate.is_some() || b.overage_cap.is_some()).then(|| OverageUsage {
+                    rate: b.overage_rate,
+                    cap: b.overage_cap_with_precision.or(b.overage_cap.map(|c| c as f64)),
+                    current_overages: b.current_overages_with_precision.or(b.current_overages.map(|c| c as f64)),
+                    charges: b.overage_charges,
+                }),
+            }).collect(),
+        }
+    }
+}
+
+impl From<&GetUserUsageAndLimitsResponse> for UsageSummary {
+    fn from(resp: &GetUserUsageAndLimitsResponse) -> Self {
+        Self {
+            subscription: resp.subscription_info.as_ref().map(|s| SubscriptionSummary {
+                title: s.subscription_title.clone(),
+                subscription_type: s.subscription_type.clone(),
+                ..Default::default()
+            }),
+            user: resp.user_info.as_ref().map(|u| UsageUser {
+                email: u.email.clone(),
+                user_id: u.user_id.clone(),
+            }),
+            overage_status: None,
+            days_until_reset: resp.days_until_reset,
+            next_date_reset: resp.next_date_reset,
+            resources: resp.usage_breakdown_list.iter().flatten().map(|b| ResourceUsage {
+                resource_type: b.resource_type.clone(),
+                currency: b.currency.clone(),
+                usage_limit: to_f64(b.usage_limit),
+                current_usage: to_f64(b.current_usage),
+                usage_limit_with_precision: b.usage_limit_with_precision,
+                current_usage_with_precision: b.current_usage_with_precision,
+                next_date_reset: resp.next_date_reset,
+                free_trial: b.free_trial_info.as_ref().map(|t| FreeTrialUsage {
+                    status: t.free_trial_status.clone(),
+                    usage_limit: to_f64(t.usage_limit),
+                    current_usage: to_f64(t.current_usage),
+                    expiry: t.free_trial_expiry,
+                    ..Default::default()
+                }),
+                bonuses: b.bonuses.iter().flatten().map(|bonus| BonusUsage {
+                    code: bonus.bonus_code.clone(),
+                    display_name: bonus.display_name.clone(),
+                    usage_limit: bonus.usage_limit.unwrap_or(0.0),
+                    current_usage: bonus.current_usage.unwrap_or(0.0),
+                    expires_at: bonus.expires_at,
+                    status: bonus.status.clone(),
+                    ..Default::default()
+                }).collect(),
+                overage: (b.overage_rate.is_some() || b.overage_cap.is_some()).then(|| OverageUsage {
+                    rate: b.overage_rate,
+                    cap: b.overage_cap.map(|c| c as f64),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }).collect(),
+        }
+    }
+}
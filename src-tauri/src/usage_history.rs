@@ -0,0 +1,270 @@
+// 用量历史 - 每次同步追加一条归一化的用量快照到本地 SQLite
+// 用于查询时间序列、按天增量和消耗速度估算
+
+use chrono::{Local, TimeZone};
+use rusqlite::{params, Connection};
+use serde::Serialize;
+
+use crate::account::get_app_data_dir;
+use crate::usage::UsageSummary;
+
+/// 按天统计 / 消耗速度查询的最大天数
+const MAX_HISTORY_DAYS: u32 = 3650;
+
+/// 单条用量快照（一个 resource type 一条）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageSnapshot {
+    /// 毫秒时间戳
+    pub captured_at: i64,
+    pub resource_type: String,
+    pub current_usage: f64,
+    pub usage_limit: f64,
+    pub bonus_usage: f64,
+    pub bonus_limit: f64,
+    pub free_trial_usage: f64,
+    pub free_trial_limit: f64,
+    pub overage: f64,
+    /// 毫秒时间戳
+    pub next_date_reset: Option<i64>,
+}
+
+impl UsageSnapshot {
+    /// 总已用量（主额度 + 奖励 + 试用）
+    pub fn total_used(&self) -> f64 {
+        self.current_usage + self.bonus_usage + self.free_trial_usage
+    }
+
+    /// 总额度（主额度 + 奖励 + 试用）
+    pub fn total_limit(&self) -> f64 {
+        self.usage_limit + self.bonus_limit + self.free_trial_limit
+    }
+}
+
+/// 按天统计的用量增量
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyUsageDelta {
+    /// 本地日期 YYYY-MM-DD
+    pub date: String,
+    pub used: f64,
+}
+
+/// 消耗速度估算
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BurnRate {
+    pub resource_type: Option<String>,
+    /// 统计窗口内的平均每日消耗
+    pub per_day: f64,
+    pub total_used: f64,
+    pub total_limit: f64,
+    pub remaining: f64,
+    pub next_date_reset: Option<i64>,
+    pub days_until_reset: Option<f64>,
+    /// 按当前速度到重置日的预计用量
+    pub projected_usage_at_reset: Option<f64>,
+    /// 按当前速度额度还能用几天（速度为 0 时为空）
+    pub days_until_exhausted: Option<f64>,
+    pub will_exhaust_before_reset: bool,
+    pub sample_count: usize,
+}
+
+fn open_db() -> Result<Connection, String> {
+    let dir = get_app_data_dir();
+    std::fs::create_dir_all(&dir).map_err(|e| format!("创建数据目录失败: {}", e))?;
+    let conn = Connection::open(dir.join("usage_history.db"))
+        .map_err(|e| format!("打开用量历史数据库失败: {}", e))?;
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS usage_snapshots (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            account_id TEXT NOT NULL,
+            captured_at INTEGER NOT NULL,
+            breakdown_index INTEGER NOT NULL,
+            resource_type TEXT NOT NULL,
+            current_usage REAL NOT NULL,
+            usage_limit REAL NOT NULL,
+            bonus_usage REAL NOT NULL,
+            bonus_limit REAL NOT NULL,
+            free_trial_usage REAL NOT NULL,
+            free_trial_limit REAL NOT NULL,
+            overage REAL NOT NULL,
+            next_date_reset INTEGER
+        );
+        CREATE INDEX IF NOT EXISTS idx_usage_snapshots_account
+            ON usage_snapshots (account_id, captured_at);",
+    )
+    .map_err(|e| format!("初始化用量历史数据库失败: {}", e))?;
+    Ok(conn)
+}
+
+/// 把归一化用量拆成快照（每个资源类型一条）
+fn normalize(usage: &UsageSummary, captured_at: i64) -> Vec<UsageSnapshot> {
+    // nextDateReset 为秒级时间戳
+    let to_millis = |v: Option<f64>| v.map(|s| (s * 1000.0) as i64);
+
+    usage.resources.iter().map(|r| UsageSnapshot {
+        captured_at,
+        resource_type: r.resource_type.clone().unwrap_or_else(|| "UNKNOWN".to_string()),
+        current_usage: r.current_usage_with_precision.unwrap_or(r.current_usage),
+        usage_limit: r.usage_limit_with_precision.unwrap_or(r.usage_limit),
+        bonus_usage: r.bonuses.iter().map(|b| b.current_usage).sum(),
+        bonus_limit: r.bonuses.iter().map(|b| b.usage_limit).sum(),
+        free_trial_usage: r.free_trial.as_ref()
+            .map(|t| t.current_usage_with_precision.unwrap_or(t.current_usage))
+            .unwrap_or(0.0),
+        free_trial_limit: r.free_trial.as_ref()
+            .map(|t| t.usage_limit_with_precision.unwrap_or(t.usage_limit))
+            .unwrap_or(0.0),
+        overage: r.overage.as_ref().and_then(|o| o.current_overages).unwrap_or(0.0),
+        next_date_reset: to_millis(r.next_date_reset.or(usage.next_date_reset)),
+    }).collect()
+}
+
+/// 记录一次同步得到的用量，返回写入的快照数
+pub fn record_snapshot(account_id: &str, usage: &UsageSummary) -> Result<usize, String> {
+    let snapshots = normalize(usage, Local::now().timestamp_millis());
+    if snapshots.is_empty() {
+        return Ok(0);
+    }
+
+    let mut conn = open_db()?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    for (idx, s) in snapshots.iter().enumerate() {
+        tx.execute(
+            "INSERT INTO usage_snapshots (account_id, captured_at, breakdown_index, resource_type,
+                current_usage, usage_limit, bonus_usage, bonus_limit, free_trial_usage, free_trial_limit,
+                overage, next_date_reset)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+            params![
+                account_id, s.captured_at, idx as i64, s.resource_type,
+                s.current_usage, s.usage_limit, s.bonus_usage, s.bonus_limit,
+                s.free_trial_usage, s.free_trial_limit, s.overage, s.next_date_reset,
+            ],
+        )
+        .map_err(|e| format!("写入用量快照失败: {}", e))?;
+    }
+    tx.commit().map_err(|e| e.to_string())?;
+    Ok(snapshots.len())
+}
+
+/// 删除账号的用量历史
+pub fn delete_history(account_ids: &[String]) -> Result<usize, String> {
+    let conn = open_db()?;
+    let mut deleted = 0;
+    for id in account_ids {
+        deleted += conn.execute("DELETE FROM usage_snapshots WHERE account_id = ?1", params![id])
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(deleted)
+}
+
+/// 查询时间序列；未指定 resource_type 时取主额度（第一条 breakdown）
+pub fn get_series(account_id: &str, from: Option<i64>, to: Option<i64>, resource_type: Option<&str>) -> Result<Vec<UsageSnapshot>, String> {
+    let conn = open_db()?;
+    let mut stmt = conn.prepare(
+        "SELECT captured_at, resource_type, current_usage, usage_limit, bonus_usage, bonus_limit,
+                free_trial_usage, free_trial_limit, overage, next_date_reset
+         FROM usage_snapshots
+         WHERE account_id = ?1
+           AND captured_at >= ?2 AND captured_at <= ?3
+           AND (CASE WHEN ?4 IS NULL THEN breakdown_index = 0 ELSE resource_type = ?4 END)
+         ORDER BY captured_at ASC",
+    )
+    .map_err(|e| e.to_string())?;
+
+    let rows = stmt.query_map(
+        params![account_id, from.unwrap_or(0), to.unwrap_or(i64::MAX), resource_type],
+        |row| Ok(UsageSnapshot {
+            captured_at: row.get(0)?,
+            resource_type: row.get(1)?,
+            current_usage: row.get(2)?,
+            usage_limit: row.get(3)?,
+            bonus_usage: row.get(4)?,
+            bonus_limit: row.get(5)?,
+            free_trial_usage: row.get(6)?,
+            free_trial_limit: row.get(7)?,
+            overage: row.get(8)?,
+            next_date_reset: row.get(9)?,
+        }),
+    )
+    .map_err(|e| e.to_string())?;
+
+    rows.collect::<Result<Vec<_>, _>>().map_err(|e| e.to_string())
+}
+
+/// 相邻两次快照之间的消耗；用量变小说明额度已重置，此时以新值作为消耗
+fn increment(prev: &UsageSnapshot, next: &UsageSnapshot) -> f64 {
+    let diff = next.total_used() - prev.total_used();
+    if diff >= 0.0 { diff } else { next.total_used() }
+}
+
+fn local_date(millis: i64) -> String {
+    Local.timestamp_millis_opt(millis)
+        .single()
+        .map(|dt| dt.format("%Y-%m-%d").to_string())
+        .unwrap_or_default()
+}
+
+/// 按天统计最近 days 天的消耗（days 限制在 1..=MAX_HISTORY_DAYS）
+pub fn get_daily_deltas(account_id: &str, days: u32, resource_type: Option<&str>) -> Result<Vec<DailyUsageDelta>, String> {
+    let days = days.clamp(1, MAX_HISTORY_DAYS);
+    let from = Local::now() - chrono::Duration::days(days as i64);
+    // 多取一天作为基准点
+    let series = get_series(account_id, Some((from - chrono::Duration::days(1)).timestamp_millis()), None, resource_type)?;
+
+    let mut deltas: Vec<DailyUsageDelta> = Vec::new();
+    for pair in series.windows(2) {
+        let date = local_date(pair[1].captured_at);
+        let used = increment(&pair[0], &pair[1]);
+        match deltas.last_mut() {
+            Some(last) if last.date == date => last.used += used,
+            _ => deltas.push(DailyUsageDelta { date, used }),
+        }
+    }
+
+    let cutoff = from.format("%Y-%m-%d").to_string();
+    deltas.retain(|d| d.date > cutoff);
+    Ok(deltas)
+}
+
+/// 按最近 window_days 天的平均速度估算到下次重置前的消耗
+pub fn get_burn_rate(account_id: &str, window_days: u32, resource_type: Option<&str>) -> Result<BurnRate, String> {
+    let now = Local::now().timestamp_millis();
+    let from = now - window_days.clamp(1, MAX_HISTORY_DAYS) as i64 * 86_400_000;
+    let series = get_series(account_id, Some(from), None, resource_type)?;
+
+    let consumed: f64 = series.windows(2).map(|p| increment(&p[0], &p[1])).sum();
+    let elapsed_days = match (series.first(), series.last()) {
+        (Some(first), Some(last)) if last.captured_at > first.captured_at => {
+            (last.captured_at - first.captured_at) as f64 / 86_400_000.0
+        }
+        _ => 0.0,
+    };
+    let per_day = if elapsed_days > 0.0 { consumed / elapsed_days } else { 0.0 };
+
+    let latest = series.last();
+    let total_used = latest.map(|s| s.total_used()).unwrap_or(0.0);
+    let total_limit = latest.map(|s| s.total_limit()).unwrap_or(0.0);
+    let remaining = (total_limit - total_used).max(0.0);
+    let next_date_reset = latest.and_then(|s| s.next_date_reset);
+    let days_until_reset = next_date_reset.map(|r| ((r - now) as f64 / 86_400_000.0).max(0.0));
+    let days_until_exhausted = (per_day > 0.0).then(|| remaining / per_day);
+
+    Ok(BurnRate {
+        resource_type: latest.map(|s| s.resource_type.clone()),
+        per_day,
+        total_used,
+        total_limit,
+        remaining,
+        next_date_reset,
+        days_until_reset,
+        projected_usage_at_reset: days_until_reset.map(|d| total_used + per_day * d),
+        days_until_exhausted,
+        will_exhaust_before_reset: match (days_until_exhausted, days_until_reset) {
+            (Some(exhausted), Some(reset)) => exhausted < reset,
+            _ => false,
+        },
+        sample_count: series.len(),
+    })
+}
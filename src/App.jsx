@@ -90,17 +90,25 @@ function App() {
       }
 
       console.log(`[AutoRefresh] 刷新 ${expiredAccounts.length} 个 token (${source})...`)
-      await Promise.allSettled(
-        expiredAccounts.map(async (account) => {
-          try {
-            await invoke('refresh_account_token', { id: account.id })
-            console.log(`[AutoRefresh] ${account.email} token 刷新成功`)
-          } catch (e) {
-            console.warn(`[AutoRefresh] ${account.email} token 刷新失败:`, e)
-          }
+      const batchId = `auto-refresh-${source}-${nowMs}`
+      const unlisten = await listen('sync-accounts-progress', (event) => {
+        const p = event.payload
+        if (p.batchId !== batchId) return
+        if (p.status === 'success') {
+          console.log(`[AutoRefresh] ${p.email} token 刷新成功`)
+        } else {
+          console.warn(`[AutoRefresh] ${p.email} token 刷新失败:`, p.error || p.status)
+        }
+      })
+      try {
+        const report = await invoke('sync_accounts', {
+          ids: expiredAccounts.map(a => a.id),
+          options: { refreshOnly: true, batchId },
         })
-      )
-      console.log('[AutoRefresh] token 刷新完成')
+        console.log(`[AutoRefresh] token 刷新完成: 成功 ${report.success}/${report.total}`)
+      } finally {
+        unlisten()
+      }
     } catch (e) {
       console.error('[AutoRefresh] 刷新失败:', e)
     } finally {
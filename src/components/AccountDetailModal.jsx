@@ -1,17 +1,19 @@
-import { useState } from 'react'
+import { useState, useEffect, useRef } from 'react'
 import { invoke } from '@tauri-apps/api/core'
+import { listen } from '@tauri-apps/api/event'
 import { X, Copy, Check, RefreshCw, User, CreditCard, Key, Clock, ChevronDown, ChevronUp, Shield } from 'lucide-react'
 import { useTheme } from '../contexts/ThemeContext'
 import { useDialog } from '../contexts/DialogContext'
 import { useI18n } from '../i18n'
+import { getPrimaryResource } from '../utils/accountStats'
 
 function AccountDetailModal({ account, onClose }) {
   const { theme, colors } = useTheme()
   const { showError } = useDialog()
   const { t } = useI18n()
   const isDark = theme === 'dark'
-  const initQuota = account.usageData?.usageBreakdownList?.[0]?.usageLimit ?? account.quota ?? 50
-  const initUsed = account.usageData?.usageBreakdownList?.[0]?.currentUsage ?? account.used ?? 0
+  const initQuota = getPrimaryResource(account)?.usageLimit ?? account.quota ?? 50
+  const initUsed = getPrimaryResource(account)?.currentUsage ?? account.used ?? 0
   const [form, setForm] = useState({
     email: account.email,
     label: account.label || '',
@@ -32,8 +34,8 @@ function AccountDetailModal({ account, onClose }) {
     setRefreshing(true)
     try {
       const updated = await invoke('sync_account', { id: account.id })
-      const quota = updated.usageData?.usageBreakdownList?.[0]?.usageLimit ?? 50
-      const used = updated.usageData?.usageBreakdownList?.[0]?.currentUsage ?? 0
+      const quota = getPrimaryResource(updated)?.usageLimit ?? 50
+      const used = getPrimaryResource(updated)?.currentUsage ?? 0
       setForm(prev => ({ ...prev, quota, used, status: updated.status }))
     } catch (e) {
       await showError(t('detail.refreshFailed'), e.toString())
@@ -42,15 +44,74 @@ function AccountDetailModal({ account, onClose }) {
     }
   }
 
+  const [reauthing, setReauthing] = useState(false)
+  const needsRelogin = ['refreshTokenInvalid', 'clientRegistrationExpired'].includes(account.health?.state)
+  // BuilderId / Enterprise 用设备码重新登录，可取消
+  const isIdc = account.provider === 'BuilderId' || account.provider === 'Enterprise' || !!account.clientIdHash || (!account.provider && !!account.startUrl)
+  const [deviceSession, setDeviceSession] = useState(null)
+  const deviceSessionRef = useRef(null)
+  deviceSessionRef.current = deviceSession
+
+  const applyReauth = (updated) => {
+    setForm(prev => ({ ...prev, status: updated.status, accessToken: updated.accessToken || '', refreshToken: updated.refreshToken || '' }))
+  }
+
+  useEffect(() => {
+    const unlisten = listen('device-login-complete', async (event) => {
+      const { sessionId, success, cancelled, error: err } = event.payload || {}
+      if (!deviceSessionRef.current || sessionId !== deviceSessionRef.current.sessionId) return
+      setDeviceSession(null)
+      setReauthing(false)
+      if (success) {
+        const accounts = await invoke('get_accounts').catch(() => [])
+        const updated = accounts.find(a => a.id === account.id)
+        if (updated) applyReauth(updated)
+      } else if (!cancelled) {
+        await showError(t('detail.reauthFailed'), err || '')
+      }
+    })
+    return () => {
+      unlisten.then(fn => fn())
+      // 关闭弹窗时取消进行中的设备码会话
+      if (deviceSessionRef.current) {
+        invoke('cancel_device_login', { sessionId: deviceSessionRef.current.sessionId }).catch(() => {})
+      }
+    }
+  }, [account.id, showError, t])
+
+  const handleReauth = async () => {
+    setReauthing(true)
+    try {
+      if (isIdc) {
+        const session = await invoke('start_device_reauth', { id: account.id })
+        setDeviceSession(session)
+        return
+      }
+      applyReauth(await invoke('reauthenticate_account', { id: account.id }))
+    } catch (e) {
+      await showError(t('detail.reauthFailed'), e.toString())
+    }
+    setReauthing(false)
+  }
+
+  const handleCancelReauth = () => {
+    if (deviceSession) {
+      invoke('cancel_device_login', { sessionId: deviceSession.sessionId }).catch(() => {})
+      setDeviceSession(null)
+    }
+    setReauthing(false)
+  }
+
   const handleCopy = (text, field) => {
     navigator.clipboard.writeText(text)
     setCopied(field)
     setTimeout(() => setCopied(null), 1500)
   }
 
-  // 从 usageData 读取免费试用和奖励信息
-  const breakdown = account.usageData?.usageBreakdownList?.[0]
-  const freeTrialInfo = breakdown?.freeTrialInfo
+  // 从 usage 读取免费试用和奖励信息
+  const usage = account.usage
+  const breakdown = getPrimaryResource(account)
+  const freeTrialInfo = breakdown?.freeTrial
   const bonuses = breakdown?.bonuses || []
   const freeTrialQuota = freeTrialInfo?.usageLimit || 0
   const freeTrialUsed = freeTrialInfo?.currentUsage || 0
@@ -77,8 +138,8 @@ function AccountDetailModal({ account, onClose }) {
             <div>
               <div className="flex items-center gap-2">
                 <h2 className={`text-lg font-semibold ${colors.text}`}>{account.email}</h2>
-                <span className={`px-2 py-0.5 rounded text-xs font-medium ${(account.usageData?.subscriptionInfo?.type?.includes('PRO+') || account.usageData?.subscriptionInfo?.subscriptionTitle?.includes('PRO+')) ? 'bg-gradient-to-r from-purple-500 to-pink-500 text-white' : (account.usageData?.subscriptionInfo?.type?.includes('PRO') || account.usageData?.subscriptionInfo?.subscriptionTitle?.includes('PRO')) ? 'bg-blue-500 text-white' : (isDark ? 'bg-gray-700 text-gray-300' : 'bg-gray-200 text-gray-600')}`}>
-                  {account.usageData?.subscriptionInfo?.subscriptionTitle || 'Free'}
+                <span className={`px-2 py-0.5 rounded text-xs font-medium ${(usage?.subscription?.type?.includes('PRO+') || usage?.subscription?.title?.includes('PRO+')) ? 'bg-gradient-to-r from-purple-500 to-pink-500 text-white' : (usage?.subscription?.type?.includes('PRO') || usage?.subscription?.title?.includes('PRO')) ? 'bg-blue-500 text-white' : (isDark ? 'bg-gray-700 text-gray-300' : 'bg-gray-200 text-gray-600')}`}>
+                  {usage?.subscription?.title || 'Free'}
                 </span>
               </div>
               <p className={`text-sm ${colors.textMuted}`}>{account.provider || t('common.unknown')} · {t('detail.addedAt')} {account.addedAt?.split(' ')[0]}</p>
@@ -128,14 +189,14 @@ function AccountDetailModal({ account, onClose }) {
                   {breakdown?.nextDateReset && <div className={`text-xs ${colors.textMuted} mt-1`}>{new Date(breakdown.nextDateReset * 1000).toLocaleDateString()} {t('detail.reset')}</div>}
                 </div>
                 
-                <div className={`rounded-lg p-3 ${freeTrialQuota && freeTrialInfo?.freeTrialStatus === 'ACTIVE' ? (isDark ? 'bg-cyan-500/20' : 'bg-cyan-50') : (isDark ? 'bg-white/5' : 'bg-gray-50')}`}>
+                <div className={`rounded-lg p-3 ${freeTrialQuota && freeTrialInfo?.status === 'ACTIVE' ? (isDark ? 'bg-cyan-500/20' : 'bg-cyan-50') : (isDark ? 'bg-white/5' : 'bg-gray-50')}`}>
                   <div className="flex items-center gap-1.5 mb-1">
-                    <div className={`w-2 h-2 rounded-full ${freeTrialInfo?.freeTrialStatus === 'ACTIVE' ? 'bg-cyan-500' : 'bg-gray-300'}`}></div>
+                    <div className={`w-2 h-2 rounded-full ${freeTrialInfo?.status === 'ACTIVE' ? 'bg-cyan-500' : 'bg-gray-300'}`}></div>
                     <span className={`text-xs ${colors.textMuted}`}>{t('detail.freeTrial')}</span>
-                    {freeTrialInfo?.freeTrialStatus && <span className={`text-xs ${freeTrialInfo.freeTrialStatus === 'ACTIVE' ? 'text-cyan-500' : colors.textMuted}`}>({freeTrialInfo.freeTrialStatus})</span>}
+                    {freeTrialInfo?.status && <span className={`text-xs ${freeTrialInfo.status === 'ACTIVE' ? 'text-cyan-500' : colors.textMuted}`}>({freeTrialInfo.status})</span>}
                   </div>
                   <div className={`text-lg font-semibold ${colors.text}`} title={freeTrialInfo?.currentUsageWithPrecision != null ? `${t('detail.precise')}: ${freeTrialInfo.currentUsageWithPrecision} / ${freeTrialInfo.usageLimitWithPrecision}` : undefined}>{freeTrialQuota ? `${freeTrialUsed} / ${freeTrialQuota}` : '-'}</div>
-                  {freeTrialInfo?.freeTrialExpiry && <div className={`text-xs ${colors.textMuted} mt-1`}>{new Date(freeTrialInfo.freeTrialExpiry * 1000).toLocaleDateString()} {t('detail.expires')}</div>}
+                  {freeTrialInfo?.expiry && <div className={`text-xs ${colors.textMuted} mt-1`}>{new Date(freeTrialInfo.expiry * 1000).toLocaleDateString()} {t('detail.expires')}</div>}
                 </div>
                 
                 <div className={`rounded-lg p-3 ${bonusQuota ? (isDark ? 'bg-purple-500/20' : 'bg-purple-50') : (isDark ? 'bg-white/5' : 'bg-gray-50')}`}>
@@ -157,7 +218,7 @@ function AccountDetailModal({ account, onClose }) {
                       <div key={idx} className={`flex items-center justify-between p-2.5 rounded-lg ${bonus.status === 'ACTIVE' ? (isDark ? 'bg-purple-500/10' : 'bg-purple-50') : bonus.status === 'EXHAUSTED' ? (isDark ? 'bg-gray-500/10' : 'bg-gray-100') : (isDark ? 'bg-white/5' : 'bg-gray-50')}`}>
                         <div className="flex-1 min-w-0">
                           <div className="flex items-center gap-2">
-                            <span className={`text-sm font-medium ${colors.text}`}>{bonus.displayName || bonus.bonusCode}</span>
+                            <span className={`text-sm font-medium ${colors.text}`}>{bonus.displayName || bonus.code}</span>
                             <span className={`text-xs px-1.5 py-0.5 rounded ${bonus.status === 'ACTIVE' ? 'bg-green-500/20 text-green-500' : bonus.status === 'EXHAUSTED' ? 'bg-gray-500/20 text-gray-500' : 'bg-yellow-500/20 text-yellow-600'}`}>{bonus.status}</span>
                           </div>
                           <div className={`text-xs ${colors.textMuted} mt-0.5`}>
@@ -168,7 +229,7 @@ function AccountDetailModal({ account, onClose }) {
                         </div>
                         <div className="text-right ml-3">
                           <div className={`text-sm font-semibold ${colors.text}`}>{Math.round(bonus.currentUsage || 0)} / {Math.round(bonus.usageLimit || 0)}</div>
-                          <div className={`text-xs ${colors.textMuted}`}>{bonus.bonusCode}</div>
+                          <div className={`text-xs ${colors.textMuted}`}>{bonus.code}</div>
                         </div>
                       </div>
                     ))}
@@ -178,14 +239,14 @@ function AccountDetailModal({ account, onClose }) {
               
               {/* 订阅信息 */}
               <div className={`mt-4 pt-4 border-t ${colors.cardBorder} grid grid-cols-2 gap-x-6 gap-y-2 text-sm`}>
-                <div className="flex justify-between"><span className={colors.textMuted}>{t('detail.userId')}</span><span className={`${colors.text} font-mono text-xs truncate max-w-[150px]`} title={account.usageData?.userInfo?.userId}>{account.usageData?.userInfo?.userId?.slice(-12) || '-'}</span></div>
-                <div className="flex justify-between"><span className={colors.textMuted}>{t('detail.email')}</span><span className={`${colors.text} text-xs`}>{account.usageData?.userInfo?.email || account.email}</span></div>
-                <div className="flex justify-between"><span className={colors.textMuted}>{t('detail.subscriptionType')}</span><span className={`${colors.text} font-mono text-xs truncate max-w-[150px]`} title={account.usageData?.subscriptionInfo?.type}>{account.usageData?.subscriptionInfo?.type || '-'}</span></div>
-                <div className="flex justify-between"><span className={colors.textMuted}>{t('detail.upgradeable')}</span><span className={colors.text}>{account.usageData?.subscriptionInfo?.upgradeCapability === 'UPGRADE_CAPABLE' ? t('common.yes') : t('common.no')}</span></div>
-                {breakdown?.overageRate && (
+                <div className="flex justify-between"><span className={colors.textMuted}>{t('detail.userId')}</span><span className={`${colors.text} font-mono text-xs truncate max-w-[150px]`} title={usage?.user?.userId}>{usage?.user?.userId?.slice(-12) || '-'}</span></div>
+                <div className="flex justify-between"><span className={colors.textMuted}>{t('detail.email')}</span><span className={`${colors.text} text-xs`}>{usage?.user?.email || account.email}</span></div>
+                <div className="flex justify-between"><span className={colors.textMuted}>{t('detail.subscriptionType')}</span><span className={`${colors.text} font-mono text-xs truncate max-w-[150px]`} title={usage?.subscription?.type}>{usage?.subscription?.type || '-'}</span></div>
+                <div className="flex justify-between"><span className={colors.textMuted}>{t('detail.upgradeable')}</span><span className={colors.text}>{usage?.subscription?.upgradeCapability === 'UPGRADE_CAPABLE' ? t('common.yes') : t('common.no')}</span></div>
+                {breakdown?.overage?.rate && (
                   <>
-                    <div className="flex justify-between"><span className={colors.textMuted}>{t('detail.overageRate')}</span><span className={colors.text}>${breakdown.overageRate}/{breakdown.unit || t('detail.perUnit')}</span></div>
-                    <div className="flex justify-between"><span className={colors.textMuted}>{t('detail.overageCap')}</span><span className={colors.text}>{breakdown.overageCap}</span></div>
+                    <div className="flex justify-between"><span className={colors.textMuted}>{t('detail.overageRate')}</span><span className={colors.text}>${breakdown.overage.rate}/{breakdown.unit || t('detail.perUnit')}</span></div>
+                    <div className="flex justify-between"><span className={colors.textMuted}>{t('detail.overageCap')}</span><span className={colors.text}>{breakdown.overage.cap}</span></div>
                   </>
                 )}
               </div>
@@ -345,18 +406,44 @@ function AccountDetailModal({ account, onClose }) {
             </div>
           </div>
 
+          {/* 设备码重新登录 */}
+          {deviceSession && (
+            <div className={`px-6 py-4 ${colors.card} border-t ${colors.cardBorder} text-center space-y-2`}>
+              <div className={`text-xs ${colors.textMuted}`}>{t('login.userCode')}</div>
+              <button type="button" onClick={() => handleCopy(deviceSession.userCode, 'userCode')} className={`inline-flex items-center gap-2 font-mono text-2xl font-bold tracking-widest ${colors.text}`}>
+                {deviceSession.userCode}
+                {copied === 'userCode' ? <Check size={16} className="text-green-500" /> : <Copy size={16} className={colors.textMuted} />}
+              </button>
+              <a href={deviceSession.verificationUriComplete || deviceSession.verificationUri} target="_blank" rel="noopener noreferrer" className="block text-xs text-blue-500 hover:underline break-all">
+                {deviceSession.verificationUri}
+              </a>
+              <div className={`text-xs ${colors.textMuted}`}>{t('login.userCodeHint')}</div>
+              <button type="button" onClick={handleCancelReauth} className={`px-4 py-1.5 rounded-lg text-xs ${isDark ? 'bg-white/10 hover:bg-white/20' : 'bg-gray-100 hover:bg-gray-200'} ${colors.text}`}>
+                {t('login.cancel')}
+              </button>
+            </div>
+          )}
+
           {/* Footer */}
           <div className={`flex justify-between items-center px-6 py-4 ${colors.card} border-t ${colors.cardBorder}`}>
-            <div className={`text-xs ${colors.textMuted}`}>
+            <div className={`text-xs ${colors.textMuted}`} title={[account.lastCheckedAt, account.lastError].filter(Boolean).join('\n')}>
               {account.status === '正常' || account.status === '有效' 
                 ? <span className="flex items-center gap-1 text-green-500"><Shield size={12} />{t('detail.accountNormal')}</span> 
                 : account.status === '封禁' || account.status === '已封禁'
                   ? <span className="flex items-center gap-1 text-red-500"><Shield size={12} />{t('detail.accountBanned')}</span>
                   : <span className="flex items-center gap-1 text-orange-500"><Shield size={12} />{account.status}</span>}
             </div>
-            <button type="button" onClick={onClose} className="px-5 py-2 bg-blue-500 text-white rounded-lg text-sm font-medium hover:bg-blue-600">
-              {t('common.close')}
-            </button>
+            <div className="flex items-center gap-2">
+              {needsRelogin && (
+                <button type="button" onClick={handleReauth} disabled={reauthing} className="px-4 py-2 bg-orange-500 text-white rounded-lg text-sm font-medium hover:bg-orange-600 disabled:opacity-50 flex items-center gap-1.5">
+                  <RefreshCw size={14} className={reauthing ? 'animate-spin' : ''} />
+                  {t('detail.reauthenticate')}
+                </button>
+              )}
+              <button type="button" onClick={onClose} className="px-5 py-2 bg-blue-500 text-white rounded-lg text-sm font-medium hover:bg-blue-600">
+                {t('common.close')}
+              </button>
+            </div>
           </div>
         </div>
       </div>
@@ -1,8 +1,8 @@
-import { RefreshCw, Eye, Trash2, Copy, Check, Clock, Repeat, Edit2 } from 'lucide-react'
+import { RefreshCw, Eye, Trash2, Copy, Check, Clock, Repeat, Edit2, SlidersHorizontal } from 'lucide-react'
 import { useTheme } from '../../contexts/ThemeContext'
 import { useI18n } from '../../i18n.jsx'
 import { getUsagePercent, getProgressBarColor } from './hooks/useAccountStats'
-import { getQuota, getUsed, getSubType, getSubPlan } from '../../utils/accountStats'
+import { getPrimaryResource, getQuota, getUsed, getSubType, getSubPlan } from '../../utils/accountStats'
 
 function AccountCard({
   account,
@@ -14,6 +14,7 @@ function AccountCard({
   onRefresh,
   onEdit,
   onEditLabel,
+  onEditProfile,
   onDelete,
   refreshingId,
   switchingId,
@@ -27,7 +28,7 @@ function AccountCard({
   const used = getUsed(account)
   const subType = getSubType(account)
   const subPlan = getSubPlan(account)
-  const breakdown = account.usageData?.usageBreakdownList?.[0]
+  const breakdown = getPrimaryResource(account)
   const percent = getUsagePercent(used, quota)
   const isExpired = account.expiresAt && new Date(account.expiresAt.replace(/\//g, '-')) < new Date()
   const isBanned = account.status === '封禁' || account.status === '已封禁'
@@ -187,6 +188,13 @@ function AccountCard({
           >
             <Edit2 size={14} className={colors.textMuted} />
           </button>
+          <button 
+            onClick={() => onEditProfile(account)} 
+            className={`p-2 rounded-lg transition-all ${isDark ? 'hover:bg-white/10' : 'hover:bg-gray-100'}`} 
+            title={t('accountCard.ideProfile')}
+          >
+            <SlidersHorizontal size={14} className={colors.textMuted} />
+          </button>
           <button 
             onClick={() => onDelete(account.id)} 
             className={`p-2 rounded-lg transition-all ${isDark ? 'hover:bg-red-500/20' : 'hover:bg-red-50'}`} 
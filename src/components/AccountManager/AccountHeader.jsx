@@ -1,4 +1,4 @@
-import { Search, Download, Upload, RefreshCw, Trash2, Plus, Sparkles } from 'lucide-react'
+import { Search, Download, Upload, RefreshCw, Trash2, Plus, Sparkles, History } from 'lucide-react'
 import { useTheme } from '../../contexts/ThemeContext'
 import { useI18n } from '../../i18n.jsx'
 
@@ -11,6 +11,7 @@ function AccountHeader({
   onImport,
   onExport,
   onRefreshAll,
+  onShowSwitchHistory,
   autoRefreshing,
   lastRefreshTime,
   refreshProgress,
@@ -81,6 +82,13 @@ function AccountHeader({
             <Download size={16} className={colors.textMuted} />
             <span className={`text-sm ${colors.textMuted}`}>{t('accounts.export')}</span>
           </button>
+          <button 
+            onClick={onShowSwitchHistory} 
+            className={`btn-icon p-2 ${colors.card} border ${colors.cardBorder} rounded-xl ${isDark ? 'hover:bg-white/5' : 'hover:bg-gray-50'} transition-all`} 
+            title={t('switchHistory.title')}
+          >
+            <History size={18} className={colors.textMuted} />
+          </button>
           <button 
             onClick={onRefreshAll} 
             disabled={autoRefreshing} 
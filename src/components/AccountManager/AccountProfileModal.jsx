@@ -0,0 +1,233 @@
+import { useState, useEffect } from 'react'
+import { invoke } from '@tauri-apps/api/core'
+import { X, SlidersHorizontal, RotateCcw, Play, Trash2, Check } from 'lucide-react'
+import { useTheme } from '../../contexts/ThemeContext'
+import { useDialog } from '../../contexts/DialogContext'
+import { useI18n } from '../../i18n.jsx'
+
+// 每个字段为 null 表示切换账号时不修改该项
+const EMPTY_PROFILE = { model: null, proxy: null, mcpServers: null, steering: null }
+
+// 账号绑定的 IDE 配置：模型、代理、启用的 MCP 服务器和 steering 文件
+function AccountProfileModal({ account, onClose }) {
+  const { theme, colors } = useTheme()
+  const { showConfirm, showError, showSuccess } = useDialog()
+  const { t } = useI18n()
+  const isDark = theme === 'dark'
+
+  const [profile, setProfile] = useState(EMPTY_PROFILE)
+  const [hasProfile, setHasProfile] = useState(false)
+  const [mcpNames, setMcpNames] = useState([])
+  const [steeringNames, setSteeringNames] = useState([])
+  const [modelIds, setModelIds] = useState([])
+  const [busy, setBusy] = useState(false)
+
+  useEffect(() => {
+    Promise.all([
+      invoke('get_account_ide_profile', { accountId: account.id }).catch(() => null),
+      invoke('get_mcp_config').catch(() => null),
+      invoke('get_steering_files').catch(() => []),
+      invoke('get_account_models', { accountId: account.id }).catch(() => null),
+    ]).then(([saved, mcp, steering, catalog]) => {
+      const current = { ...EMPTY_PROFILE, ...(saved || {}) }
+      setProfile(current)
+      setHasProfile(!!saved)
+      setMcpNames(Object.keys(mcp?.mcpServers || {}).sort())
+      // 已停用的 steering 文件不在目录中，合并配置里记录的文件名
+      const names = new Set([...steering.map(f => f.fileName), ...(current.steering || [])])
+      setSteeringNames([...names].sort())
+      setModelIds((catalog?.models || []).map(m => m.modelId))
+    })
+  }, [account.id])
+
+  const toggleManaged = (key, initial) => {
+    setProfile(p => ({ ...p, [key]: p[key] === null ? initial : null }))
+  }
+
+  const toggleItem = (key, name) => {
+    setProfile(p => {
+      const list = p[key] || []
+      return { ...p, [key]: list.includes(name) ? list.filter(n => n !== name) : [...list, name] }
+    })
+  }
+
+  const handleSave = async () => {
+    setBusy(true)
+    try {
+      const isEmpty = Object.values(profile).every(v => v === null)
+      await invoke('save_account_ide_profile', { accountId: account.id, profile: isEmpty ? null : profile })
+      setHasProfile(!isEmpty)
+      await showSuccess(t('accountProfile.saved'), t('accountProfile.savedDesc'))
+    } catch (e) {
+      await showError(t('settings.saveFailed'), String(e))
+    } finally {
+      setBusy(false)
+    }
+  }
+
+  const handleDelete = async () => {
+    const ok = await showConfirm(t('accountProfile.delete'), t('accountProfile.deleteConfirm'))
+    if (!ok) return
+    await invoke('save_account_ide_profile', { accountId: account.id, profile: null })
+      .catch(e => showError(t('common.error'), String(e)))
+    setProfile(EMPTY_PROFILE)
+    setHasProfile(false)
+  }
+
+  const handleApply = async () => {
+    setBusy(true)
+    try {
+      await invoke('apply_account_ide_profile', { accountId: account.id })
+      await showSuccess(t('accountProfile.applied'), t('accountProfile.appliedDesc'))
+    } catch (e) {
+      await showError(t('accountProfile.applyFailed'), String(e))
+    } finally {
+      setBusy(false)
+    }
+  }
+
+  const handleRestore = async () => {
+    setBusy(true)
+    try {
+      const restored = await invoke('restore_account_ide_profile')
+      await showSuccess(t('accountProfile.restored'), `${t('accountProfile.restoredDesc')} ${restored.appliedAt}`)
+    } catch (e) {
+      await showError(t('accountProfile.restoreFailed'), String(e))
+    } finally {
+      setBusy(false)
+    }
+  }
+
+  const sectionClass = `p-3 rounded-lg ${isDark ? 'bg-white/5' : 'bg-gray-50'} space-y-2`
+  const inputClass = `w-full px-3 py-2 border rounded-lg text-sm ${colors.text} ${colors.input} ${colors.inputFocus} focus:ring-2`
+
+  const renderToggle = (field, label, initial) => (
+    <label className="flex items-center gap-2 cursor-pointer">
+      <input type="checkbox" checked={profile[field] !== null} onChange={() => toggleManaged(field, initial)} className="w-4 h-4 rounded" />
+      <span className={`text-sm font-medium ${colors.text}`}>{label}</span>
+    </label>
+  )
+
+  const renderItems = (field, names) => (
+    names.length === 0 ? (
+      <p className={`text-xs ${colors.textMuted}`}>{t('accountProfile.noItems')}</p>
+    ) : (
+      <div className="grid grid-cols-2 gap-1">
+        {names.map(name => (
+          <label key={name} className="flex items-center gap-2 cursor-pointer min-w-0">
+            <input type="checkbox" checked={profile[field].includes(name)} onChange={() => toggleItem(field, name)} className="w-3.5 h-3.5 rounded" />
+            <span className={`text-xs ${colors.text} truncate`}>{name}</span>
+          </label>
+        ))}
+      </div>
+    )
+  )
+
+  return (
+    <div className="fixed inset-0 bg-black/60 backdrop-blur-sm flex items-center justify-center z-50 p-4" onClick={onClose}>
+      <div
+        className={`${isDark ? 'bg-[#1a1a2e]' : 'bg-white'} rounded-xl w-full max-w-lg shadow-2xl max-h-[85vh] overflow-hidden flex flex-col`}
+        onClick={e => e.stopPropagation()}
+      >
+        <div className={`px-5 py-4 border-b ${colors.cardBorder} flex items-center justify-between`}>
+          <div className="min-w-0">
+            <div className="flex items-center gap-2">
+              <SlidersHorizontal size={18} className="text-blue-500" />
+              <h2 className={`font-semibold ${colors.text}`}>{t('accountProfile.title')}</h2>
+            </div>
+            <p className={`text-xs ${colors.textMuted} truncate mt-0.5`}>{account.email}</p>
+          </div>
+          <button onClick={onClose} className={`p-1 rounded-lg ${isDark ? 'hover:bg-white/10' : 'hover:bg-gray-100'}`}>
+            <X size={18} className={colors.textMuted} />
+          </button>
+        </div>
+
+        <div className="flex-1 overflow-auto p-5 space-y-3">
+          <p className={`text-xs ${colors.textMuted}`}>{t('accountProfile.desc')}</p>
+
+          <div className={sectionClass}>
+            {renderToggle('model', t('accountProfile.model'), '')}
+            {profile.model !== null && (
+              <>
+                <input
+                  type="text"
+                  value={profile.model}
+                  onChange={(e) => setProfile(p => ({ ...p, model: e.target.value }))}
+                  placeholder="claude-sonnet-4-6-20260217-thinking"
+                  list="account-profile-models"
+                  className={inputClass}
+                />
+                {/* 账号模型目录中的模型作为候选 */}
+                <datalist id="account-profile-models">
+                  {modelIds.map(id => <option key={id} value={id} />)}
+                </datalist>
+              </>
+            )}
+          </div>
+
+          <div className={sectionClass}>
+            {renderToggle('proxy', t('accountProfile.proxy'), '')}
+            {profile.proxy !== null && (
+              <input
+                type="text"
+                value={profile.proxy}
+                onChange={(e) => setProfile(p => ({ ...p, proxy: e.target.value }))}
+                placeholder={t('accountProfile.proxyPlaceholder')}
+                className={inputClass}
+              />
+            )}
+          </div>
+
+          <div className={sectionClass}>
+            {renderToggle('mcpServers', t('accountProfile.mcpServers'), [])}
+            {profile.mcpServers !== null && renderItems('mcpServers', mcpNames)}
+          </div>
+
+          <div className={sectionClass}>
+            {renderToggle('steering', t('accountProfile.steering'), [])}
+            {profile.steering !== null && renderItems('steering', steeringNames)}
+          </div>
+        </div>
+
+        <div className={`px-5 py-4 border-t ${colors.cardBorder} flex items-center justify-between gap-2`}>
+          <div className="flex gap-2">
+            <button
+              onClick={handleRestore}
+              disabled={busy}
+              title={t('accountProfile.restoreHint')}
+              className={`px-3 py-2 rounded-lg text-sm flex items-center gap-1.5 ${isDark ? 'bg-white/10 hover:bg-white/20' : 'bg-gray-100 hover:bg-gray-200'} ${colors.text} disabled:opacity-50`}
+            >
+              <RotateCcw size={14} />
+              {t('accountProfile.restore')}
+            </button>
+            {hasProfile && (
+              <button onClick={handleDelete} disabled={busy} title={t('accountProfile.delete')} className={`p-2 rounded-lg ${isDark ? 'hover:bg-red-500/20' : 'hover:bg-red-50'} disabled:opacity-50`}>
+                <Trash2 size={14} className="text-red-400" />
+              </button>
+            )}
+          </div>
+          <div className="flex gap-2">
+            <button
+              onClick={handleApply}
+              disabled={busy || !hasProfile}
+              className={`px-3 py-2 rounded-lg text-sm flex items-center gap-1.5 ${isDark ? 'bg-white/10 hover:bg-white/20' : 'bg-gray-100 hover:bg-gray-200'} ${colors.text} disabled:opacity-50 disabled:cursor-not-allowed`}
+            >
+              <Play size={14} />
+              {t('accountProfile.applyNow')}
+            </button>
+            <button
+              onClick={handleSave}
+              disabled={busy}
+              className="px-4 py-2 rounded-lg bg-blue-500 text-white text-sm hover:bg-blue-600 disabled:opacity-50 flex items-center gap-1.5"
+            >
+              <Check size={14} />
+              {t('common.save')}
+            </button>
+          </div>
+        </div>
+      </div>
+    </div>
+  )
+}
+
+export default AccountProfileModal
@@ -2,7 +2,7 @@ import { RefreshCw, Edit2, Trash2, Copy, Check, Clock, Repeat } from 'lucide-rea
 import { useTheme } from '../../contexts/ThemeContext'
 import { useI18n } from '../../i18n'
 import { getUsagePercent, getProgressBarColor } from './hooks/useAccountStats'
-import { getQuota, getUsed, getSubType, getSubPlan } from '../../utils/accountStats'
+import { getPrimaryResource, getQuota, getUsed, getSubType, getSubPlan } from '../../utils/accountStats'
 
 function AccountRow({
   account,
@@ -22,12 +22,12 @@ function AccountRow({
   const { t } = useI18n()
   const isDark = theme === 'dark'
   
-  // 从 usageData 读取配额信息
+  // 从 usage 读取配额信息
   const quota = getQuota(account)
   const used = getUsed(account)
   const subType = getSubType(account)
   const subPlan = getSubPlan(account)
-  const breakdown = account.usageData?.usageBreakdownList?.[0]
+  const breakdown = getPrimaryResource(account)
   const percent = getUsagePercent(used, quota)
   const isExpired = account.expiresAt && new Date(account.expiresAt.replace(/\//g, '-')) < new Date()
 
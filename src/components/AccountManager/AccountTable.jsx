@@ -15,11 +15,12 @@ function AccountTable({
   onRefresh,
   onEdit,
   onEditLabel,
+  onEditProfile,
   onDelete,
   onAdd,
   refreshingId,
   switchingId,
-  localToken,
+  activeAccount,
 }) {
   const { theme, colors } = useTheme()
   const { t } = useI18n()
@@ -67,10 +68,11 @@ function AccountTable({
               onRefresh={onRefresh}
               onEdit={onEdit}
               onEditLabel={onEditLabel}
+              onEditProfile={onEditProfile}
               onDelete={onDelete}
               refreshingId={refreshingId}
               switchingId={switchingId}
-              isCurrentAccount={localToken?.refreshToken && account.refreshToken === localToken.refreshToken}
+              isCurrentAccount={!!activeAccount?.accountId && account.id === activeAccount.accountId}
             />
           ))}
           {/* 添加账号卡片 */}
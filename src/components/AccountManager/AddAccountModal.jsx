@@ -15,11 +15,15 @@ function AddAccountModal({ onClose, onSuccess }) {
   const [clientId, setClientId] = useState('')
   const [clientSecret, setClientSecret] = useState('')
   const [region, setRegion] = useState('us-east-1')
+  const [startUrl, setStartUrl] = useState('')
 
   const awsRegions = [
     { value: 'us-east-1', label: 'us-east-1 (N. Virginia)' },
     { value: 'us-west-2', label: 'us-west-2 (Oregon)' },
     { value: 'eu-west-1', label: 'eu-west-1 (Ireland)' },
+    { value: 'eu-central-1', label: 'eu-central-1 (Frankfurt)' },
+    { value: 'ap-southeast-1', label: 'ap-southeast-1 (Singapore)' },
+    { value: 'ap-northeast-1', label: 'ap-northeast-1 (Tokyo)' },
   ]
 
   const handleSaveLocal = async () => {
@@ -57,7 +61,7 @@ function AddAccountModal({ onClose, onSuccess }) {
           setAddLoading(false)
           return
         }
-        await invoke('add_account_by_idc', { refreshToken, clientId, clientSecret, region })
+        await invoke('add_account_by_idc', { refreshToken, clientId, clientSecret, region, startUrl: startUrl.trim() || null })
       } else {
         await invoke('add_account_by_social', { refreshToken })
       }
@@ -181,6 +185,16 @@ function AddAccountModal({ onClose, onSuccess }) {
                     <ChevronDown size={16} className={`absolute right-4 top-1/2 -translate-y-1/2 ${colors.textMuted} pointer-events-none`} />
                   </div>
                 </div>
+                <div>
+                  <label className={`block text-xs font-medium ${colors.textMuted} mb-1.5`}>{t('addAccount.startUrl')}</label>
+                  <input 
+                    type="text" 
+                    placeholder="https://d-xxxxxxxxxx.awsapps.com/start" 
+                    value={startUrl} 
+                    onChange={(e) => setStartUrl(e.target.value)} 
+                    className={`w-full px-4 py-3 border rounded-xl text-sm ${colors.text} ${isDark ? 'bg-white/5 border-white/10' : 'bg-white border-gray-200'} focus:outline-none focus:ring-2 focus:ring-blue-500/30 focus:border-blue-500 transition-all`} 
+                  />
+                </div>
               </>
             )}
 
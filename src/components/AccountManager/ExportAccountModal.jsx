@@ -0,0 +1,124 @@
+import { useState } from 'react'
+import { X, Download, AlertTriangle } from 'lucide-react'
+import { useTheme } from '../../contexts/ThemeContext'
+import { useDialog } from '../../contexts/DialogContext'
+import { useI18n } from '../../i18n.jsx'
+
+// 导出方式：默认加密导出，明文导出需要用户明确选择
+const PROFILES = ['encrypted', 'metadata', 'full']
+
+function ExportAccountModal({ selectedCount, onExport, onClose }) {
+  const { theme, colors } = useTheme()
+  const { showError, showSuccess } = useDialog()
+  const { t } = useI18n()
+  const isDark = theme === 'dark'
+
+  const [profile, setProfile] = useState('encrypted')
+  const [passphrase, setPassphrase] = useState('')
+  const [confirm, setConfirm] = useState('')
+  const [exporting, setExporting] = useState(false)
+
+  const needsPassphrase = profile === 'encrypted'
+  const mismatch = needsPassphrase && confirm.length > 0 && passphrase !== confirm
+  const canExport = !exporting && (!needsPassphrase || (passphrase.length > 0 && passphrase === confirm))
+
+  const handleExport = async () => {
+    setExporting(true)
+    try {
+      const saved = await onExport(profile, needsPassphrase ? passphrase : null)
+      if (!saved) return
+      await showSuccess(t('accounts.exportSuccess'), '')
+      onClose()
+    } catch (e) {
+      await showError(t('accounts.exportFailed'), String(e))
+    } finally {
+      setExporting(false)
+    }
+  }
+
+  const inputClass = `w-full px-3 py-2 border rounded-lg text-sm ${colors.text} ${colors.input} ${colors.inputFocus} focus:ring-2`
+
+  return (
+    <div className="fixed inset-0 bg-black/60 backdrop-blur-sm flex items-center justify-center z-50 p-4" onClick={onClose}>
+      <div
+        className={`${isDark ? 'bg-[#1a1a2e]' : 'bg-white'} rounded-xl w-full max-w-md shadow-2xl overflow-hidden flex flex-col`}
+        onClick={e => e.stopPropagation()}
+      >
+        <div className={`px-5 py-4 border-b ${colors.cardBorder} flex items-center justify-between`}>
+          <div className="min-w-0">
+            <div className="flex items-center gap-2">
+              <Download size={18} className="text-blue-500" />
+              <h2 className={`font-semibold ${colors.text}`}>{t('export.title')}</h2>
+            </div>
+            <p className={`text-xs ${colors.textMuted} mt-0.5`}>
+              {selectedCount > 0 ? t('export.selectedCount', { count: selectedCount }) : t('export.allAccounts')}
+            </p>
+          </div>
+          <button onClick={onClose} className={`p-1 rounded-lg ${isDark ? 'hover:bg-white/10' : 'hover:bg-gray-100'}`}>
+            <X size={18} className={colors.textMuted} />
+          </button>
+        </div>
+
+        <div className="p-5 space-y-3">
+          {PROFILES.map(p => (
+            <label
+              key={p}
+              className={`flex items-start gap-3 p-3 rounded-lg cursor-pointer border ${profile === p ? 'border-blue-500' : colors.cardBorder} ${isDark ? 'bg-white/5' : 'bg-gray-50'}`}
+            >
+              <input type="radio" name="export-profile" checked={profile === p} onChange={() => setProfile(p)} className="mt-0.5" />
+              <div>
+                <div className={`text-sm font-medium ${colors.text}`}>{t(`export.profile.${p}`)}</div>
+                <div className={`text-xs ${colors.textMuted}`}>{t(`export.profile.${p}Desc`)}</div>
+              </div>
+            </label>
+          ))}
+
+          {needsPassphrase && (
+            <div className="space-y-2">
+              <input
+                type="password"
+                value={passphrase}
+                onChange={(e) => setPassphrase(e.target.value)}
+                placeholder={t('export.passphrase')}
+                className={inputClass}
+              />
+              <input
+                type="password"
+                value={confirm}
+                onChange={(e) => setConfirm(e.target.value)}
+                placeholder={t('export.passphraseConfirm')}
+                className={inputClass}
+              />
+              {mismatch && <p className="text-xs text-red-500">{t('export.passphraseMismatch')}</p>}
+            </div>
+          )}
+
+          {profile === 'full' && (
+            <div className={`flex items-start gap-2 p-3 rounded-lg text-xs ${isDark ? 'bg-orange-500/10 text-orange-300' : 'bg-orange-50 text-orange-600'}`}>
+              <AlertTriangle size={14} className="flex-shrink-0 mt-0.5" />
+              <span>{t('export.fullWarning')}</span>
+            </div>
+          )}
+        </div>
+
+        <div className={`px-5 py-4 border-t ${colors.cardBorder} flex justify-end gap-2`}>
+          <button
+            onClick={onClose}
+            className={`px-4 py-2 rounded-lg text-sm ${isDark ? 'bg-white/10 hover:bg-white/20' : 'bg-gray-100 hover:bg-gray-200'} ${colors.text}`}
+          >
+            {t('common.cancel')}
+          </button>
+          <button
+            onClick={handleExport}
+            disabled={!canExport}
+            className="px-4 py-2 rounded-lg bg-blue-500 text-white text-sm hover:bg-blue-600 disabled:opacity-50 disabled:cursor-not-allowed"
+          >
+            {t('accounts.export')}
+          </button>
+        </div>
+      </div>
+    </div>
+  )
+}
+
+export default ExportAccountModal
@@ -4,6 +4,9 @@ import { invoke } from '@tauri-apps/api/core'
 import { useTheme } from '../../contexts/ThemeContext'
 import { useI18n } from '../../i18n.jsx'
 
+// 加密导出包的 format 字段（与后端 account_export::BUNDLE_FORMAT 一致）
+const BUNDLE_FORMAT = 'kiro-account-manager-bundle'
+
 // 校验单条账号数据（兼容导出格式和手动输入格式）
 function validateAccount(item, index) {
   const errors = []
@@ -70,6 +73,7 @@ function ImportAccountModal({ onClose, onSuccess }) {
   const [importing, setImporting] = useState(false)
   const [importProgress, setImportProgress] = useState({ current: 0, total: 0, currentEmail: '' })
   const [importResult, setImportResult] = useState(null)
+  const [bundlePassphrase, setBundlePassphrase] = useState('')
 
   // SSO Token 导入状态
   const [ssoToken, setSsoToken] = useState('')
@@ -87,6 +91,11 @@ function ImportAccountModal({ onClose, onSuccess }) {
     
     try {
       let data = JSON.parse(text)
+      // 加密导出包由后端解密导入
+      if (data?.format === BUNDLE_FORMAT) {
+        setParseResult({ bundle: true, valid: [], invalid: [], errors: [] })
+        return
+      }
       if (!Array.isArray(data)) {
         data = [data]
       }
@@ -128,8 +137,25 @@ function ImportAccountModal({ onClose, onSuccess }) {
     parseJson(text)
   }
 
+  // 导入加密导出包
+  const handleBundleImport = async () => {
+    setImporting(true)
+    setImportProgress({ current: 0, total: 1, currentEmail: '' })
+    try {
+      const count = await invoke('import_accounts', { json: jsonText, passphrase: bundlePassphrase })
+      setImportResult({ success: [], failed: [], count })
+      if (count > 0) onSuccess?.()
+    } catch (e) {
+      setImportResult({ success: [], failed: [{ index: 1, error: String(e) }], count: 0 })
+    } finally {
+      setImporting(false)
+      setBundlePassphrase('')
+    }
+  }
+
   // 执行 JSON 导入
   const handleJsonImport = async () => {
+    if (parseResult?.bundle) return handleBundleImport()
     if (!parseResult?.valid.length) return
     
     setImporting(true)
@@ -239,6 +265,7 @@ function ImportAccountModal({ onClose, onSuccess }) {
     setJsonText('')
     setSsoToken('')
     setParseResult(null)
+    setBundlePassphrase('')
   }
 
   // 渲染结果
@@ -248,7 +275,7 @@ function ImportAccountModal({ onClose, onSuccess }) {
         <div className="flex items-center gap-2 mb-2">
           <CheckCircle size={20} className="text-green-500" />
           <span className={`font-medium ${isDark ? 'text-green-300' : 'text-green-700'}`}>
-            {t('import.successCount', { count: result.success.length })}
+            {t('import.successCount', { count: result.count ?? result.success.length })}
           </span>
         </div>
         {result.success.length > 0 && (
@@ -429,7 +456,21 @@ function ImportAccountModal({ onClose, onSuccess }) {
                 />
               </div>
 
-              {parseResult && (
+              {parseResult?.bundle && (
+                <div>
+                  <label className={`block text-sm font-medium ${colors.text} mb-1`}>
+                    {t('import.bundlePassphrase')}
+                  </label>
+                  <input
+                    type="password"
+                    value={bundlePassphrase}
+                    onChange={(e) => setBundlePassphrase(e.target.value)}
+                    className={`w-full px-3 py-2 rounded-xl border ${colors.cardBorder} ${isDark ? 'bg-white/5' : 'bg-gray-50'} ${colors.text} text-sm focus:outline-none focus:ring-2 focus:ring-blue-500/30`}
+                  />
+                </div>
+              )}
+
+              {parseResult && !parseResult.bundle && (
                 <div className="space-y-2">
                   {parseResult.valid.length > 0 && (
                     <div className={`flex items-center gap-2 text-sm ${isDark ? 'text-green-400' : 'text-green-600'}`}>
@@ -544,7 +585,7 @@ function ImportAccountModal({ onClose, onSuccess }) {
               {activeTab === 'json' ? (
                 <button
                   onClick={handleJsonImport}
-                  disabled={importing || !parseResult?.valid.length}
+                  disabled={importing || (parseResult?.bundle ? !bundlePassphrase : !parseResult?.valid.length)}
                   className="px-4 py-2 bg-gradient-to-r from-blue-500 to-blue-600 text-white rounded-xl font-medium hover:from-blue-600 hover:to-blue-700 disabled:opacity-50 flex items-center gap-2"
                 >
                   <Upload size={16} />
@@ -0,0 +1,96 @@
+import { useState, useEffect, useCallback } from 'react'
+import { invoke } from '@tauri-apps/api/core'
+import { X, History, RotateCcw } from 'lucide-react'
+import { useTheme } from '../../contexts/ThemeContext'
+import { useDialog } from '../../contexts/DialogContext'
+import { useI18n } from '../../i18n.jsx'
+
+function SwitchHistoryModal({ onClose, onReverted }) {
+  const { theme, colors } = useTheme()
+  const { showError, showSuccess } = useDialog()
+  const { t } = useI18n()
+  const isDark = theme === 'dark'
+
+  const [history, setHistory] = useState([])
+  const [reverting, setReverting] = useState(false)
+
+  const loadHistory = useCallback(() => {
+    invoke('get_switch_history').then(setHistory).catch(() => setHistory([]))
+  }, [])
+
+  useEffect(() => { loadHistory() }, [loadHistory])
+
+  // 最近一条未撤销的记录的文件已清理时无法撤销
+  const canRevert = history.some(h => !h.reverted) && !history.find(h => !h.reverted)?.purged
+
+  const handleRevert = async () => {
+    setReverting(true)
+    try {
+      await invoke('revert_last_switch')
+      await showSuccess(t('switchHistory.reverted'), t('switchHistory.revertedDesc'))
+      loadHistory()
+      onReverted?.()
+    } catch (e) {
+      await showError(t('switchHistory.revertFailed'), String(e))
+    } finally {
+      setReverting(false)
+    }
+  }
+
+  return (
+    <div className="fixed inset-0 bg-black/60 backdrop-blur-sm flex items-center justify-center z-50 p-4" onClick={onClose}>
+      <div
+        className={`${isDark ? 'bg-[#1a1a2e]' : 'bg-white'} rounded-xl w-full max-w-lg shadow-2xl max-h-[85vh] overflow-hidden flex flex-col`}
+        onClick={e => e.stopPropagation()}
+      >
+        <div className={`px-5 py-4 border-b ${colors.cardBorder} flex items-center justify-between`}>
+          <div className="flex items-center gap-2">
+            <History size={18} className="text-blue-500" />
+            <h2 className={`font-semibold ${colors.text}`}>{t('switchHistory.title')}</h2>
+          </div>
+          <button onClick={onClose} className={`p-1 rounded-lg ${isDark ? 'hover:bg-white/10' : 'hover:bg-gray-100'}`}>
+            <X size={18} className={colors.textMuted} />
+          </button>
+        </div>
+
+        <div className="flex-1 overflow-auto p-5 space-y-2">
+          {history.length === 0 ? (
+            <p className={`text-sm text-center ${colors.textMuted} py-8`}>{t('switchHistory.empty')}</p>
+          ) : history.map(item => (
+            <div key={item.id} className={`p-3 rounded-lg ${isDark ? 'bg-white/5' : 'bg-gray-50'} ${item.reverted ? 'opacity-60' : ''}`}>
+              <div className="flex items-center justify-between">
+                <span className={`text-sm font-medium ${colors.text}`}>
+                  {item.fromProvider || '-'} → {item.toProvider}
+                </span>
+                <span className={`text-xs ${colors.textMuted}`}>{item.createdAt}</span>
+              </div>
+              {item.reverted && (
+                <div className={`text-xs mt-1 ${item.revertReason ? 'text-red-500' : colors.textMuted}`}>
+                  {item.revertReason
+                    ? `${t('switchHistory.rolledBack')}: ${item.revertReason}`
+                    : `${t('switchHistory.revertedAt')} ${item.revertedAt}`}
+                </div>
+              )}
+              {!item.reverted && item.purged && (
+                <div className={`text-xs mt-1 ${colors.textMuted}`}>{t('switchHistory.purged')}</div>
+              )}
+            </div>
+          ))}
+        </div>
+
+        <div className={`px-5 py-4 border-t ${colors.cardBorder} flex justify-end`}>
+          <button
+            onClick={handleRevert}
+            disabled={!canRevert || reverting}
+            className="px-4 py-2 rounded-lg bg-blue-500 text-white text-sm hover:bg-blue-600 disabled:opacity-50 disabled:cursor-not-allowed flex items-center gap-1.5"
+          >
+            <RotateCcw size={14} className={reverting ? 'animate-spin' : ''} />
+            {t('switchHistory.revertLast')}
+          </button>
+        </div>
+      </div>
+    </div>
+  )
+}
+
+export default SwitchHistoryModal
@@ -33,35 +33,36 @@ export function useAccounts() {
     setAutoRefreshing(true)
     setRefreshProgress({ current: 0, total: accountsToRefresh.length, currentEmail: '', results: [] })
 
-    const updatedAccounts = [...accountList]
+    // 后端并发刷新，按批次 ID 接收逐个账号的进度事件
+    const batchId = crypto.randomUUID()
     const results = []
+    const unlisten = await listen('sync-accounts-progress', (event) => {
+      const p = event.payload
+      if (p.batchId !== batchId) return
+      const success = p.status === 'success'
+      results.push({ email: p.email, success, message: success ? 'Token 已刷新' : String(p.error || p.status).slice(0, 30) })
+      setRefreshProgress({ current: p.completed, total: p.total, currentEmail: p.email, results: [...results] })
+    })
 
-    for (let i = 0; i < accountsToRefresh.length; i++) {
-      const account = accountsToRefresh[i]
-      setRefreshProgress(prev => ({ ...prev, currentEmail: account.email }))
-      let success = false, message = ''
-      try {
+    try {
+      await invoke('sync_accounts', {
+        ids: accountsToRefresh.map(a => a.id),
         // 只刷新 token，不获取 usage
-        const updated = await invoke('refresh_account_token', { id: account.id })
-        const idx = updatedAccounts.findIndex(a => a.id === account.id)
-        if (idx !== -1) updatedAccounts[idx] = updated
-        success = true
-        message = 'Token 已刷新'
-      } catch (e) {
-        message = String(e).slice(0, 30)
-      }
-      results.push({ email: account.email, success, message })
-      setRefreshProgress({ current: i + 1, total: accountsToRefresh.length, currentEmail: '', results: [...results] })
-      if (i < accountsToRefresh.length - 1) await new Promise(r => setTimeout(r, 500))
+        options: { refreshOnly: true, batchId },
+      })
+    } catch (e) {
+      console.error('批量刷新失败:', e)
+    } finally {
+      unlisten()
     }
 
-    setAccounts(updatedAccounts)
+    await loadAccounts()
     setLastRefreshTime(new Date().toLocaleTimeString())
     setTimeout(() => {
       setAutoRefreshing(false)
       setRefreshProgress({ current: 0, total: 0, currentEmail: '', results: [] })
     }, 1500)
-  }, [autoRefreshing, isExpiringSoon])
+  }, [autoRefreshing, isExpiringSoon, loadAccounts])
 
 
   const handleRefreshStatus = useCallback(async (id) => {
@@ -72,16 +73,20 @@ export function useAccounts() {
       return { success: true }
     } catch (e) {
       console.warn(e)
-      // 更新账号状态为错误信息
+      // 后端已记录失败状态（status / health / lastError），重新加载
       const errorMsg = String(e)
-      setAccounts(prev => prev.map(a => a.id === id ? { ...a, status: errorMsg.includes('401') || errorMsg.includes('过期') ? 'Token已失效' : '刷新失败' } : a))
+      try {
+        const list = await invoke('get_accounts')
+        setAccounts(list)
+      } catch {}
       return { success: false, error: errorMsg }
     } finally {
       setRefreshingId(null)
     }
   }, [])
 
-  const handleExport = useCallback(async (selectedIds = []) => {
+  // profile: metadata（不含凭证）| encrypted（口令加密）| full（明文凭证）
+  const handleExport = useCallback(async (selectedIds = [], profile = 'metadata', passphrase = null) => {
     try {
       const { save } = await import('@tauri-apps/plugin-dialog')
       const { writeTextFile } = await import('@tauri-apps/plugin-fs')
@@ -97,12 +102,18 @@ export function useAccounts() {
         title: '导出账号数据'
       })
       
-      if (!filePath) return // 用户取消
+      if (!filePath) return false // 用户取消
       
-      const json = await invoke('export_accounts', { ids: selectedIds.length > 0 ? selectedIds : null })
+      const json = await invoke('export_accounts_with_profile', {
+        ids: selectedIds.length > 0 ? selectedIds : null,
+        profile,
+        passphrase,
+      })
       await writeTextFile(filePath, json)
+      return true
     } catch (e) {
       console.error('导出失败:', e)
+      throw e
     }
   }, [])
 
@@ -114,6 +125,8 @@ export function useAccounts() {
   useEffect(() => {
     loadAccounts()
     const unlistenLoginSuccess = listen('login-success', () => loadAccounts())
+    // IDE 刷新 Token 后已写回账号列表
+    const unlistenTokenSynced = listen('kiro-token-synced', () => loadAccounts())
     const unlistenKiroLoginData = listen('kiro-login-data', async (event) => {
       try {
         const data = typeof event.payload === 'string' ? JSON.parse(event.payload) : event.payload
@@ -142,6 +155,7 @@ export function useAccounts() {
 
     return () => {
       unlistenLoginSuccess.then(fn => fn())
+      unlistenTokenSynced.then(fn => fn())
       unlistenKiroLoginData.then(fn => fn())
       clearInterval(interval)
     }
@@ -1,5 +1,6 @@
 import { useState, useCallback, useMemo, useEffect } from 'react'
 import { invoke } from '@tauri-apps/api/core'
+import { listen } from '@tauri-apps/api/event'
 import { useTheme } from '../../contexts/ThemeContext'
 import { useDialog } from '../../contexts/DialogContext'
 import { useI18n } from '../../i18n'
@@ -9,10 +10,13 @@ import AccountTable from './AccountTable'
 import AccountPagination from './AccountPagination'
 import AddAccountModal from './AddAccountModal'
 import ImportAccountModal from './ImportAccountModal'
+import ExportAccountModal from './ExportAccountModal'
 import RefreshProgressModal from './RefreshProgressModal'
 import AccountDetailModal from '../AccountDetailModal'
 import EditAccountModal from './EditAccountModal'
 import ConfirmDialog from './ConfirmDialog'
+import SwitchHistoryModal from './SwitchHistoryModal'
+import AccountProfileModal from './AccountProfileModal'
 
 function AccountManager() {
   const { colors } = useTheme()
@@ -24,20 +28,30 @@ function AccountManager() {
   const [currentPage, setCurrentPage] = useState(1)
   const [editingAccount, setEditingAccount] = useState(null)
   const [editingLabelAccount, setEditingLabelAccount] = useState(null)
+  const [profileAccount, setProfileAccount] = useState(null)
   const [showAddModal, setShowAddModal] = useState(false)
   const [showImportModal, setShowImportModal] = useState(false)
+  const [showExportModal, setShowExportModal] = useState(false)
+  const [showSwitchHistory, setShowSwitchHistory] = useState(false)
   const [copiedId, setCopiedId] = useState(null)
   
   // 切换账号弹窗状态
   const [switchDialog, setSwitchDialog] = useState(null) // { type, title, message, account }
   
-  // 当前登录的本地 token
-  const [localToken, setLocalToken] = useState(null)
-  
-  useEffect(() => {
-    invoke('get_kiro_local_token').then(setLocalToken).catch(() => setLocalToken(null))
+  // 当前 IDE 登录的账号
+  const [activeAccount, setActiveAccount] = useState(null)
+  const [importingActive, setImportingActive] = useState(false)
+
+  const loadActiveAccount = useCallback(() => {
+    invoke('get_active_account').then(setActiveAccount).catch(() => setActiveAccount(null))
   }, [])
 
+  useEffect(() => {
+    loadActiveAccount()
+    const unlisten = listen('active-account-changed', (event) => setActiveAccount(event.payload))
+    return () => { unlisten.then(fn => fn()) }
+  }, [loadActiveAccount])
+
   const {
     accounts,
     loadAccounts,
@@ -157,7 +171,9 @@ function AccountManager() {
         provider: account.provider || 'Google',
         authMethod,
         resetMachineId: shouldResetMachineId,
-        autoRestart: false
+        autoRestart: false,
+        // 切换后应用账号绑定的 IDE 配置
+        accountId: account.id,
       }
       
       if (isIdC) {
@@ -165,26 +181,28 @@ function AccountManager() {
         params.region = account.region || 'us-east-1'
         params.clientId = account.clientId || null
         params.clientSecret = account.clientSecret || null
+        params.startUrl = account.startUrl || null
       } else {
         params.profileArn = account.profileArn || 'arn:aws:codewhisperer:us-east-1:699475941385:profile/EHGA3GRVQMUK'
       }
       
-      await invoke('switch_kiro_account', { params })
+      const result = await invoke('switch_kiro_account', { params })
       
       // 更新当前账号标识
-      invoke('get_kiro_local_token').then(setLocalToken).catch(() => setLocalToken(null))
+      loadActiveAccount()
       
-      // 从 usage_data 获取配额信息
-      const usageData = account.usageData
-      const breakdown = usageData?.usage_breakdown_list?.[0] || usageData?.usageBreakdownList?.[0]
-      const used = breakdown?.current_usage ?? breakdown?.currentUsage ?? 0
-      const limit = breakdown?.usage_limit ?? breakdown?.usageLimit ?? 50
+      // 从 usage 获取配额信息
+      const breakdown = account.usage?.resources?.[0]
+      const used = breakdown?.currentUsage ?? 0
+      const limit = breakdown?.usageLimit ?? 50
       const remaining = limit - used
       const provider = account.provider || 'Unknown'
       setSwitchDialog({
         type: 'success',
         title: t('switch.success'),
-        message: `${account.email}\n\n📊 ${t('switch.quota')}: ${used}/${limit} (${t('switch.remaining')} ${remaining})\n🏷️ ${t('switch.type')}: ${provider}`,
+        message: `${account.email}\n\n📊 ${t('switch.quota')}: ${used}/${limit} (${t('switch.remaining')} ${remaining})\n🏷️ ${t('switch.type')}: ${provider}`
+          + (result?.profileApplied ? `\n⚙️ ${t('accountProfile.appliedOnSwitch')}` : '')
+          + (result?.profileError ? `\n⚠️ ${t('accountProfile.applyFailed')}: ${result.profileError}` : ''),
         account: null,
       })
     } catch (e) {
@@ -197,7 +215,21 @@ function AccountManager() {
     } finally {
       setSwitchingId(null)
     }
-  }, [switchDialog, setSwitchingId])
+  }, [switchDialog, setSwitchingId, loadActiveAccount, t])
+
+  // 一键导入 IDE 当前登录的账号
+  const handleImportActive = useCallback(async () => {
+    setImportingActive(true)
+    try {
+      await invoke('add_local_kiro_account')
+      await loadAccounts()
+      loadActiveAccount()
+    } catch (e) {
+      setSwitchDialog({ type: 'error', title: t('accounts.importActiveFailed'), message: String(e), account: null })
+    } finally {
+      setImportingActive(false)
+    }
+  }, [loadAccounts, loadActiveAccount, t])
 
   return (
     <div className={`h-full flex flex-col ${colors.main}`}>
@@ -208,12 +240,27 @@ function AccountManager() {
         onBatchDelete={onBatchDelete}
         onAdd={() => setShowAddModal(true)}
         onImport={() => setShowImportModal(true)}
-        onExport={() => handleExport(selectedIds)}
+        onExport={() => setShowExportModal(true)}
         onRefreshAll={() => autoRefreshAll(accounts, true)}
+        onShowSwitchHistory={() => setShowSwitchHistory(true)}
         autoRefreshing={autoRefreshing}
         lastRefreshTime={lastRefreshTime}
         refreshProgress={refreshProgress}
       />
+      {activeAccount?.importable && (
+        <div className={`mx-6 mt-4 px-4 py-3 rounded-xl border flex items-center justify-between gap-3 ${colors.card} ${colors.cardBorder}`}>
+          <span className={`text-sm ${colors.text}`}>
+            {t('accounts.activeNotInStore')}{activeAccount.detectedEmail ? `：${activeAccount.detectedEmail}` : ''}
+          </span>
+          <button
+            onClick={handleImportActive}
+            disabled={importingActive}
+            className="px-4 py-1.5 text-sm rounded-lg bg-blue-500 text-white hover:bg-blue-600 disabled:opacity-50 transition-colors"
+          >
+            {importingActive ? t('accounts.importing') : t('accounts.importActive')}
+          </button>
+        </div>
+      )}
       <div className="flex-1 overflow-auto">
       <AccountTable
         accounts={paginatedAccounts}
@@ -227,11 +274,12 @@ function AccountManager() {
         onRefresh={handleRefreshStatus}
         onEdit={setEditingAccount}
         onEditLabel={setEditingLabelAccount}
+        onEditProfile={setProfileAccount}
         onDelete={handleDelete}
         onAdd={() => setShowAddModal(true)}
         refreshingId={refreshingId}
         switchingId={switchingId}
-        localToken={localToken}
+        activeAccount={activeAccount}
       />
       </div>
       <div className="animate-slide-in-right delay-200">
@@ -253,7 +301,16 @@ function AccountManager() {
       {showAddModal && (<AddAccountModal onClose={() => setShowAddModal(false)} onSuccess={loadAccounts} />)}
       {editingLabelAccount && (<EditAccountModal account={editingLabelAccount} onClose={() => setEditingLabelAccount(null)} onSuccess={loadAccounts} />)}
       {showImportModal && (<ImportAccountModal onClose={() => setShowImportModal(false)} onSuccess={loadAccounts} />)}
+      {showExportModal && (
+        <ExportAccountModal
+          selectedCount={selectedIds.length}
+          onExport={(profile, passphrase) => handleExport(selectedIds, profile, passphrase)}
+          onClose={() => setShowExportModal(false)}
+        />
+      )}
       {autoRefreshing && (<RefreshProgressModal refreshProgress={refreshProgress} />)}
+      {profileAccount && (<AccountProfileModal account={profileAccount} onClose={() => setProfileAccount(null)} />)}
+      {showSwitchHistory && (<SwitchHistoryModal onClose={() => setShowSwitchHistory(false)} onReverted={loadActiveAccount} />)}
       
       {/* 切换账号弹窗 */}
       {switchDialog && (
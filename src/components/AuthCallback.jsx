@@ -23,7 +23,7 @@ export default function AuthCallback() {
         setMessage('正在交换访问令牌...')
 
         // 调用 Rust 处理回调
-        await invoke('handle_kiro_social_callback', { code, state })
+        await invoke('handle_kiro_social_callback', { code, callbackState: state })
 
         setStatus('success')
         setMessage('登录成功！账号已添加到应用中。')
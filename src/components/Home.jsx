@@ -315,18 +315,18 @@ function Home() {
 
         {/* 当前账号配额详情 */}
         {localToken && currentAccount && (() => {
-          const usageData = currentAccount.usageData
-          const breakdown = usageData?.usageBreakdownList?.[0] || usageData?.usageBreakdown
-          const subInfo = usageData?.subscriptionInfo
-          const userInfo = usageData?.userInfo
-          const overageConfig = usageData?.overageConfiguration
-          const freeTrial = breakdown?.freeTrialInfo
+          const usage = currentAccount.usage
+          const breakdown = usage?.resources?.[0]
+          const subInfo = usage?.subscription
+          const userInfo = usage?.user
+          const overageStatus = usage?.overageStatus
+          const freeTrial = breakdown?.freeTrial
           const bonuses = breakdown?.bonuses || []
           const mainUsed = breakdown?.currentUsage ?? 0
           const mainLimit = breakdown?.usageLimit ?? 0
           const mainPercent = mainLimit > 0 ? Math.round((mainUsed / mainLimit) * 100) : 0
-          const daysUntilReset = usageData?.daysUntilReset ?? 0
-          const nextDateReset = usageData?.nextDateReset
+          const daysUntilReset = usage?.daysUntilReset ?? 0
+          const nextDateReset = usage?.nextDateReset
           
           return (
             <div className={`card-glow ${colors.card} rounded-2xl shadow-sm border ${colors.cardBorder} overflow-hidden animate-scale-in delay-500`}>
@@ -348,7 +348,7 @@ function Home() {
                         subInfo.type.includes('PRO') ? 'bg-blue-500 text-white' :
                         (isDark ? 'bg-gray-600 text-gray-300' : 'bg-gray-200 text-gray-700')
                       }`}>
-                        {subInfo.subscriptionTitle || 'Free'}
+                        {subInfo.title || 'Free'}
                       </span>
                     )}
                   </div>
@@ -389,7 +389,7 @@ function Home() {
                       <div className="space-y-1.5 text-xs">
                         <div className="flex justify-between">
                           <span className={colors.textMuted}>{t('home.type')}</span>
-                          <span className={colors.text}>{subInfo.subscriptionTitle || '-'}</span>
+                          <span className={colors.text}>{subInfo.title || '-'}</span>
                         </div>
                         <div className="flex justify-between">
                           <span className={colors.textMuted}>{t('home.overage')}</span>
@@ -403,11 +403,11 @@ function Home() {
                             {subInfo.upgradeCapability === 'UPGRADE_CAPABLE' ? '✓' : '✗'}
                           </span>
                         </div>
-                        {overageConfig && (
+                        {overageStatus && (
                           <div className="flex justify-between">
                             <span className={colors.textMuted}>{t('home.status')}</span>
-                            <span className={`${overageConfig.overageStatus === 'ENABLED' ? 'text-green-500' : colors.textMuted}`}>
-                              {overageConfig.overageStatus === 'ENABLED' ? t('home.enabled') : t('home.disabled')}
+                            <span className={`${overageStatus === 'ENABLED' ? 'text-green-500' : colors.textMuted}`}>
+                              {overageStatus === 'ENABLED' ? t('home.enabled') : t('home.disabled')}
                             </span>
                           </div>
                         )}
@@ -427,10 +427,10 @@ function Home() {
                         <span className={colors.textMuted}>{t('home.reset')}</span>
                         <span className={colors.text}>{nextDateReset ? new Date(nextDateReset * 1000).toLocaleDateString() : '-'}</span>
                       </div>
-                      {breakdown?.overageRate && (
+                      {breakdown?.overage?.rate && (
                         <div className="flex justify-between">
                           <span className={colors.textMuted}>{t('home.rate')}</span>
-                          <span className={colors.text}>${breakdown.overageRate}/次</span>
+                          <span className={colors.text}>${breakdown.overage.rate}/次</span>
                         </div>
                       )}
                       <div className="flex justify-between">
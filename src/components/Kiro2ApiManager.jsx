@@ -28,7 +28,7 @@ function Kiro2ApiManager() {
   const [form, setForm] = useState(DEFAULTS)
   const [status, setStatus] = useState({
     running: false,
-    healthy: false,
+    health: null,
     pid: null,
     port: null,
     url: null,
@@ -403,7 +403,7 @@ function Kiro2ApiManager() {
             </div>
             <div className={`rounded-xl p-3 ${isDark ? 'bg-white/5' : 'bg-gray-50'}`}>
               <div className={colors.textMuted}>健康检查</div>
-              <div className={`${colors.text} font-semibold`}>{status.healthy ? '正常' : '不可达'}</div>
+              <div className={`${colors.text} font-semibold`}>{status.health?.healthy ? '正常' : '不可达'}</div>
             </div>
             <div className={`rounded-xl p-3 ${isDark ? 'bg-white/5' : 'bg-gray-50'}`}>
               <div className={colors.textMuted}>PID</div>
@@ -1,7 +1,7 @@
-import { useState, useEffect } from 'react'
+import { useState, useEffect, useRef } from 'react'
 import { invoke } from '@tauri-apps/api/core'
 import { listen } from '@tauri-apps/api/event'
-import { Loader, ArrowRight, X } from 'lucide-react'
+import { Loader, ArrowRight, X, Copy, Check } from 'lucide-react'
 import { useTheme } from '../contexts/ThemeContext'
 import { useI18n } from '../i18n.jsx'
 
@@ -11,6 +11,15 @@ function Login({ onLogin }) {
   const isDark = theme === 'dark'
   const [loadingProvider, setLoadingProvider] = useState(null)
   const [error, setError] = useState('')
+  const [showEnterprise, setShowEnterprise] = useState(false)
+  const [startUrl, setStartUrl] = useState('')
+  const [ssoRegion, setSsoRegion] = useState('us-east-1')
+  // 设备码登录会话（BuilderId / Enterprise）
+  const [deviceSession, setDeviceSession] = useState(null)
+  // 事件回调中读取当前会话，只处理本窗口发起的会话
+  const deviceSessionRef = useRef(null)
+  deviceSessionRef.current = deviceSession
+  const [codeCopied, setCodeCopied] = useState(false)
 
   useEffect(() => {
     const unlistenSuccess = listen('login-success', (event) => {
@@ -18,13 +27,31 @@ function Login({ onLogin }) {
       setLoadingProvider(null)
       onLogin?.(event.payload)
     })
-    return () => { unlistenSuccess.then(fn => fn()) }
-  }, [onLogin])
+    const unlistenDevice = listen('device-login-complete', (event) => {
+      const { sessionId, success, cancelled, error: err } = event.payload || {}
+      if (!deviceSessionRef.current || sessionId !== deviceSessionRef.current.sessionId) return
+      setDeviceSession(null)
+      if (!success) {
+        setLoadingProvider(null)
+        if (!cancelled) setError(err || t('login.failed'))
+      }
+    })
+    return () => {
+      unlistenSuccess.then(fn => fn())
+      unlistenDevice.then(fn => fn())
+    }
+  }, [onLogin, t])
 
   const handleLogin = async (provider) => {
     setLoadingProvider(provider)
     setError('')
     try {
+      if (provider === 'BuilderId' || provider === 'Enterprise') {
+        const extra = provider === 'Enterprise' ? { startUrl: startUrl.trim(), region: ssoRegion.trim() } : {}
+        const session = await invoke('start_device_login', { provider, ...extra })
+        setDeviceSession(session)
+        return
+      }
       await invoke('kiro_login', { provider })
     } catch (e) {
       console.error('Login error:', e)
@@ -34,10 +61,20 @@ function Login({ onLogin }) {
   }
 
   const handleCancel = () => {
+    if (deviceSession) {
+      invoke('cancel_device_login', { sessionId: deviceSession.sessionId }).catch(() => {})
+      setDeviceSession(null)
+    }
     setLoadingProvider(null)
     setError('')
   }
 
+  const handleCopyCode = () => {
+    navigator.clipboard.writeText(deviceSession.userCode)
+    setCodeCopied(true)
+    setTimeout(() => setCodeCopied(false), 1500)
+  }
+
   const providers = [
     {
       id: 'Google',
@@ -119,6 +156,56 @@ function Login({ onLogin }) {
             </button>
           ))}
 
+          {/* 企业 IAM Identity Center */}
+          <button
+            onClick={() => setShowEnterprise(v => !v)}
+            disabled={!!loadingProvider}
+            className={`w-full flex items-center justify-center gap-3 px-5 py-4 ${isDark ? 'bg-white/5 border-white/10' : 'bg-white border-gray-200'} border rounded-xl transition-all duration-200 disabled:opacity-50 hover:border-orange-400 hover:shadow-lg active:scale-[0.98]`}
+          >
+            {loadingProvider === 'Enterprise' ? <Loader size={20} className="animate-spin text-purple-500" /> : <span className="text-[#ff9900] font-bold text-lg">aws</span>}
+            <span className={`${colors.text} font-medium`}>{t('login.enterprise')}</span>
+          </button>
+          {showEnterprise && (
+            <div className={`space-y-2 p-3 ${isDark ? 'bg-white/5 border-white/10' : 'bg-white border-gray-200'} border rounded-xl`}>
+              <input
+                type="text"
+                value={startUrl}
+                onChange={(e) => setStartUrl(e.target.value)}
+                placeholder={`${t('login.startUrl')}: https://d-xxxxxxxxxx.awsapps.com/start`}
+                className={`w-full px-3 py-2 border rounded-lg text-sm ${colors.text} ${isDark ? 'bg-white/5 border-white/10' : 'bg-white border-gray-200'} focus:outline-none focus:ring-2 focus:ring-orange-500/30`}
+              />
+              <input
+                type="text"
+                value={ssoRegion}
+                onChange={(e) => setSsoRegion(e.target.value)}
+                placeholder={t('login.ssoRegion')}
+                className={`w-full px-3 py-2 border rounded-lg text-sm ${colors.text} ${isDark ? 'bg-white/5 border-white/10' : 'bg-white border-gray-200'} focus:outline-none focus:ring-2 focus:ring-orange-500/30`}
+              />
+              <button
+                onClick={() => handleLogin('Enterprise')}
+                disabled={!!loadingProvider || !startUrl.trim()}
+                className="w-full px-4 py-2 bg-orange-500 text-white rounded-lg text-sm font-medium hover:bg-orange-600 disabled:opacity-50"
+              >
+                {t('login.signIn')}
+              </button>
+            </div>
+          )}
+
+          {/* 设备码：浏览器未自动打开时可手动复制 */}
+          {deviceSession && (
+            <div className={`p-4 ${isDark ? 'bg-white/5 border-white/10' : 'bg-white border-gray-200'} border rounded-xl text-center space-y-2`}>
+              <div className={`text-xs ${colors.textMuted}`}>{t('login.userCode')}</div>
+              <button onClick={handleCopyCode} className={`inline-flex items-center gap-2 font-mono text-2xl font-bold tracking-widest ${colors.text}`}>
+                {deviceSession.userCode}
+                {codeCopied ? <Check size={16} className="text-green-500" /> : <Copy size={16} className={colors.textMuted} />}
+              </button>
+              <a href={deviceSession.verificationUriComplete || deviceSession.verificationUri} target="_blank" rel="noopener noreferrer" className="block text-xs text-purple-500 hover:underline break-all">
+                {deviceSession.verificationUri}
+              </a>
+              <div className={`text-xs ${colors.textMuted}`}>{t('login.userCodeHint')}</div>
+            </div>
+          )}
+
           {/* 取消按钮 */}
           {loadingProvider && (
             <button
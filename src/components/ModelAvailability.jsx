@@ -0,0 +1,100 @@
+import { useState, useEffect, useCallback } from 'react'
+import { invoke } from '@tauri-apps/api/core'
+import { RefreshCw, ChevronDown, ChevronRight } from 'lucide-react'
+import { useTheme } from '../contexts/ThemeContext'
+import { useI18n } from '../i18n.jsx'
+
+// 各模型在全部账号中的可用情况（来自各账号的模型目录缓存）
+function ModelAvailability({ onRefreshed }) {
+  const { theme, colors } = useTheme()
+  const { t } = useI18n()
+  const isDark = theme === 'dark'
+
+  const [report, setReport] = useState(null)
+  const [emails, setEmails] = useState({})
+  const [expanded, setExpanded] = useState(null)
+  const [refreshing, setRefreshing] = useState(false)
+
+  const load = useCallback(async (refresh = false) => {
+    const [result, accounts] = await Promise.all([
+      invoke('get_model_availability', { refresh }).catch(() => null),
+      invoke('get_accounts').catch(() => []),
+    ])
+    setReport(result)
+    setEmails(Object.fromEntries(accounts.map(a => [a.id, a.email])))
+  }, [])
+
+  useEffect(() => { load() }, [load])
+
+  const handleRefresh = async () => {
+    setRefreshing(true)
+    try {
+      await load(true)
+      onRefreshed?.()
+    } finally {
+      setRefreshing(false)
+    }
+  }
+
+  const totalAccounts = report ? report.cachedAccounts + report.uncachedAccountIds.length : 0
+  const errorCount = report ? Object.keys(report.errors).length : 0
+
+  return (
+    <div className={`mt-5 p-4 rounded-xl ${isDark ? 'bg-white/5' : 'bg-gray-50'}`}>
+      <div className="flex items-center justify-between mb-3">
+        <div>
+          <span className={`text-sm font-medium ${colors.text}`}>{t('modelCatalog.availability')}</span>
+          {report && (
+            <p className={`text-xs ${colors.textMuted} mt-0.5`}>
+              {t('modelCatalog.cachedAccounts', { cached: report.cachedAccounts, total: totalAccounts })}
+              {errorCount > 0 && <span className="text-red-500"> · {t('modelCatalog.fetchErrors', { count: errorCount })}</span>}
+            </p>
+          )}
+        </div>
+        <button
+          onClick={handleRefresh}
+          disabled={refreshing}
+          className={`px-3 py-1.5 rounded-lg text-xs flex items-center gap-1.5 ${isDark ? 'bg-white/10 hover:bg-white/20' : 'bg-white hover:bg-gray-100 border border-gray-200'} ${colors.text} disabled:opacity-50`}
+        >
+          <RefreshCw size={12} className={refreshing ? 'animate-spin' : ''} />
+          {t('modelCatalog.refresh')}
+        </button>
+      </div>
+
+      {!report || report.models.length === 0 ? (
+        <p className={`text-xs ${colors.textMuted}`}>{t('modelCatalog.empty')}</p>
+      ) : (
+        <div className="space-y-1">
+          {report.models.map(model => {
+            const isOpen = expanded === model.modelId
+            const missing = report.cachedAccounts - model.accountIds.length
+            return (
+              <div key={model.modelId}>
+                <button
+                  onClick={() => setExpanded(isOpen ? null : model.modelId)}
+                  className={`w-full flex items-center gap-2 px-2 py-1.5 rounded-lg text-left ${isDark ? 'hover:bg-white/5' : 'hover:bg-gray-100'}`}
+                >
+                  {isOpen ? <ChevronDown size={12} className={colors.textMuted} /> : <ChevronRight size={12} className={colors.textMuted} />}
+                  <span className={`text-xs font-mono ${colors.text} truncate flex-1`}>
+                    {model.modelId}
+                    {model.rateMultiplier != null && <span className={colors.textMuted}> · {model.rateMultiplier}x</span>}
+                  </span>
+                  <span className={`text-xs flex-shrink-0 ${missing > 0 ? 'text-orange-500' : 'text-green-500'}`}>
+                    {model.accountIds.length}/{report.cachedAccounts}
+                  </span>
+                </button>
+                {isOpen && (
+                  <div className={`ml-6 mb-1 text-xs ${colors.textMuted} space-y-0.5`}>
+                    {model.accountIds.map(id => <div key={id} className="truncate">{emails[id] || id}</div>)}
+                  </div>
+                )}
+              </div>
+            )
+          })}
+        </div>
+      )}
+    </div>
+  )
+}
+
+export default ModelAvailability
@@ -5,6 +5,8 @@ import { Lock, Copy, Sun, Moon, Palette, Check, RefreshCw, Settings as SettingsI
 import { useTheme } from '../contexts/ThemeContext'
 import { useDialog } from '../contexts/DialogContext'
 import { useI18n } from '../i18n.jsx'
+import SettingsProfiles from './SettingsProfiles'
+import ModelAvailability from './ModelAvailability'
 
 function Settings() {
   const { theme, setTheme, colors } = useTheme()
@@ -14,6 +16,8 @@ function Settings() {
   
   const [aiModel, setAiModel] = useState('claude-sonnet-4-6-20260217-thinking')
   const [lockModel, setLockModel] = useState(true)
+  const [modelCatalog, setModelCatalog] = useState(null) // 当前账号可用的模型，null 表示未获取
+  const [catalogAccountId, setCatalogAccountId] = useState(null) // 模型列表对应的账号，设置模型时按它校验
   const [autoRefresh, setAutoRefresh] = useState(true)
   const [autoRefreshInterval, setAutoRefreshInterval] = useState(50) // 分钟
   const [autoChangeMachineId, setAutoChangeMachineId] = useState(false)
@@ -28,6 +32,15 @@ function Settings() {
   const [detectedBrowsers, setDetectedBrowsers] = useState([])
   const [showBrowserList, setShowBrowserList] = useState(false)
   const [detectingProxy, setDetectingProxy] = useState(false)
+  const [loopbackCallback, setLoopbackCallback] = useState(false)
+  const [ideTokenSync, setIdeTokenSync] = useState(true)
+  const [ideTokenConflictPolicy, setIdeTokenConflictPolicy] = useState('newest')
+  const [kiroDataDir, setKiroDataDir] = useState('')
+  const [originalKiroDataDir, setOriginalKiroDataDir] = useState('')
+  const [kiroPaths, setKiroPaths] = useState(null)
+  const [kiroIdePath, setKiroIdePath] = useState('')
+  const [originalKiroIdePath, setOriginalKiroIdePath] = useState('')
+  const [kiroShutdownTimeout, setKiroShutdownTimeout] = useState(10) // 秒
   
   // Kiro IDE 状态
   const [loading, setLoading] = useState(false)
@@ -44,11 +57,13 @@ function Settings() {
   const loadSettings = async () => {
     setLoading(true)
     try {
-      const [kiroSettings, appSettings, sysMachine] = await Promise.all([
+      const [kiroSettings, appSettings, sysMachine, paths] = await Promise.all([
         invoke('get_kiro_settings').catch(() => null),
         invoke('get_app_settings').catch(() => null),
-        invoke('get_system_machine_guid').catch(() => null)
+        invoke('get_system_machine_guid').catch(() => null),
+        invoke('get_kiro_paths').catch(() => null)
       ])
+      setKiroPaths(paths)
       setSystemMachineInfo(sysMachine)
       if (sysMachine?.backupExists) {
         const backup = await invoke('get_machine_guid_backup').catch(() => null)
@@ -71,6 +86,14 @@ function Settings() {
         const browser = appSettings.browserPath || ''
         setBrowserPath(browser)
         setOriginalBrowserPath(browser)
+        setLoopbackCallback(appSettings.oauthCallbackMode === 'loopback')
+        setIdeTokenSync(appSettings.ideTokenSync ?? true)
+        setIdeTokenConflictPolicy(appSettings.ideTokenConflictPolicy || 'newest')
+        setKiroDataDir(appSettings.kiroDataDir || '')
+        setOriginalKiroDataDir(appSettings.kiroDataDir || '')
+        setKiroIdePath(appSettings.kiroIdePath || '')
+        setOriginalKiroIdePath(appSettings.kiroIdePath || '')
+        setKiroShutdownTimeout(appSettings.kiroShutdownTimeout ?? 10)
       }
     } catch (err) {
       console.error('Failed to load settings:', err)
@@ -80,8 +103,18 @@ function Settings() {
     }
   }
 
+  // 读取当前 IDE 登录账号的模型目录（有缓存时不发请求）
+  const loadModelCatalog = async () => {
+    const active = await invoke('get_active_account').catch(() => null)
+    if (!active?.accountId) return
+    const catalog = await invoke('get_account_models', { accountId: active.accountId }).catch(() => null)
+    setModelCatalog(catalog?.models?.length ? catalog.models : null)
+    setCatalogAccountId(active.accountId)
+  }
+
   useEffect(() => {
     loadSettings()
+    loadModelCatalog()
   }, [])
 
   // 保存应用设置（后端已实现增量更新，直接传入要更新的字段）
@@ -114,15 +147,18 @@ function Settings() {
   const proxyChanged = httpProxy !== originalProxy
 
   const handleApplyModel = async (model) => {
+    const previousModel = aiModel
     setAiModel(model)
     setSavingModel(true)
     try {
-      await invoke('set_kiro_model', { model })
+      await invoke('set_kiro_model', { model, accountId: catalogAccountId })
       // 如果锁定模型，保存到应用设置
       if (lockModel) {
-        await saveAppSettings({ locked_model: model })
+        await saveAppSettings({ lockedModel: model })
       }
     } catch (err) {
+      // 模型不在可用列表中时后端会拒绝，恢复原来的选择
+      setAiModel(previousModel)
       await showError(t('settings.saveFailed'), t('settings.saveFailed') + ': ' + err)
     } finally {
       setSavingModel(false)
@@ -131,7 +167,7 @@ function Settings() {
 
   const handleLockModelChange = async (checked) => {
     setLockModel(checked)
-    await saveAppSettings({ lock_model: checked, locked_model: checked ? aiModel : null })
+    await saveAppSettings({ lockModel: checked, lockedModel: checked ? aiModel : null })
   }
 
   const handleAutoRefreshChange = async (checked) => {
@@ -156,6 +192,21 @@ function Settings() {
     await saveAppSettings({ bindMachineIdToAccount: checked })
   }
 
+  const handleLoopbackCallbackChange = async (checked) => {
+    setLoopbackCallback(checked)
+    await saveAppSettings({ oauthCallbackMode: checked ? 'loopback' : 'deepLink' })
+  }
+
+  const handleIdeTokenSyncChange = async (checked) => {
+    setIdeTokenSync(checked)
+    await saveAppSettings({ ideTokenSync: checked })
+  }
+
+  const handleIdeTokenConflictPolicyChange = async (policy) => {
+    setIdeTokenConflictPolicy(policy)
+    await saveAppSettings({ ideTokenConflictPolicy: policy })
+  }
+
   const handleApplyBrowser = async () => {
     setSavingBrowser(true)
     try {
@@ -171,6 +222,25 @@ function Settings() {
 
   const browserChanged = browserPath !== originalBrowserPath
 
+  const handleApplyKiroDataDir = async () => {
+    await saveAppSettings({ kiroDataDir: kiroDataDir.trim() })
+    setOriginalKiroDataDir(kiroDataDir.trim())
+    // 路径变化后重新读取 Kiro IDE 设置
+    loadSettings()
+  }
+
+  const handleApplyKiroIdePath = async () => {
+    await saveAppSettings({ kiroIdePath: kiroIdePath.trim() })
+    setOriginalKiroIdePath(kiroIdePath.trim())
+    checkKiroStatus()
+  }
+
+  const handleKiroShutdownTimeoutChange = async (value) => {
+    const timeout = parseInt(value) || 10
+    setKiroShutdownTimeout(timeout)
+    await saveAppSettings({ kiroShutdownTimeout: timeout })
+  }
+
   const handleDetectBrowsers = async () => {
     try {
       const browsers = await invoke('detect_installed_browsers')
@@ -218,12 +288,14 @@ function Settings() {
   }
 
   const [kiroRunning, setKiroRunning] = useState(false)
+  const [kiroIdeInfo, setKiroIdeInfo] = useState(null)
 
   // 检查 Kiro IDE 运行状态
   const checkKiroStatus = async () => {
     try {
-      const running = await invoke('is_kiro_ide_running')
-      setKiroRunning(running)
+      const info = await invoke('get_kiro_ide_info')
+      setKiroIdeInfo(info)
+      setKiroRunning(info.running)
     } catch (err) {
       console.error('Failed to check Kiro status:', err)
     }
@@ -427,14 +499,30 @@ function Settings() {
                 disabled={savingModel}
                 className={`w-full px-4 py-3 border rounded-xl ${colors.text} ${colors.input} ${colors.inputFocus} focus:ring-2 appearance-none cursor-pointer disabled:opacity-50 transition-all`}
               >
-                <option value="claude-sonnet-4-6-20260217-thinking">Claude Sonnet 4.6 Thinking - 1.3x (⭐ {t('common.recommended')})</option>
-                <option value="claude-sonnet-4-6-20260217">Claude Sonnet 4.6 - 1.3x</option>
-                <option value="claude-sonnet-4.6">Claude Sonnet 4.6 (Legacy Alias) - 1.3x</option>
-                <option value="claude-sonnet-4.5">Claude Sonnet 4.5 - 1.3x</option>
-                <option value="claude-sonnet-4">Claude Sonnet 4 - 1.3x</option>
-                <option value="claude-haiku-4.5">Claude Haiku 4.5 - 0.4x</option>
-                <option value="claude-opus-4.6">Claude Opus 4.6 - 2.2x</option>
-                <option value="claude-opus-4.5">Claude Opus 4.5 - 2.2x</option>
+                {modelCatalog ? (
+                  <>
+                    {modelCatalog.map(m => (
+                      <option key={m.modelId} value={m.modelId}>
+                        {m.modelName || m.modelId}{m.rateMultiplier != null ? ` - ${m.rateMultiplier}x` : ''}
+                      </option>
+                    ))}
+                    {/* 当前设置的模型不在账号的模型目录中 */}
+                    {!modelCatalog.some(m => m.modelId === aiModel) && (
+                      <option value={aiModel} disabled>{aiModel} ({t('modelCatalog.unavailable')})</option>
+                    )}
+                  </>
+                ) : (
+                  <>
+                    <option value="claude-sonnet-4-6-20260217-thinking">Claude Sonnet 4.6 Thinking - 1.3x (⭐ {t('common.recommended')})</option>
+                    <option value="claude-sonnet-4-6-20260217">Claude Sonnet 4.6 - 1.3x</option>
+                    <option value="claude-sonnet-4.6">Claude Sonnet 4.6 (Legacy Alias) - 1.3x</option>
+                    <option value="claude-sonnet-4.5">Claude Sonnet 4.5 - 1.3x</option>
+                    <option value="claude-sonnet-4">Claude Sonnet 4 - 1.3x</option>
+                    <option value="claude-haiku-4.5">Claude Haiku 4.5 - 0.4x</option>
+                    <option value="claude-opus-4.6">Claude Opus 4.6 - 2.2x</option>
+                    <option value="claude-opus-4.5">Claude Opus 4.5 - 2.2x</option>
+                  </>
+                )}
               </select>
               <div className="absolute right-4 top-1/2 -translate-y-1/2 pointer-events-none">
                 <svg width="12" height="12" viewBox="0 0 12 12" fill="none">
@@ -457,6 +545,8 @@ function Settings() {
               <p className={`text-xs ${colors.textMuted} mt-0.5`}>{t('settings.lockModelDesc')}</p>
             </div>
           </label>
+
+          <ModelAvailability onRefreshed={loadModelCatalog} />
         </section>
 
         {/* 账号设置 */}
@@ -523,6 +613,35 @@ function Settings() {
               </div>
             </label>
           )}
+
+          <label className={`flex items-start gap-3 cursor-pointer mt-3 ${isDark ? 'bg-white/5 hover:bg-white/10' : 'bg-gray-50 hover:bg-gray-100'} rounded-xl p-4 transition-all hover:scale-[1.01]`}>
+            <input
+              type="checkbox"
+              checked={ideTokenSync}
+              onChange={(e) => handleIdeTokenSyncChange(e.target.checked)}
+              className="mt-0.5 w-4 h-4 rounded-lg border-gray-300 text-blue-500 focus:ring-blue-500"
+            />
+            <RefreshCw size={16} className={`${colors.textMuted} mt-0.5 flex-shrink-0`} />
+            <div>
+              <span className={`text-sm font-medium ${colors.text}`}>{t('settings.ideTokenSync')}</span>
+              <p className={`text-xs ${colors.textMuted} mt-0.5`}>{t('settings.ideTokenSyncDesc')}</p>
+            </div>
+          </label>
+
+          {ideTokenSync && (
+            <div className="ml-7 mt-3">
+              <label className={`block text-sm ${colors.textMuted} mb-2`}>{t('settings.ideTokenConflictPolicy')}</label>
+              <select
+                value={ideTokenConflictPolicy}
+                onChange={(e) => handleIdeTokenConflictPolicyChange(e.target.value)}
+                className={`w-full px-4 py-3 border rounded-xl ${colors.text} ${colors.input} ${colors.inputFocus} focus:ring-2 transition-all`}
+              >
+                <option value="newest">{t('settings.conflictNewest')}</option>
+                <option value="ide">{t('settings.conflictIde')}</option>
+                <option value="manager">{t('settings.conflictManager')}</option>
+              </select>
+            </div>
+          )}
         </section>
 
         {/* 浏览器设置 */}
@@ -612,6 +731,19 @@ function Settings() {
           <p className={`text-xs ${colors.textMuted} mt-3`}>
             {t('settings.browserTip')}
           </p>
+
+          <label className={`flex items-start gap-3 cursor-pointer mt-4 ${isDark ? 'bg-white/5 hover:bg-white/10' : 'bg-gray-50 hover:bg-gray-100'} rounded-xl p-4 transition-all`}>
+            <input
+              type="checkbox"
+              checked={loopbackCallback}
+              onChange={(e) => handleLoopbackCallbackChange(e.target.checked)}
+              className="mt-0.5 w-4 h-4 rounded-lg border-gray-300 text-blue-500 focus:ring-blue-500"
+            />
+            <div>
+              <span className={`text-sm font-medium ${colors.text}`}>{t('settings.loopbackCallback')}</span>
+              <p className={`text-xs ${colors.textMuted} mt-0.5`}>{t('settings.loopbackCallbackDesc')}</p>
+            </div>
+          </label>
         </section>
 
         {/* 代理设置 */}
@@ -692,6 +824,12 @@ function Settings() {
               }`}>
                 {kiroRunning ? t('settings.running') : t('settings.notRunning')}
               </span>
+              {kiroIdeInfo?.version && (
+                <span className={`text-xs ${colors.textMuted}`}>v{kiroIdeInfo.version}</span>
+              )}
+              {kiroRunning && kiroIdeInfo?.pids?.length > 0 && (
+                <span className={`text-xs ${colors.textMuted}`}>PID {kiroIdeInfo.pids[0]}</span>
+              )}
             </div>
             <button
               onClick={handleToggleKiro}
@@ -704,8 +842,78 @@ function Settings() {
               {kiroRunning ? t('settings.stop') : t('settings.start')}
             </button>
           </div>
+
+          <div className="mt-4">
+            <label className={`block text-sm ${colors.textMuted} mb-2`}>{t('settings.kiroIdePath')}</label>
+            <div className="flex gap-3">
+              <input
+                type="text"
+                value={kiroIdePath}
+                onChange={(e) => setKiroIdePath(e.target.value)}
+                placeholder={kiroIdeInfo?.path || t('settings.kiroIdePathPlaceholder')}
+                className={`flex-1 px-4 py-3 border rounded-xl ${colors.text} ${colors.input} ${colors.inputFocus} focus:ring-2 transition-all`}
+              />
+              <button
+                onClick={handleApplyKiroIdePath}
+                disabled={kiroIdePath === originalKiroIdePath}
+                className="btn-icon px-5 py-3 rounded-xl flex items-center gap-2 font-medium bg-blue-500 text-white hover:bg-blue-600 disabled:opacity-50 disabled:cursor-not-allowed transition-all"
+              >
+                <Check size={16} />
+                {t('settings.apply')}
+              </button>
+            </div>
+            {kiroIdeInfo && (
+              <p className={`text-xs mt-2 ${kiroIdeInfo.path ? colors.textMuted : 'text-orange-500'}`}>
+                {kiroIdeInfo.path ? `${kiroIdeInfo.path} (${kiroIdeInfo.source})` : t('settings.kiroIdeNotFound')}
+              </p>
+            )}
+          </div>
+
+          <div className="mt-4">
+            <label className={`block text-sm ${colors.textMuted} mb-2`}>{t('settings.kiroShutdownTimeout')}</label>
+            <select
+              value={kiroShutdownTimeout}
+              onChange={(e) => handleKiroShutdownTimeoutChange(e.target.value)}
+              className={`w-full px-4 py-2 border rounded-xl ${colors.text} ${colors.input} ${colors.inputFocus} focus:ring-2 appearance-none cursor-pointer transition-all`}
+            >
+              <option value="5">5 {t('common.seconds')}</option>
+              <option value="10">10 {t('common.seconds')} ({t('common.recommended')})</option>
+              <option value="30">30 {t('common.seconds')}</option>
+              <option value="60">60 {t('common.seconds')}</option>
+            </select>
+            <p className={`text-xs mt-2 ${colors.textMuted}`}>{t('settings.kiroShutdownTimeoutDesc')}</p>
+          </div>
+
+          <div className="mt-4">
+            <label className={`block text-sm ${colors.textMuted} mb-2`}>{t('settings.kiroDataDir')}</label>
+            <div className="flex gap-3">
+              <input
+                type="text"
+                value={kiroDataDir}
+                onChange={(e) => setKiroDataDir(e.target.value)}
+                placeholder={kiroPaths?.dataDir || t('settings.kiroDataDirPlaceholder')}
+                className={`flex-1 px-4 py-3 border rounded-xl ${colors.text} ${colors.input} ${colors.inputFocus} focus:ring-2 transition-all`}
+              />
+              <button
+                onClick={handleApplyKiroDataDir}
+                disabled={kiroDataDir === originalKiroDataDir}
+                className="btn-icon px-5 py-3 rounded-xl flex items-center gap-2 font-medium bg-blue-500 text-white hover:bg-blue-600 disabled:opacity-50 disabled:cursor-not-allowed transition-all"
+              >
+                <Check size={16} />
+                {t('settings.apply')}
+              </button>
+            </div>
+            {kiroPaths && (
+              <p className={`text-xs mt-2 ${kiroPaths.exists ? colors.textMuted : 'text-orange-500'}`}>
+                {kiroPaths.settingsPath || '-'} ({kiroPaths.source}){!kiroPaths.exists && ` - ${t('settings.kiroDataDirMissing')}`}
+              </p>
+            )}
+          </div>
         </section>
 
+        {/* IDE 设置方案 */}
+        <SettingsProfiles onApplied={loadSettings} />
+
         {/* 系统机器码管理 */}
         <section className={`card-glow ${colors.card} rounded-2xl p-6 shadow-sm border ${colors.cardBorder} mb-6 animate-slide-in-left delay-600`}>
           <div className="flex items-center gap-2 mb-1">
@@ -0,0 +1,252 @@
+import { useState, useEffect, useCallback } from 'react'
+import { invoke } from '@tauri-apps/api/core'
+import { Layers, Plus, Trash2, Pencil, GitCompare, Check, RotateCcw, X } from 'lucide-react'
+import { useTheme } from '../contexts/ThemeContext'
+import { useDialog } from '../contexts/DialogContext'
+import { useI18n } from '../i18n.jsx'
+
+const STATUS_COLORS = {
+  added: 'text-green-500',
+  changed: 'text-blue-500',
+  removed: 'text-red-500',
+  unchanged: '',
+}
+
+// Kiro IDE 设置方案：一组 settings.json 键值，可对比、应用、撤销
+function SettingsProfiles({ onApplied }) {
+  const { theme, colors } = useTheme()
+  const { showConfirm, showError, showSuccess } = useDialog()
+  const { t } = useI18n()
+  const isDark = theme === 'dark'
+
+  const [profiles, setProfiles] = useState([])
+  const [applied, setApplied] = useState([])
+  const [editing, setEditing] = useState(null) // { id, name, description, settingsText }
+  const [diff, setDiff] = useState(null) // { id, items }
+  const [busy, setBusy] = useState(false)
+
+  const load = useCallback(async () => {
+    const [list, history] = await Promise.all([
+      invoke('get_settings_profiles').catch(() => []),
+      invoke('get_applied_settings_profiles').catch(() => []),
+    ])
+    setProfiles(list)
+    setApplied(history)
+  }, [])
+
+  useEffect(() => { load() }, [load])
+
+  const handleNew = async () => {
+    // 以当前 settings.json 的 kiroAgent.* 设置作为初始内容
+    const current = await invoke('get_all_kiro_settings').catch(() => ({}))
+    const initial = Object.fromEntries(Object.entries(current).filter(([k]) => k.startsWith('kiroAgent.')))
+    setEditing({ id: null, name: '', description: '', settingsText: JSON.stringify(initial, null, 2) })
+  }
+
+  const handleEdit = (profile) => {
+    setEditing({
+      id: profile.id,
+      name: profile.name,
+      description: profile.description || '',
+      settingsText: JSON.stringify(profile.settings, null, 2),
+    })
+  }
+
+  const handleSave = async () => {
+    let settings
+    try {
+      settings = JSON.parse(editing.settingsText || '{}')
+      if (!settings || typeof settings !== 'object' || Array.isArray(settings)) throw new Error(t('settingsProfiles.mustBeObject'))
+    } catch (e) {
+      await showError(t('settingsProfiles.invalidJson'), String(e.message || e))
+      return
+    }
+    try {
+      await invoke('save_settings_profile', {
+        profile: { id: editing.id, name: editing.name, description: editing.description || null, settings }
+      })
+      setEditing(null)
+      load()
+    } catch (e) {
+      await showError(t('settings.saveFailed'), String(e))
+    }
+  }
+
+  const handleDelete = async (profile) => {
+    const ok = await showConfirm(t('settingsProfiles.delete'), t('settingsProfiles.deleteConfirm', { name: profile.name }))
+    if (!ok) return
+    await invoke('delete_settings_profile', { id: profile.id }).catch(e => showError(t('common.error'), String(e)))
+    if (diff?.id === profile.id) setDiff(null)
+    load()
+  }
+
+  const handleDiff = async (profile) => {
+    if (diff?.id === profile.id) {
+      setDiff(null)
+      return
+    }
+    try {
+      setDiff({ id: profile.id, items: await invoke('diff_settings_profile', { id: profile.id }) })
+    } catch (e) {
+      await showError(t('common.error'), String(e))
+    }
+  }
+
+  const handleApply = async (profile) => {
+    setBusy(true)
+    try {
+      const changes = await invoke('apply_settings_profile', { id: profile.id })
+      await showSuccess(t('settingsProfiles.applied'), t('settingsProfiles.appliedDesc', { count: changes.length }))
+      setDiff(null)
+      load()
+      onApplied?.()
+    } catch (e) {
+      await showError(t('settingsProfiles.applyFailed'), String(e))
+    } finally {
+      setBusy(false)
+    }
+  }
+
+  const handleRevert = async () => {
+    setBusy(true)
+    try {
+      const reverted = await invoke('revert_settings_profile')
+      await showSuccess(t('settingsProfiles.reverted'), reverted.profileName)
+      setDiff(null)
+      load()
+      onApplied?.()
+    } catch (e) {
+      await showError(t('settingsProfiles.revertFailed'), String(e))
+    } finally {
+      setBusy(false)
+    }
+  }
+
+  const formatValue = (value) => value === null || value === undefined ? '-' : JSON.stringify(value)
+
+  return (
+    <section className={`card-glow ${colors.card} rounded-2xl p-6 shadow-sm border ${colors.cardBorder} mb-6 animate-slide-in-left delay-500`}>
+      <div className="flex items-center justify-between mb-5">
+        <div>
+          <div className="flex items-center gap-2 mb-1">
+            <Layers size={18} className="text-blue-500" />
+            <h2 className={`text-lg font-semibold ${colors.text}`}>{t('settingsProfiles.title')}</h2>
+          </div>
+          <p className={`text-sm ${colors.textMuted}`}>{t('settingsProfiles.desc')}</p>
+        </div>
+        <div className="flex gap-2">
+          <button
+            onClick={handleRevert}
+            disabled={busy || applied.length === 0}
+            title={applied[0] ? `${applied[0].profileName} (${applied[0].appliedAt})` : ''}
+            className={`btn-icon px-3 py-2 rounded-xl text-sm flex items-center gap-1.5 ${isDark ? 'bg-white/10 hover:bg-white/20' : 'bg-gray-100 hover:bg-gray-200'} ${colors.text} disabled:opacity-50 disabled:cursor-not-allowed`}
+          >
+            <RotateCcw size={14} />
+            {t('settingsProfiles.revertLast')}
+          </button>
+          <button
+            onClick={handleNew}
+            className="btn-icon px-3 py-2 rounded-xl text-sm flex items-center gap-1.5 bg-blue-500 text-white hover:bg-blue-600"
+          >
+            <Plus size={14} />
+            {t('settingsProfiles.new')}
+          </button>
+        </div>
+      </div>
+
+      {editing && (
+        <div className={`mb-4 p-4 rounded-xl ${isDark ? 'bg-white/5' : 'bg-gray-50'} space-y-3`}>
+          <input
+            type="text"
+            value={editing.name}
+            onChange={(e) => setEditing({ ...editing, name: e.target.value })}
+            placeholder={t('settingsProfiles.name')}
+            className={`w-full px-4 py-2 border rounded-xl ${colors.text} ${colors.input} ${colors.inputFocus} focus:ring-2`}
+          />
+          <input
+            type="text"
+            value={editing.description}
+            onChange={(e) => setEditing({ ...editing, description: e.target.value })}
+            placeholder={t('settingsProfiles.description')}
+            className={`w-full px-4 py-2 border rounded-xl ${colors.text} ${colors.input} ${colors.inputFocus} focus:ring-2`}
+          />
+          <textarea
+            value={editing.settingsText}
+            onChange={(e) => setEditing({ ...editing, settingsText: e.target.value })}
+            rows={8}
+            spellCheck={false}
+            className={`w-full px-4 py-2 border rounded-xl font-mono text-xs ${colors.text} ${colors.input} ${colors.inputFocus} focus:ring-2`}
+          />
+          <p className={`text-xs ${colors.textMuted}`}>{t('settingsProfiles.nullHint')}</p>
+          <div className="flex justify-end gap-2">
+            <button onClick={() => setEditing(null)} className={`px-4 py-2 rounded-xl text-sm ${colors.textMuted} ${isDark ? 'hover:bg-white/10' : 'hover:bg-gray-100'}`}>
+              {t('common.cancel')}
+            </button>
+            <button
+              onClick={handleSave}
+              disabled={!editing.name.trim()}
+              className="px-4 py-2 rounded-xl text-sm bg-blue-500 text-white hover:bg-blue-600 disabled:opacity-50 flex items-center gap-1.5"
+            >
+              <Check size={14} />
+              {t('common.save')}
+            </button>
+          </div>
+        </div>
+      )}
+
+      {profiles.length === 0 && !editing ? (
+        <p className={`text-sm text-center ${colors.textMuted} py-4`}>{t('settingsProfiles.empty')}</p>
+      ) : (
+        <div className="space-y-2">
+          {profiles.map(profile => (
+            <div key={profile.id} className={`p-3 rounded-xl ${isDark ? 'bg-white/5' : 'bg-gray-50'}`}>
+              <div className="flex items-center justify-between gap-3">
+                <div className="min-w-0">
+                  <div className={`text-sm font-medium ${colors.text} truncate`}>{profile.name}</div>
+                  <div className={`text-xs ${colors.textMuted} truncate`}>
+                    {t('settingsProfiles.keyCount', { count: Object.keys(profile.settings).length })}
+                    {profile.description ? ` · ${profile.description}` : ''}
+                  </div>
+                </div>
+                <div className="flex items-center gap-1 flex-shrink-0">
+                  <button onClick={() => handleDiff(profile)} title={t('settingsProfiles.diff')} className={`p-1.5 rounded-lg ${isDark ? 'hover:bg-white/10' : 'hover:bg-gray-200'}`}>
+                    {diff?.id === profile.id ? <X size={14} className={colors.textMuted} /> : <GitCompare size={14} className={colors.textMuted} />}
+                  </button>
+                  <button onClick={() => handleEdit(profile)} title={t('common.edit')} className={`p-1.5 rounded-lg ${isDark ? 'hover:bg-white/10' : 'hover:bg-gray-200'}`}>
+                    <Pencil size={14} className={colors.textMuted} />
+                  </button>
+                  <button onClick={() => handleDelete(profile)} title={t('settingsProfiles.delete')} className={`p-1.5 rounded-lg ${isDark ? 'hover:bg-white/10' : 'hover:bg-gray-200'}`}>
+                    <Trash2 size={14} className="text-red-500" />
+                  </button>
+                  <button
+                    onClick={() => handleApply(profile)}
+                    disabled={busy}
+                    className="ml-1 px-3 py-1.5 rounded-lg text-xs bg-blue-500 text-white hover:bg-blue-600 disabled:opacity-50"
+                  >
+                    {t('settingsProfiles.apply')}
+                  </button>
+                </div>
+              </div>
+
+              {diff?.id === profile.id && (
+                <div className={`mt-3 pt-3 border-t ${colors.cardBorder} space-y-1`}>
+                  {diff.items.map(item => (
+                    <div key={item.key} className="text-xs font-mono flex gap-2">
+                      <span className={`w-20 flex-shrink-0 ${STATUS_COLORS[item.status] || colors.textMuted}`}>{t(`settingsProfiles.status.${item.status}`)}</span>
+                      <span className={`${colors.text} truncate`}>{item.key}</span>
+                      <span className={`${colors.textMuted} truncate`}>
+                        {item.status === 'unchanged' ? formatValue(item.current) : `${formatValue(item.current)} → ${formatValue(item.desired)}`}
+                      </span>
+                    </div>
+                  ))}
+                </div>
+              )}
+            </div>
+          ))}
+        </div>
+      )}
+    </section>
+  )
+}
+
+export default SettingsProfiles
@@ -1,33 +1,24 @@
 // 账号统计计算工具函数
 
-// 从 account 获取 quota（兼容旧数据和新 usageData）
-// API 返回 camelCase，后端 serde 序列化也是 camelCase
-// 兼容 usageBreakdownList（数组）和 usageBreakdown（单个对象）
-const getBreakdown = (a) => {
-  return a.usageData?.usageBreakdownList?.[0] || a.usageData?.usageBreakdown || null
-}
+// 从 account.usage（后端归一化后的用量）获取 quota
+// 主资源即 resources[0]，额度 = 主额度 + 免费试用 + 奖励
+const getPrimaryResource = (a) => a.usage?.resources?.[0] || null
 const getQuota = (a) => {
-  const breakdown = getBreakdown(a)
-  const main = breakdown?.usageLimit ?? breakdown?.usage_limit ?? a.quota ?? 50
-  // 兼容 camelCase 和 snake_case
-  const freeTrialInfo = breakdown?.freeTrialInfo || breakdown?.free_trial_info
-  const freeTrial = freeTrialInfo?.usageLimit ?? freeTrialInfo?.usage_limit ?? 0
-  const bonuses = breakdown?.bonuses || []
-  const bonus = bonuses.reduce((sum, b) => sum + (b.usageLimit || b.usage_limit || 0), 0)
+  const resource = getPrimaryResource(a)
+  const main = resource?.usageLimit ?? a.quota ?? 50
+  const freeTrial = resource?.freeTrial?.usageLimit ?? 0
+  const bonus = (resource?.bonuses || []).reduce((sum, b) => sum + (b.usageLimit || 0), 0)
   return main + freeTrial + bonus
 }
 const getUsed = (a) => {
-  const breakdown = getBreakdown(a)
-  const main = breakdown?.currentUsage ?? breakdown?.current_usage ?? a.used ?? 0
-  // 兼容 camelCase 和 snake_case
-  const freeTrialInfo = breakdown?.freeTrialInfo || breakdown?.free_trial_info
-  const freeTrial = freeTrialInfo?.currentUsage ?? freeTrialInfo?.current_usage ?? 0
-  const bonuses = breakdown?.bonuses || []
-  const bonus = bonuses.reduce((sum, b) => sum + (b.currentUsage || b.current_usage || 0), 0)
+  const resource = getPrimaryResource(a)
+  const main = resource?.currentUsage ?? a.used ?? 0
+  const freeTrial = resource?.freeTrial?.currentUsage ?? 0
+  const bonus = (resource?.bonuses || []).reduce((sum, b) => sum + (b.currentUsage || 0), 0)
   return main + freeTrial + bonus
 }
-const getSubType = (a) => a.usageData?.subscriptionInfo?.type ?? a.subscriptionType ?? ''
-const getSubPlan = (a) => a.usageData?.subscriptionInfo?.subscriptionTitle ?? a.subscriptionPlan ?? ''
+const getSubType = (a) => a.usage?.subscription?.type ?? a.subscriptionType ?? ''
+const getSubPlan = (a) => a.usage?.subscription?.title ?? a.subscriptionPlan ?? ''
 
 export function calcAccountStats(accounts) {
   const total = accounts.length
@@ -49,4 +40,4 @@ export function getUsagePercent(used, quota) {
   return quota === 0 ? 0 : Math.min(100, (used / quota) * 100)
 }
 
-export { getQuota, getUsed, getSubType, getSubPlan }
+export { getPrimaryResource, getQuota, getUsed, getSubType, getSubPlan }